@@ -80,6 +80,8 @@ export function MintCredential() {
           errorMsg = 'Credential metadata is too large (max 4KB)';
         } else if (error.message.includes('TooManyCredentials')) {
           errorMsg = 'Maximum credential limit reached (500 per account)';
+        } else if (error.message.includes('RateLimited') || error.message.includes('Custom error: 1')) {
+          errorMsg = 'Too many credentials minted recently (10 per minute, 100 per hour)';
         } else if (error.message.includes('Cancelled')) {
           errorMsg = 'Transaction was cancelled';
         } else if (error.message.includes('balance')) {
@@ -51,6 +51,7 @@ export interface PortfolioExport {
     account_url: string;
     total_credentials: number;
     credential_transactions: CredentialTransaction[];
+    storage_proof?: VerifiablePortfolio;
   };
   metadata: {
     app_version: string;
@@ -59,6 +60,32 @@ export interface PortfolioExport {
   };
 }
 
+// A storage entry proven against a block's state root (see credentials_exportPortfolio)
+export interface ProvenStorageEntry {
+  key: string;
+  value: string | null;
+  proof: string[];
+}
+
+// Response of the node's credentials_exportPortfolio RPC
+export interface VerifiablePortfolio {
+  account: string;
+  at: string;
+  header: {
+    parentHash: string;
+    number: string;
+    stateRoot: string;
+    extrinsicsRoot: string;
+    digest: { logs: string[] };
+  };
+  ownerCredentials: ProvenStorageEntry;
+  credentials: {
+    credentialId: string;
+    metadata: string;
+    entry: ProvenStorageEntry;
+  }[];
+}
+
 export interface CredentialTransaction {
   credential_id: string;
   credential_name: string;
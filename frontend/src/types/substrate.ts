@@ -2,7 +2,7 @@
 // This file contains type definitions that match the Substrate pallet schema
 
 import type { Codec } from '@polkadot/types/types';
-import type { Vec, Option, U8aFixed, Bytes } from '@polkadot/types';
+import type { Vec, Option, U8aFixed, Bytes, u32 } from '@polkadot/types';
 import type { AccountId32, H256 } from '@polkadot/types/interfaces';
 import type { Observable } from 'rxjs';
 import type { 
@@ -16,6 +16,7 @@ import type {
 export interface PalletFreelanceCredentialsCredential extends Codec {
   readonly owner: AccountId32;
   readonly metadata: Bytes;
+  readonly mintedAt: u32;
 }
 
 export interface PalletFreelanceCredentialsError extends Codec {
@@ -26,21 +27,56 @@ export interface PalletFreelanceCredentialsError extends Codec {
   readonly isNotCredentialOwner: boolean;
 }
 
+export type PalletFreelanceCredentialsCredentialType =
+  | 'Skill'
+  | 'Review'
+  | 'Payment'
+  | 'Certification'
+  | 'Other';
+
+export type PalletFreelanceCredentialsVisibility = 'Public' | 'Private';
+
+export type PalletFreelanceCredentialsCredentialField =
+  | 'CredentialType'
+  | 'Name'
+  | 'Description'
+  | 'Issuer'
+  | 'Timestamp'
+  | 'Visibility'
+  | 'Rating'
+  | 'ProofHash'
+  | 'Other'
+  | 'SkillId';
+
 export interface PalletFreelanceCredentialsEvent extends Codec {
   readonly isCredentialMinted: boolean;
   readonly asCredentialMinted: {
     readonly credentialId: H256;
     readonly owner: AccountId32;
+    readonly credentialType: PalletFreelanceCredentialsCredentialType;
+    readonly contentHash: H256;
+    readonly metadataLen: u32;
+    readonly visibility: PalletFreelanceCredentialsVisibility;
+    readonly issuer: Bytes;
   };
   readonly isCredentialUpdated: boolean;
   readonly asCredentialUpdated: {
     readonly credentialId: H256;
     readonly owner: AccountId32;
+    readonly credentialType: PalletFreelanceCredentialsCredentialType;
+    readonly contentHash: H256;
+    readonly metadataLen: u32;
+    readonly visibility: PalletFreelanceCredentialsVisibility;
+    readonly issuer: Bytes;
+    readonly changedFields: PalletFreelanceCredentialsCredentialField[];
   };
   readonly isCredentialDeleted: boolean;
   readonly asCredentialDeleted: {
     readonly credentialId: H256;
     readonly owner: AccountId32;
+    readonly credentialType: PalletFreelanceCredentialsCredentialType;
+    readonly contentHash: H256;
+    readonly issuer: Bytes;
   };
 }
 
@@ -112,18 +148,64 @@ declare module '@polkadot/api-base/types/events' {
     freelanceCredentials: {
       CredentialMinted: AugmentedEvent<
         ApiType,
-        [credentialId: H256, owner: AccountId32],
-        { credentialId: H256; owner: AccountId32 }
+        [
+          credentialId: H256,
+          owner: AccountId32,
+          credentialType: Codec,
+          contentHash: H256,
+          metadataLen: u32,
+          visibility: Codec,
+          issuer: Bytes
+        ],
+        {
+          credentialId: H256;
+          owner: AccountId32;
+          credentialType: Codec;
+          contentHash: H256;
+          metadataLen: u32;
+          visibility: Codec;
+          issuer: Bytes;
+        }
       >;
       CredentialUpdated: AugmentedEvent<
         ApiType,
-        [credentialId: H256, owner: AccountId32],
-        { credentialId: H256; owner: AccountId32 }
+        [
+          credentialId: H256,
+          owner: AccountId32,
+          credentialType: Codec,
+          contentHash: H256,
+          metadataLen: u32,
+          visibility: Codec,
+          issuer: Bytes,
+          changedFields: Vec<Codec>
+        ],
+        {
+          credentialId: H256;
+          owner: AccountId32;
+          credentialType: Codec;
+          contentHash: H256;
+          metadataLen: u32;
+          visibility: Codec;
+          issuer: Bytes;
+          changedFields: Vec<Codec>;
+        }
       >;
       CredentialDeleted: AugmentedEvent<
         ApiType,
-        [credentialId: H256, owner: AccountId32],
-        { credentialId: H256; owner: AccountId32 }
+        [
+          credentialId: H256,
+          owner: AccountId32,
+          credentialType: Codec,
+          contentHash: H256,
+          issuer: Bytes
+        ],
+        {
+          credentialId: H256;
+          owner: AccountId32;
+          credentialType: Codec;
+          contentHash: H256;
+          issuer: Bytes;
+        }
       >;
     };
   }
@@ -2,7 +2,7 @@ import { ApiPromise, WsProvider } from '@polkadot/api';
 import { web3Enable, web3FromAddress } from '@polkadot/extension-dapp';
 import { performanceMonitor, PerformanceMetrics } from './performance';
 
-import type { Credential } from '../types';
+import type { Credential, VerifiablePortfolio } from '../types';
 
 // API Error types
 export const ApiErrorType = {
@@ -31,6 +31,20 @@ export class ApiError extends Error {
   }
 }
 
+// Node RPC methods that are not part of the standard Substrate set
+const CUSTOM_RPC = {
+  credentials: {
+    exportPortfolio: {
+      description: 'Export an account\'s credentials with storage proofs',
+      params: [
+        { name: 'account', type: 'AccountId' },
+        { name: 'at', type: 'BlockHash', isOptional: true },
+      ],
+      type: 'Json',
+    },
+  },
+};
+
 // Configuration interface
 interface ApiConfig {
   endpoints: string[];
@@ -39,6 +53,11 @@ interface ApiConfig {
   retryDelay: number;
 }
 
+/**
+ * Proxy types of the runtime that restrict proxies to credential calls
+ */
+export type CredentialProxyType = 'CredentialManagement' | 'NonDelete';
+
 // Transaction result interface
 export interface TransactionResult {
   hash: string;
@@ -169,6 +188,7 @@ class FreelanceForgeAPI {
           const api = await Promise.race([
             ApiPromise.create({
               provider,
+              rpc: CUSTOM_RPC,
               throwOnConnect: true, // Throw errors immediately instead of retrying
               // Add runtime version compatibility
               noInitWarn: true,
@@ -304,6 +324,21 @@ class FreelanceForgeAPI {
     }
   }
 
+  /**
+   * Extract owner and metadata bytes from a `Credentials` storage value.
+   * Storage version 1 stores a `CredentialInfo` struct; older nodes return an (owner, metadata) tuple.
+   */
+  private decodeCredentialRecord(json: unknown): [string, number[] | string] | null {
+    if (Array.isArray(json) && json.length === 2) {
+      return json as [string, number[] | string];
+    }
+    if (json && typeof json === 'object' && 'owner' in json && 'metadata' in json) {
+      const record = json as { owner: string; metadata: number[] | string };
+      return [record.owner, record.metadata];
+    }
+    return null;
+  }
+
   /**
    * Parse simple string format: type|name|issuer|timestamp|visibility
    */
@@ -405,7 +440,8 @@ class FreelanceForgeAPI {
    */
   async mintCredential(
     accountAddress: string,
-    credentialData: CredentialMetadata
+    credentialData: CredentialMetadata,
+    onBehalfOf?: string
   ): Promise<TransactionResult> {
     // Create a unique transaction key to prevent duplicates
     const transactionKey = `mint_${accountAddress}_${credentialData.name}_${credentialData.timestamp}`;
@@ -462,6 +498,10 @@ class FreelanceForgeAPI {
         let tx: any;
         try {
           tx = api.tx.freelanceCredentials.mintCredential(metadataString);
+          if (onBehalfOf) {
+            // Signed by a proxy (e.g. a credentials-only hot key) of the owner
+            tx = api.tx.proxy.proxy(onBehalfOf, null, tx);
+          }
           console.log('Transaction created successfully');
           console.log('Transaction method:', tx.method.toHuman());
         } catch (txError) {
@@ -513,12 +553,26 @@ class FreelanceForgeAPI {
               const errorEvent = result.events?.find(({ event }: any) =>
                 event && api.events.system.ExtrinsicFailed.is(event)
               );
-
+              // A proxied extrinsic succeeds even if the call it wraps fails: the inner
+              // result is only reported by `proxy.ProxyExecuted`
+              const proxyEvent = onBehalfOf
+                ? result.events?.find(({ event }: any) =>
+                  event && api.events.proxy.ProxyExecuted.is(event)
+                )
+                : undefined;
+              const proxyResult = proxyEvent?.event?.data?.[0] as any;
+
+              let dispatchError: any;
               if (errorEvent && errorEvent.event && errorEvent.event.data) {
-                const [dispatchError] = errorEvent.event.data;
+                [dispatchError] = errorEvent.event.data;
+              } else if (proxyResult?.isErr) {
+                dispatchError = proxyResult.asErr;
+              }
+
+              if (dispatchError) {
                 let errorMessage = 'Transaction failed';
 
-                if (dispatchError && typeof dispatchError === 'object' && 'isModule' in dispatchError) {
+                if (typeof dispatchError === 'object' && 'isModule' in dispatchError) {
                   try {
                     const decoded = api.registry.findMetaError((dispatchError as any).asModule);
                     errorMessage = `${decoded.section}.${decoded.name}: ${decoded.docs}`;
@@ -791,6 +845,88 @@ class FreelanceForgeAPI {
     });
   }
 
+  /**
+   * Let `delegate`, e.g. a browser hot key, manage the credentials of `accountAddress` through
+   * `pallet_proxy`. `CredentialManagement` proxies can make any credentials call but never move
+   * funds; `NonDelete` proxies additionally cannot delete, purge or migrate credentials.
+   */
+  async addCredentialProxy(
+    accountAddress: string,
+    delegate: string,
+    proxyType: CredentialProxyType = 'CredentialManagement'
+  ): Promise<TransactionResult> {
+    const api = await this.connect();
+    return this.signAndWatch(accountAddress, api.tx.proxy.addProxy(delegate, proxyType, 0));
+  }
+
+  /**
+   * Revoke a proxy added with `addCredentialProxy`
+   */
+  async removeCredentialProxy(
+    accountAddress: string,
+    delegate: string,
+    proxyType: CredentialProxyType = 'CredentialManagement'
+  ): Promise<TransactionResult> {
+    const api = await this.connect();
+    return this.signAndWatch(accountAddress, api.tx.proxy.removeProxy(delegate, proxyType, 0));
+  }
+
+  /**
+   * Sign `tx` with `accountAddress` and resolve once it is finalized
+   */
+  private async signAndWatch(accountAddress: string, tx: any): Promise<TransactionResult> {
+    const api = await this.connect();
+    await web3Enable('FreelanceForge');
+    const injector = await web3FromAddress(accountAddress);
+
+    return new Promise<TransactionResult>((resolve, reject) => {
+      let isResolved = false; // Prevent multiple resolutions
+
+      tx.signAndSend(accountAddress, { signer: injector.signer }, (result: any) => {
+        if (isResolved) return;
+
+        if (result.status.isFinalized) {
+          isResolved = true;
+          const errorEvent = result.events.find(({ event }: any) =>
+            api.events.system.ExtrinsicFailed.is(event)
+          );
+
+          if (errorEvent) {
+            const [dispatchError] = errorEvent.event.data;
+            let errorMessage = 'Transaction failed';
+            if (dispatchError && typeof dispatchError === 'object' && 'isModule' in dispatchError) {
+              try {
+                const decoded = api.registry.findMetaError((dispatchError as any).asModule);
+                errorMessage = `${decoded.section}.${decoded.name}: ${decoded.docs}`;
+              } catch (decodeError) {
+                console.warn('Failed to decode error:', decodeError);
+              }
+            }
+            reject(new ApiError(ApiErrorType.TRANSACTION_FAILED, errorMessage));
+          } else {
+            resolve({
+              hash: result.txHash.toString(),
+              blockHash: result.status.asFinalized.toString(),
+              success: true,
+            });
+          }
+        } else if (result.isError) {
+          isResolved = true;
+          reject(new ApiError(ApiErrorType.TRANSACTION_FAILED, 'Transaction failed'));
+        }
+      }).catch((error: unknown) => {
+        const errorMessage = error instanceof Error ? error.message : 'Unknown error';
+        if (errorMessage.includes('Cancelled')) {
+          reject(new ApiError(ApiErrorType.TRANSACTION_FAILED, 'Transaction cancelled by user'));
+        } else if (errorMessage.includes('balance')) {
+          reject(new ApiError(ApiErrorType.INSUFFICIENT_BALANCE, 'Insufficient balance for transaction'));
+        } else {
+          reject(new ApiError(ApiErrorType.TRANSACTION_FAILED, errorMessage, error instanceof Error ? error : undefined));
+        }
+      });
+    });
+  }
+
   /**
    * Get all credentials for a specific account
    */
@@ -842,11 +978,12 @@ class FreelanceForgeAPI {
 
               try {
                 const credentialJson = credentialData.toJSON();
-                if (!Array.isArray(credentialJson) || credentialJson.length !== 2) {
+                const record = this.decodeCredentialRecord(credentialJson);
+                if (!record) {
                   console.warn(`Invalid credential data format for ${credentialId}:`, credentialJson);
                   continue;
                 }
-                [owner, metadataBytes] = credentialJson as [string, number[]];
+                [owner, metadataBytes] = record as [string, number[]];
               } catch (parseError) {
                 console.warn(`Failed to parse credential data for ${credentialId}:`, parseError);
                 continue;
@@ -977,7 +1114,11 @@ class FreelanceForgeAPI {
           return null;
         }
 
-        const [owner, metadataBytes] = credentialData.toJSON() as [string, number[] | string];
+        const record = this.decodeCredentialRecord(credentialData.toJSON());
+        if (!record) {
+          throw new Error('Invalid credential data format');
+        }
+        const [owner, metadataBytes] = record;
 
         // Convert bytes back to JSON string
         let uint8Array: Uint8Array;
@@ -1031,6 +1172,16 @@ class FreelanceForgeAPI {
     });
   }
 
+  /**
+   * Export an account's credentials with storage proofs, verifiable against the
+   * returned block header's state root
+   */
+  async exportVerifiablePortfolio(accountAddress: string, at?: string): Promise<VerifiablePortfolio> {
+    const api = await this.connect();
+    const result = await (api.rpc as any).credentials.exportPortfolio(accountAddress, at);
+    return result.toJSON() as VerifiablePortfolio;
+  }
+
   /**
    * Get network information
    */
@@ -1057,11 +1208,12 @@ export async function connectToBlockchain(): Promise<ApiPromise> {
 
 export async function mintCredential(
   accountAddress: string,
-  credentialData: CredentialMetadata
+  credentialData: CredentialMetadata,
+  onBehalfOf?: string
 ): Promise<TransactionResult> {
   return performanceMonitor.measure(
     PerformanceMetrics.API_MINT_CREDENTIAL,
-    () => freelanceForgeAPI.mintCredential(accountAddress, credentialData),
+    () => freelanceForgeAPI.mintCredential(accountAddress, credentialData, onBehalfOf),
     { accountAddress, credentialType: credentialData.credential_type }
   );
 }
@@ -1077,6 +1229,22 @@ export async function updateCredential(
   return freelanceForgeAPI.updateCredential(accountAddress, credentialId, updates);
 }
 
+export async function addCredentialProxy(
+  accountAddress: string,
+  delegate: string,
+  proxyType?: CredentialProxyType
+): Promise<TransactionResult> {
+  return freelanceForgeAPI.addCredentialProxy(accountAddress, delegate, proxyType);
+}
+
+export async function removeCredentialProxy(
+  accountAddress: string,
+  delegate: string,
+  proxyType?: CredentialProxyType
+): Promise<TransactionResult> {
+  return freelanceForgeAPI.removeCredentialProxy(accountAddress, delegate, proxyType);
+}
+
 export async function deleteCredential(
   accountAddress: string,
   credentialId: string
@@ -1098,4 +1266,11 @@ export async function getCredentialById(credentialId: string): Promise<Credentia
 
 export async function getNetworkInfo() {
   return freelanceForgeAPI.getNetworkInfo();
+}
+
+export async function exportVerifiablePortfolio(
+  accountAddress: string,
+  at?: string
+): Promise<VerifiablePortfolio> {
+  return freelanceForgeAPI.exportVerifiablePortfolio(accountAddress, at);
 }
\ No newline at end of file
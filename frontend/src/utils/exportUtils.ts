@@ -5,11 +5,11 @@ import type {
   CredentialTransaction,
   ExportStats 
 } from '../types';
-import { getNetworkInfo } from './api';
+import { exportVerifiablePortfolio, getNetworkInfo } from './api';
 
 // App version - in a real app this would come from package.json
 const APP_VERSION = '1.0.0';
-const EXPORT_FORMAT_VERSION = '1.0';
+const EXPORT_FORMAT_VERSION = '1.1';
 
 /**
  * Generate blockchain explorer URLs based on network
@@ -132,6 +132,18 @@ export async function generatePortfolioExport(
   
   // Generate credential transaction data
   const credentialTransactions = generateCredentialTransactions(exportCredentials, network);
+
+  // Storage proofs let third parties check the export against a trusted state root.
+  // They cover every on-chain credential of the account, so they are omitted for
+  // public-only exports to avoid disclosing private credentials.
+  let storageProof;
+  if (!publicOnly) {
+    try {
+      storageProof = await exportVerifiablePortfolio(walletAddress);
+    } catch (error) {
+      console.warn('Failed to fetch storage proofs, exporting without them:', error);
+    }
+  }
   
   // Create export data
   const exportData: PortfolioExport = {
@@ -145,6 +157,7 @@ export async function generatePortfolioExport(
       account_url,
       total_credentials: exportCredentials.length,
       credential_transactions: credentialTransactions,
+      storage_proof: storageProof,
     },
     metadata: {
       app_version: APP_VERSION,
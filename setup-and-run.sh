@@ -364,7 +364,7 @@ run_application() {
     # Start Substrate node in background
     print_status "Starting Substrate node..."
     cd substrate-node
-    cargo run --release -p minimal-template-node -- --dev --rpc-external &
+    cargo run --release -p minimal-template-node -- --dev --rpc-external --enable-offchain-indexing true &
     SUBSTRATE_PID=$!
     cd ..
     
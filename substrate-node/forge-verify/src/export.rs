@@ -0,0 +1,57 @@
+//! Portfolio export format.
+//!
+//! Produced by the node's `credentials_exportPortfolio` RPC and embedded by the frontend
+//! under `blockchain_verification.storage_proof` in downloaded portfolios.
+
+use polkadot_sdk::{
+	sp_core::{Bytes, H256},
+	sp_runtime::{generic, traits::BlakeTwo256, AccountId32},
+};
+use serde::{Deserialize, Serialize};
+
+/// Header of a FreelanceForge block.
+pub type Header = generic::Header<u32, BlakeTwo256>;
+
+/// A storage entry together with a proof of its value.
+///
+/// `proof` holds the trie nodes, as returned by `state_getReadProof`, proving that `key`
+/// maps to `value` (or is absent when `value` is `None`) under the exported block's state
+/// root.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenEntry {
+	/// Raw storage key.
+	pub key: Bytes,
+	/// SCALE-encoded value stored under `key`.
+	pub value: Option<Bytes>,
+	/// Trie nodes proving the value.
+	pub proof: Vec<Bytes>,
+}
+
+/// A credential in a portfolio export.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedCredential {
+	/// ID of the credential.
+	pub credential_id: H256,
+	/// Metadata of the credential, for convenience. Verifiers must decode it from `entry`.
+	pub metadata: String,
+	/// Proven `Credentials` entry of the credential.
+	pub entry: ProvenEntry,
+}
+
+/// A verifiable export of an account's credentials.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioExport {
+	/// Account whose credentials are exported.
+	pub account: AccountId32,
+	/// Hash of the block the export was read at.
+	pub at: H256,
+	/// Header of that block, which commits to the state root the proofs verify against.
+	pub header: Header,
+	/// Proven `OwnerCredentials` entry of the account.
+	pub owner_credentials: ProvenEntry,
+	/// Every credential listed in `owner_credentials`.
+	pub credentials: Vec<ExportedCredential>,
+}
@@ -0,0 +1,18 @@
+//! # FreelanceForge portfolio verifier
+//!
+//! Verifies portfolio exports produced by the node's `credentials_exportPortfolio` RPC
+//! without running a node: every storage proof is checked against the state root of a
+//! block the reader trusts, and the proven `pallet-freelance-credentials` storage values
+//! are decoded to tell authentic credentials from revoked, missing or forged ones.
+//!
+//! [`vc`] renders exported credentials as W3C Verifiable Credentials whose proof is the
+//! storage proof, for systems that consume the VC Data Model.
+//!
+//! The `forge-verify` binary wraps [`verify::verify`] for use from the command line.
+
+pub mod export;
+pub mod vc;
+pub mod verify;
+
+pub use export::PortfolioExport;
+pub use verify::{verify, CredentialStatus, Report, TrustAnchor};
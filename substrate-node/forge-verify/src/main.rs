@@ -0,0 +1,163 @@
+//! `forge-verify`: check a FreelanceForge portfolio export against a trusted block.
+//!
+//! Accepts either the raw output of the `credentials_exportPortfolio` RPC or a portfolio
+//! downloaded from the frontend, whose `blockchain_verification.storage_proof` holds it.
+//! Credentials shown by a frontend portfolio but not held on chain are reported as
+//! revoked.
+//!
+//! With `--format vc`, the authentic credentials are printed as a JSON array of W3C
+//! Verifiable Credentials instead of the text report.
+//!
+//! Exits with status 0 when every credential is authentic, 1 when some are not and 2 when
+//! the export cannot be verified at all.
+
+use clap::{Parser, ValueEnum};
+use forge_verify::{
+	export::Header, vc, verify, CredentialStatus, PortfolioExport, Report, TrustAnchor,
+};
+use polkadot_sdk::sp_core::H256;
+use serde_json::Value;
+use std::{path::PathBuf, process::ExitCode};
+
+#[derive(Debug, Parser)]
+#[command(name = "forge-verify", about = "Verify a FreelanceForge portfolio export offline")]
+struct Cli {
+	/// Portfolio export (JSON) to verify.
+	file: PathBuf,
+
+	/// Hash of a block you trust, e.g. read from a block explorer. The export must have
+	/// been taken at this block.
+	#[arg(long, value_name = "HASH", conflicts_with = "header", required_unless_present = "header")]
+	block_hash: Option<H256>,
+
+	/// Header of a block you trust, as JSON returned by `chain_getHeader`.
+	#[arg(long, value_name = "FILE")]
+	header: Option<PathBuf>,
+
+	/// Output format.
+	#[arg(long, value_enum, default_value_t = Format::Text)]
+	format: Format,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+	/// Human-readable verification report.
+	Text,
+	/// Authentic credentials as W3C Verifiable Credentials (JSON).
+	Vc,
+}
+
+/// Storage proofs and claimed credential IDs extracted from an export file.
+fn parse_export(file: Value) -> Result<(PortfolioExport, Vec<H256>), String> {
+	// Frontend portfolios embed the RPC output and list the credentials they display
+	if let Some(storage_proof) = file.pointer("/blockchain_verification/storage_proof") {
+		let export = serde_json::from_value(storage_proof.clone())
+			.map_err(|e| format!("invalid storage proof: {e}"))?;
+		let claimed = file
+			.get("credentials")
+			.and_then(Value::as_array)
+			.into_iter()
+			.flatten()
+			.filter_map(|credential| credential.get("id")?.as_str()?.parse().ok())
+			.collect();
+		return Ok((export, claimed));
+	}
+
+	if file.get("blockchain_verification").is_some() {
+		return Err("portfolio was exported without storage proofs".into());
+	}
+
+	let export = serde_json::from_value(file).map_err(|e| format!("invalid export: {e}"))?;
+	Ok((export, Vec::new()))
+}
+
+fn read_json(path: &PathBuf) -> Result<Value, String> {
+	let contents =
+		std::fs::read_to_string(path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+	serde_json::from_str(&contents).map_err(|e| format!("{} is not valid JSON: {e}", path.display()))
+}
+
+fn run(cli: Cli) -> Result<bool, String> {
+	let (export, claimed) = parse_export(read_json(&cli.file)?)?;
+
+	let anchor = match (cli.block_hash, cli.header) {
+		(Some(hash), _) => TrustAnchor::BlockHash(hash),
+		(None, Some(path)) => {
+			let header: Header = serde_json::from_value(read_json(&path)?)
+				.map_err(|e| format!("invalid header: {e}"))?;
+			TrustAnchor::Header(header)
+		},
+		(None, None) => unreachable!("clap requires one of --block-hash and --header"),
+	};
+
+	let report = verify(&export, &anchor, &claimed).map_err(|e| e.to_string())?;
+
+	match cli.format {
+		Format::Text => print_report(&export, &report),
+		Format::Vc => print_credentials(&export, &report)?,
+	}
+
+	Ok(report.is_fully_authentic())
+}
+
+fn print_report(export: &PortfolioExport, report: &Report) {
+	println!("Account:    {}", report.account);
+	println!("Block:      #{} ({:?})", export.header.number, export.at);
+	println!("State root: {:?}", report.state_root);
+	println!();
+	for credential in &report.credentials {
+		let name = credential
+			.credential
+			.as_ref()
+			.map(|record| metadata_name(&record.metadata))
+			.unwrap_or_default();
+		println!("{:?}  {:<10}  {name}", credential.credential_id, credential.status);
+	}
+
+	let count = |wanted: fn(&CredentialStatus) -> bool| {
+		report.credentials.iter().filter(|credential| wanted(&credential.status)).count()
+	};
+	println!();
+	println!(
+		"{} authentic, {} revoked, {} missing, {} invalid",
+		count(|status| *status == CredentialStatus::Authentic),
+		count(|status| *status == CredentialStatus::Revoked),
+		count(|status| *status == CredentialStatus::Missing),
+		count(|status| matches!(status, CredentialStatus::Invalid(_))),
+	);
+}
+
+/// Print the authentic credentials of `export` as Verifiable Credentials.
+fn print_credentials(export: &PortfolioExport, report: &Report) -> Result<(), String> {
+	let credentials: Vec<_> = vc::render_portfolio(export)
+		.into_iter()
+		.filter(|credential| {
+			report.credentials.iter().any(|checked| {
+				checked.status == CredentialStatus::Authentic &&
+					credential.id == vc::credential_uri(&checked.credential_id)
+			})
+		})
+		.collect();
+	let json = serde_json::to_string_pretty(&credentials).map_err(|e| e.to_string())?;
+	println!("{json}");
+	Ok(())
+}
+
+/// Human-readable name of a credential, if its metadata declares one.
+fn metadata_name(metadata: &[u8]) -> String {
+	serde_json::from_slice::<Value>(metadata)
+		.ok()
+		.and_then(|value| Some(value.get("name").or(value.get("n"))?.as_str()?.to_owned()))
+		.unwrap_or_default()
+}
+
+fn main() -> ExitCode {
+	match run(Cli::parse()) {
+		Ok(true) => ExitCode::SUCCESS,
+		Ok(false) => ExitCode::from(1),
+		Err(error) => {
+			eprintln!("error: {error}");
+			ExitCode::from(2)
+		},
+	}
+}
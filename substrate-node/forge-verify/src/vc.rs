@@ -0,0 +1,335 @@
+//! Rendering of credentials as W3C Verifiable Credentials.
+//!
+//! Each on-chain credential becomes a [VC Data Model 2.0] document:
+//!
+//! - the holder is identified as `did:forge:<ss58 account>`;
+//! - the issuer is the holder too, since every credential is minted by its holder and
+//!   nothing on chain attests the metadata's `issuer`, which is only reported as the
+//!   issuer's declared name;
+//! - the document ID is derived from the credential's storage key, which keeps the ID
+//!   minted with the credential across metadata updates;
+//! - the well-known metadata fields become claims of `credentialSubject`;
+//! - the `proof` is a [`StorageProof`]: the header of the anchor block and a Merkle proof
+//!   of the credential's `Credentials` storage entry under its state root, rather than a
+//!   signature. [`verify`] checks it.
+//!
+//! [VC Data Model 2.0]: https://www.w3.org/TR/vc-data-model-2.0/
+
+use crate::{
+	export::{Header, PortfolioExport, ProvenEntry},
+	verify::{self as verifier, StoredCredential},
+};
+use codec::Decode;
+use pallet_freelance_credentials::{metadata::ParsedMetadata, CredentialField, CredentialType};
+use polkadot_sdk::{
+	sp_core::{Bytes, H256},
+	sp_runtime::AccountId32,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Base context of VC Data Model 2.0 documents.
+pub const CREDENTIALS_V2_CONTEXT: &str = "https://www.w3.org/ns/credentials/v2";
+
+/// `type` of the proofs attached by this module.
+pub const PROOF_TYPE: &str = "FreelanceForgeStorageProof";
+
+/// DID of an account, `did:forge:<ss58 address>`.
+pub fn did(account: &AccountId32) -> String {
+	format!("did:forge:{account}")
+}
+
+/// Identifier of a credential document.
+pub fn credential_uri(credential_id: &H256) -> String {
+	format!("urn:freelanceforge:credential:{credential_id:?}")
+}
+
+/// Issuer of a verifiable credential.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Issuer {
+	/// DID of the issuer.
+	pub id: String,
+	/// Issuer name declared by the credential metadata.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub name: Option<String>,
+}
+
+/// Subject of a verifiable credential: the holder and the claims made about them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialSubject {
+	/// DID of the holder.
+	pub id: String,
+	/// Credential type (`skill`, `review`, `payment`, `certification` or `other`).
+	pub credential_type: String,
+	/// Name of the skill, review, payment or certification.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub name: Option<String>,
+	/// Free-form description.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub description: Option<String>,
+	/// Rating given by a client, for reviews.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub rating: Option<Value>,
+	/// Hash of supporting evidence stored off chain.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub proof_hash: Option<String>,
+}
+
+/// Proof that a credential is stored on chain at an anchor block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageProof {
+	/// Always [`PROOF_TYPE`].
+	#[serde(rename = "type")]
+	pub proof_type: String,
+	/// Always `assertionMethod`.
+	pub proof_purpose: String,
+	/// Hash of the anchor block.
+	pub block_hash: H256,
+	/// Number of the anchor block.
+	pub block_number: u32,
+	/// Header of the anchor block, which commits to the state root the proof verifies
+	/// against.
+	pub header: Header,
+	/// Storage key of the credential's `Credentials` entry.
+	pub storage_key: Bytes,
+	/// SCALE-encoded credential record stored under `storage_key`.
+	pub storage_value: Bytes,
+	/// Trie nodes proving `storage_value` under the header's state root.
+	pub proof_value: Vec<Bytes>,
+}
+
+/// A credential rendered as a W3C Verifiable Credential.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiableCredential {
+	/// JSON-LD contexts.
+	#[serde(rename = "@context")]
+	pub context: Vec<String>,
+	/// URI of the credential, derived from its on-chain ID.
+	pub id: String,
+	/// `VerifiableCredential` and, for known credential types, a more specific type.
+	#[serde(rename = "type")]
+	pub types: Vec<String>,
+	/// Issuer of the credential.
+	pub issuer: Issuer,
+	/// Timestamp declared by the credential metadata.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub valid_from: Option<String>,
+	/// Holder and claims.
+	pub credential_subject: CredentialSubject,
+	/// On-chain anchor of the credential.
+	pub proof: StorageProof,
+}
+
+/// Render a credential proven by `entry` at the block of `header`.
+///
+/// Returns `None` if `entry` is not a `Credentials` entry or holds no decodable credential
+/// record.
+pub fn render(header: &Header, entry: &ProvenEntry) -> Option<VerifiableCredential> {
+	let credential_id = verifier::credential_id_from_key(&entry.key)?;
+	let value = entry.value.as_ref()?;
+	let credential = StoredCredential::decode(&mut &value[..]).ok()?;
+	let metadata = ParsedMetadata::parse(&credential.metadata);
+
+	let str_field = |field| metadata.field(field).and_then(Value::as_str).map(str::to_owned);
+
+	let (credential_type, specific_type) = credential_type_names(metadata.credential_type());
+	let mut types = vec!["VerifiableCredential".to_owned()];
+	types.extend(specific_type.map(str::to_owned));
+
+	Some(VerifiableCredential {
+		context: vec![CREDENTIALS_V2_CONTEXT.to_owned()],
+		id: credential_uri(&credential_id),
+		types,
+		issuer: Issuer { id: did(&credential.owner), name: str_field(CredentialField::Issuer) },
+		valid_from: metadata.field(CredentialField::Timestamp).and_then(date_time),
+		credential_subject: CredentialSubject {
+			id: did(&credential.owner),
+			credential_type: credential_type.to_owned(),
+			name: str_field(CredentialField::Name),
+			description: str_field(CredentialField::Description),
+			rating: metadata.field(CredentialField::Rating).cloned(),
+			proof_hash: str_field(CredentialField::ProofHash),
+		},
+		proof: StorageProof {
+			proof_type: PROOF_TYPE.to_owned(),
+			proof_purpose: "assertionMethod".to_owned(),
+			block_hash: header.hash(),
+			block_number: header.number,
+			header: header.clone(),
+			storage_key: entry.key.clone(),
+			storage_value: value.clone(),
+			proof_value: entry.proof.clone(),
+		},
+	})
+}
+
+/// Render every credential of a portfolio export.
+pub fn render_portfolio(export: &PortfolioExport) -> Vec<VerifiableCredential> {
+	export
+		.credentials
+		.iter()
+		.filter_map(|exported| render(&export.header, &exported.entry))
+		.collect()
+}
+
+/// Check the storage proof of `credential` against the block hash `trusted`.
+///
+/// The document's claims are not compared with the proven record; relying parties should
+/// read claims from [`StoredCredential::metadata`] of the returned record.
+pub fn verify(
+	credential: &VerifiableCredential,
+	trusted: H256,
+) -> Result<StoredCredential, String> {
+	let proof = &credential.proof;
+	if proof.proof_type != PROOF_TYPE {
+		return Err(format!("unsupported proof type {}", proof.proof_type));
+	}
+	if proof.header.hash() != trusted {
+		return Err("proof is anchored to an untrusted block".into());
+	}
+
+	let credential_id = verifier::credential_id_from_key(&proof.storage_key)
+		.ok_or_else(|| "proof is not for a credential storage key".to_string())?;
+	let record = StoredCredential::decode(&mut &proof.storage_value[..])
+		.map_err(|_| "credential record cannot be decoded".to_string())?;
+	if credential.id != credential_uri(&credential_id) ||
+		credential.issuer.id != did(&record.owner) ||
+		credential.credential_subject.id != did(&record.owner)
+	{
+		return Err("document does not describe the proven credential".into());
+	}
+
+	let entry = ProvenEntry {
+		key: proof.storage_key.clone(),
+		value: Some(proof.storage_value.clone()),
+		proof: proof.proof_value.clone(),
+	};
+	verifier::check_entry(proof.header.state_root, &entry)?;
+	Ok(record)
+}
+
+/// Metadata name of a credential type and its VC type, if it has a specific one.
+fn credential_type_names(credential_type: CredentialType) -> (&'static str, Option<&'static str>) {
+	match credential_type {
+		CredentialType::Skill => ("skill", Some("SkillCredential")),
+		CredentialType::Review => ("review", Some("ReviewCredential")),
+		CredentialType::Payment => ("payment", Some("PaymentCredential")),
+		CredentialType::Certification => ("certification", Some("CertificationCredential")),
+		CredentialType::Other => ("other", None),
+	}
+}
+
+/// An XML Schema `dateTimeStamp` from a metadata timestamp.
+///
+/// Strings are assumed to be ISO 8601 already (the frontend writes them with
+/// `Date.toISOString`); numbers are Unix timestamps in seconds or, above 10^12,
+/// milliseconds.
+fn date_time(timestamp: &Value) -> Option<String> {
+	match timestamp {
+		Value::String(timestamp) if timestamp.contains('T') => Some(timestamp.clone()),
+		Value::Number(timestamp) => {
+			let timestamp = timestamp.as_u64()?;
+			let seconds = if timestamp >= 1_000_000_000_000 { timestamp / 1000 } else { timestamp };
+			Some(format_unix_time(seconds))
+		},
+		_ => None,
+	}
+}
+
+/// Format Unix time as `YYYY-MM-DDThh:mm:ssZ`.
+fn format_unix_time(seconds: u64) -> String {
+	let (days, time) = (seconds / 86_400, seconds % 86_400);
+
+	// Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+	let z = days + 719_468;
+	let era = z / 146_097;
+	let day_of_era = z % 146_097;
+	let year_of_era =
+		(day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let mp = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * mp + 2) / 5 + 1;
+	let month = if mp < 10 { mp + 3 } else { mp - 9 };
+	let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+	format!(
+		"{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+		time / 3_600,
+		time % 3_600 / 60,
+		time % 60
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::verify::tests::{account, export, export_of};
+
+	#[test]
+	fn renders_and_verifies_credential() {
+		let export = export();
+
+		let credentials = render_portfolio(&export);
+
+		assert_eq!(credentials.len(), 1);
+		let credential = &credentials[0];
+		assert_eq!(credential.id, credential_uri(&export.credentials[0].credential_id));
+		assert_eq!(credential.types, ["VerifiableCredential", "SkillCredential"]);
+		// The declared issuer is not attested, so the holder issues the credential
+		assert_eq!(credential.issuer.id, did(&account()));
+		assert_eq!(credential.issuer.name.as_deref(), Some("Acme"));
+		assert_eq!(credential.credential_subject.id, did(&account()));
+		assert_eq!(credential.credential_subject.name.as_deref(), Some("Rust"));
+		assert_eq!(credential.valid_from.as_deref(), Some("2024-01-15T10:30:00Z"));
+		assert_eq!(credential.proof.block_hash, export.at);
+
+		let json = serde_json::to_value(credential).unwrap();
+		assert_eq!(json["@context"][0], CREDENTIALS_V2_CONTEXT);
+		assert_eq!(json["credentialSubject"]["credentialType"], "skill");
+		assert_eq!(json["proof"]["type"], PROOF_TYPE);
+
+		let record = verify(credential, export.at).unwrap();
+		assert_eq!(record.minted_at, 7);
+	}
+
+	#[test]
+	fn updated_credential_keeps_its_id() {
+		let credential_id = H256::repeat_byte(7);
+		let export = export_of(credential_id);
+
+		let credential = render_portfolio(&export).remove(0);
+
+		assert_eq!(credential.id, credential_uri(&credential_id));
+		assert!(verify(&credential, export.at).is_ok());
+	}
+
+	#[test]
+	fn tampered_credential_is_rejected() {
+		let export = export();
+		let credential = render_portfolio(&export).remove(0);
+
+		assert!(verify(&credential, H256::repeat_byte(1)).is_err());
+
+		let mut tampered = credential.clone();
+		tampered.issuer.id = did(&AccountId32::new([2; 32]));
+		assert!(verify(&tampered, export.at).is_err());
+
+		let mut tampered = credential.clone();
+		tampered.credential_subject.id = did(&AccountId32::new([3; 32]));
+		assert!(verify(&tampered, export.at).is_err());
+
+		let mut tampered = credential;
+		tampered.proof.proof_value.clear();
+		assert!(verify(&tampered, export.at).is_err());
+	}
+
+	#[test]
+	fn formats_unix_timestamps() {
+		assert_eq!(format_unix_time(0), "1970-01-01T00:00:00Z");
+		assert_eq!(format_unix_time(951_782_400), "2000-02-29T00:00:00Z");
+		assert_eq!(date_time(&Value::from(1_705_314_600_000u64)).unwrap(), "2024-01-15T10:30:00Z");
+	}
+}
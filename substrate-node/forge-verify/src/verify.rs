@@ -0,0 +1,373 @@
+//! Verification of portfolio exports against a trusted block.
+
+use crate::export::{Header, PortfolioExport, ProvenEntry};
+use codec::Decode;
+use pallet_freelance_credentials::CredentialInfo;
+use polkadot_sdk::{
+	sp_core::{blake2_128, twox_128, H256},
+	sp_runtime::{traits::BlakeTwo256, AccountId32},
+	sp_state_machine::read_proof_check,
+	sp_trie::StorageProof,
+};
+use std::fmt;
+
+/// Name of the credentials pallet in the FreelanceForge runtime, which prefixes its
+/// storage keys.
+pub const PALLET_NAME: &[u8] = b"FreelanceCredentials";
+
+/// A credential record as stored by the FreelanceForge runtime.
+pub type StoredCredential = CredentialInfo<AccountId32, u32>;
+
+/// What the verifier is told to trust.
+#[derive(Clone, Debug)]
+pub enum TrustAnchor {
+	/// Hash of a block, obtained from a trusted source. The export's header must hash to it.
+	BlockHash(H256),
+	/// A block header, obtained from a trusted source. Proofs are checked against its state
+	/// root.
+	Header(Header),
+}
+
+/// Outcome of verifying a single credential.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CredentialStatus {
+	/// The credential is stored on chain under its ID, belongs to the exported account and
+	/// its metadata matches the exported one.
+	Authentic,
+	/// The credential is claimed by the portfolio but not held by the account at the
+	/// trusted block: it was deleted, purged or moved away.
+	Revoked,
+	/// The account holds the credential at the trusted block, but the export contains no
+	/// proof of it.
+	Missing,
+	/// The proof or the proven value does not back the claim.
+	Invalid(String),
+}
+
+impl fmt::Display for CredentialStatus {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			CredentialStatus::Authentic => write!(f, "authentic"),
+			CredentialStatus::Revoked => write!(f, "revoked"),
+			CredentialStatus::Missing => write!(f, "missing"),
+			CredentialStatus::Invalid(reason) => write!(f, "invalid ({reason})"),
+		}
+	}
+}
+
+/// Verification result of a single credential.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CredentialReport {
+	/// ID of the credential.
+	pub credential_id: H256,
+	/// Verification outcome.
+	pub status: CredentialStatus,
+	/// Proven on-chain record, when the credential is authentic.
+	pub credential: Option<StoredCredential>,
+}
+
+/// Verification result of a whole export.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+	/// Account whose portfolio was verified.
+	pub account: AccountId32,
+	/// State root the proofs were checked against.
+	pub state_root: H256,
+	/// One entry per credential held, exported or claimed.
+	pub credentials: Vec<CredentialReport>,
+}
+
+impl Report {
+	/// Whether every credential is authentic.
+	pub fn is_fully_authentic(&self) -> bool {
+		self.credentials.iter().all(|report| report.status == CredentialStatus::Authentic)
+	}
+}
+
+/// Errors preventing any credential from being verified.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+	/// The export's header does not hash to the trusted block hash.
+	UntrustedHeader { expected: H256, actual: H256 },
+	/// The `OwnerCredentials` entry is not the one of the exported account.
+	WrongOwnerKey,
+	/// The `OwnerCredentials` proof does not verify against the state root.
+	InvalidOwnerProof(String),
+	/// The proven `OwnerCredentials` value cannot be decoded.
+	UndecodableOwnerCredentials,
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::UntrustedHeader { expected, actual } => write!(
+				f,
+				"export header hashes to {actual:?}, but the trusted block is {expected:?}"
+			),
+			Error::WrongOwnerKey =>
+				write!(f, "owner credentials proof is for a different account"),
+			Error::InvalidOwnerProof(reason) =>
+				write!(f, "owner credentials proof is invalid: {reason}"),
+			Error::UndecodableOwnerCredentials =>
+				write!(f, "owner credentials value cannot be decoded"),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+fn storage_prefix(storage_name: &[u8]) -> Vec<u8> {
+	[twox_128(PALLET_NAME), twox_128(storage_name)].concat()
+}
+
+/// Storage key of `OwnerCredentials` for `account`.
+pub fn owner_credentials_key(account: &AccountId32) -> Vec<u8> {
+	let account: &[u8] = account.as_ref();
+	[storage_prefix(b"OwnerCredentials"), blake2_128(account).to_vec(), account.to_vec()].concat()
+}
+
+/// Storage key of `Credentials` for `credential_id`.
+pub fn credential_key(credential_id: &H256) -> Vec<u8> {
+	let id = credential_id.as_bytes();
+	[storage_prefix(b"Credentials"), blake2_128(id).to_vec(), id.to_vec()].concat()
+}
+
+/// ID of the credential stored under `key`, if `key` is a `Credentials` storage key.
+pub fn credential_id_from_key(key: &[u8]) -> Option<H256> {
+	// Map keys are the 32 byte storage prefix, a 16 byte Blake2_128 hash and the ID
+	let credential_id = key.get(48..).filter(|id| id.len() == 32).map(H256::from_slice)?;
+	(credential_key(&credential_id) == key).then_some(credential_id)
+}
+
+/// Check `entry` against `state_root`, returning the proven value.
+///
+/// The value carried by the entry is only trusted if the proof yields exactly that value.
+pub(crate) fn check_entry(state_root: H256, entry: &ProvenEntry) -> Result<Option<Vec<u8>>, String> {
+	let proof = StorageProof::new(entry.proof.iter().map(|node| node.0.clone()));
+	let mut values = read_proof_check::<BlakeTwo256, _>(state_root, proof, [&entry.key.0[..]])
+		.map_err(|error| error.to_string())?;
+	let proven = values.remove(&entry.key.0).flatten();
+	if proven.as_deref() != entry.value.as_ref().map(|value| &value.0[..]) {
+		return Err("value differs from the proven one".into());
+	}
+	Ok(proven)
+}
+
+/// Verify `export` against `anchor`.
+///
+/// `claimed` lists credential IDs the portfolio presents to the reader (for instance the
+/// credentials shown in a frontend export); claimed credentials the account does not hold
+/// at the trusted block are reported as revoked.
+pub fn verify(
+	export: &PortfolioExport,
+	anchor: &TrustAnchor,
+	claimed: &[H256],
+) -> Result<Report, Error> {
+	let state_root = match anchor {
+		TrustAnchor::BlockHash(expected) => {
+			let actual = export.header.hash();
+			if actual != *expected {
+				return Err(Error::UntrustedHeader { expected: *expected, actual });
+			}
+			export.header.state_root
+		},
+		TrustAnchor::Header(header) => header.state_root,
+	};
+
+	if export.owner_credentials.key.0 != owner_credentials_key(&export.account) {
+		return Err(Error::WrongOwnerKey);
+	}
+	let held: Vec<H256> = match check_entry(state_root, &export.owner_credentials)
+		.map_err(Error::InvalidOwnerProof)?
+	{
+		Some(value) => Vec::<H256>::decode(&mut &value[..])
+			.map_err(|_| Error::UndecodableOwnerCredentials)?,
+		None => Vec::new(),
+	};
+
+	let mut reports: Vec<CredentialReport> = held
+		.iter()
+		.map(|credential_id| {
+			let Some(exported) =
+				export.credentials.iter().find(|exported| exported.credential_id == *credential_id)
+			else {
+				return CredentialReport {
+					credential_id: *credential_id,
+					status: CredentialStatus::Missing,
+					credential: None,
+				};
+			};
+			let (status, credential) = match verify_credential(state_root, export, exported) {
+				Ok(credential) => (CredentialStatus::Authentic, Some(credential)),
+				Err(reason) => (CredentialStatus::Invalid(reason), None),
+			};
+			CredentialReport { credential_id: *credential_id, status, credential }
+		})
+		.collect();
+
+	// Exported or claimed credentials the account does not hold
+	let not_held = export
+		.credentials
+		.iter()
+		.map(|exported| exported.credential_id)
+		.chain(claimed.iter().copied())
+		.filter(|credential_id| !held.contains(credential_id));
+	for credential_id in not_held {
+		if reports.iter().all(|report| report.credential_id != credential_id) {
+			reports.push(CredentialReport {
+				credential_id,
+				status: CredentialStatus::Revoked,
+				credential: None,
+			});
+		}
+	}
+
+	Ok(Report { account: export.account.clone(), state_root, credentials: reports })
+}
+
+/// Check the proven `Credentials` entry of a credential held by the exported account.
+///
+/// The credential ID is bound by the proven storage key: IDs are content-addressed at mint
+/// time only, and updates replace the metadata under the same ID.
+fn verify_credential(
+	state_root: H256,
+	export: &PortfolioExport,
+	exported: &crate::export::ExportedCredential,
+) -> Result<StoredCredential, String> {
+	if exported.entry.key.0 != credential_key(&exported.credential_id) {
+		return Err("proof is for a different storage key".into());
+	}
+	let value = check_entry(state_root, &exported.entry)?
+		.ok_or_else(|| "credential is listed but absent from storage".to_string())?;
+	let credential = StoredCredential::decode(&mut &value[..])
+		.map_err(|_| "credential record cannot be decoded".to_string())?;
+	if credential.owner != export.account {
+		return Err("credential belongs to another account".into());
+	}
+	if credential.metadata[..] != *exported.metadata.as_bytes() {
+		return Err("exported metadata differs from the on-chain metadata".into());
+	}
+	Ok(credential)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use super::*;
+	use crate::export::ExportedCredential;
+	use codec::Encode;
+	use polkadot_sdk::{
+		sp_core::Bytes,
+		sp_runtime::{
+			traits::{Hash, Header as _},
+			Digest, StateVersion,
+		},
+		sp_state_machine::{prove_read, Backend, InMemoryBackend},
+	};
+	use std::collections::BTreeMap;
+
+	const METADATA: &[u8] =
+		br#"{"name":"Rust","credential_type":"skill","issuer":"Acme","timestamp":"2024-01-15T10:30:00Z"}"#;
+
+	pub(crate) fn account() -> AccountId32 {
+		AccountId32::new([1; 32])
+	}
+
+	/// An export of a single credential, proven against an in-memory state
+	pub(crate) fn export() -> PortfolioExport {
+		export_of(BlakeTwo256::hash(METADATA))
+	}
+
+	/// An export of a single credential stored under `credential_id`
+	pub(crate) fn export_of(credential_id: H256) -> PortfolioExport {
+		let credential = StoredCredential {
+			owner: account(),
+			metadata: METADATA.to_vec().try_into().unwrap(),
+			minted_at: 7,
+		};
+		let storage: BTreeMap<Vec<u8>, Vec<u8>> = [
+			(owner_credentials_key(&account()), vec![credential_id].encode()),
+			(credential_key(&credential_id), credential.encode()),
+		]
+		.into_iter()
+		.collect();
+		let backend = InMemoryBackend::<BlakeTwo256>::from((storage, StateVersion::V1));
+		let state_root = *backend.root();
+
+		let prove = |key: Vec<u8>| ProvenEntry {
+			value: backend.storage(&key).unwrap().map(Bytes),
+			proof: prove_read(backend.clone(), [&key[..]])
+				.unwrap()
+				.into_iter_nodes()
+				.map(Bytes)
+				.collect(),
+			key: Bytes(key),
+		};
+
+		let header = Header::new(10, H256::zero(), state_root, H256::zero(), Digest::default());
+		PortfolioExport {
+			account: account(),
+			at: header.hash(),
+			header,
+			owner_credentials: prove(owner_credentials_key(&account())),
+			credentials: vec![ExportedCredential {
+				credential_id,
+				metadata: String::from_utf8(METADATA.to_vec()).unwrap(),
+				entry: prove(credential_key(&credential_id)),
+			}],
+		}
+	}
+
+	#[test]
+	fn authentic_export_verifies() {
+		let export = export();
+		let revoked = H256::repeat_byte(9);
+
+		let report = verify(&export, &TrustAnchor::BlockHash(export.at), &[revoked]).unwrap();
+
+		assert_eq!(report.credentials.len(), 2);
+		assert_eq!(report.credentials[0].status, CredentialStatus::Authentic);
+		assert_eq!(report.credentials[0].credential.as_ref().unwrap().minted_at, 7);
+		assert_eq!(report.credentials[1].credential_id, revoked);
+		assert_eq!(report.credentials[1].status, CredentialStatus::Revoked);
+	}
+
+	#[test]
+	fn updated_credential_verifies() {
+		// Minted with other metadata, so its ID no longer hashes its metadata
+		let credential_id = BlakeTwo256::hash(br#"{"name":"Rust"}"#);
+		let export = export_of(credential_id);
+
+		let report = verify(&export, &TrustAnchor::BlockHash(export.at), &[]).unwrap();
+
+		assert_eq!(report.credentials.len(), 1);
+		assert_eq!(report.credentials[0].credential_id, credential_id);
+		assert_eq!(report.credentials[0].status, CredentialStatus::Authentic);
+	}
+
+	#[test]
+	fn forged_values_are_rejected() {
+		let mut export = export();
+		export.at = H256::repeat_byte(1);
+		assert!(matches!(
+			verify(&export, &TrustAnchor::BlockHash(export.at), &[]),
+			Err(Error::UntrustedHeader { .. })
+		));
+
+		let mut export = self::export();
+		export.credentials[0].metadata = r#"{"name":"Haskell"}"#.into();
+		let report = verify(&export, &TrustAnchor::Header(export.header.clone()), &[]).unwrap();
+		assert!(matches!(report.credentials[0].status, CredentialStatus::Invalid(_)));
+
+		// A proof of another credential cannot stand in for a held one
+		let mut export = self::export();
+		let other = export_of(H256::repeat_byte(7));
+		export.credentials[0].entry = other.credentials[0].entry.clone();
+		let report = verify(&export, &TrustAnchor::Header(export.header.clone()), &[]).unwrap();
+		assert!(matches!(report.credentials[0].status, CredentialStatus::Invalid(_)));
+
+		let mut export = self::export();
+		export.credentials.clear();
+		let report = verify(&export, &TrustAnchor::Header(export.header.clone()), &[]).unwrap();
+		assert_eq!(report.credentials[0].status, CredentialStatus::Missing);
+	}
+}
@@ -15,7 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use minimal_template_runtime::WASM_BINARY;
+use minimal_template_runtime::{currency::DECIMALS, WASM_BINARY};
 use polkadot_sdk::{
 	sc_service::{ChainType, Properties},
 	*,
@@ -26,7 +26,7 @@ pub type ChainSpec = sc_service::GenericChainSpec;
 
 fn props() -> Properties {
 	let mut properties = Properties::new();
-	properties.insert("tokenDecimals".to_string(), 0.into());
+	properties.insert("tokenDecimals".to_string(), DECIMALS.into());
 	properties.insert("tokenSymbol".to_string(), "MINI".into());
 	properties
 }
@@ -40,3 +40,14 @@ pub fn development_chain_spec() -> Result<ChainSpec, String> {
 		.with_properties(props())
 		.build())
 }
+
+/// Local testnet with Alice, Bob and Charlie as validators, to run with `--consensus aura`.
+pub fn local_chain_spec() -> Result<ChainSpec, String> {
+	Ok(ChainSpec::builder(WASM_BINARY.expect("Development wasm not available"), Default::default())
+		.with_name("Local Testnet")
+		.with_id("local_testnet")
+		.with_chain_type(ChainType::Local)
+		.with_genesis_config_preset_name(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET)
+		.with_properties(props())
+		.build())
+}
@@ -16,11 +16,16 @@
 // limitations under the License.
 
 use polkadot_sdk::{sc_cli::RunCmd, *};
+use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
 pub enum Consensus {
 	ManualSeal(u64),
+	/// Manual seal driven only by the `engine_createBlock` and `engine_finalizeBlock` RPCs.
+	ManualSealRpc,
 	InstantSeal,
+	/// Aura block authoring with GRANDPA finality, for networks of several validators.
+	Aura,
 	None,
 }
 
@@ -30,6 +35,10 @@ impl std::str::FromStr for Consensus {
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
 		Ok(if s == "instant-seal" {
 			Consensus::InstantSeal
+		} else if s == "manual-seal-rpc" {
+			Consensus::ManualSealRpc
+		} else if s == "aura" {
+			Consensus::Aura
 		} else if let Some(block_time) = s.strip_prefix("manual-seal-") {
 			Consensus::ManualSeal(block_time.parse().map_err(|_| "invalid block time")?)
 		} else if s.to_lowercase() == "none" {
@@ -48,10 +57,41 @@ pub struct Cli {
 	#[clap(long, default_value = "manual-seal-3000")]
 	pub consensus: Consensus,
 
+	#[clap(flatten)]
+	pub indexer: IndexerParams,
+
+	#[clap(flatten)]
+	pub dev: DevParams,
+
 	#[clap(flatten)]
 	pub run: RunCmd,
 }
 
+/// Options of the embedded credentials indexer.
+#[derive(Debug, Clone, clap::Args)]
+pub struct IndexerParams {
+	/// Index credential events of finalized blocks into a SQLite database at this path,
+	/// and serve it through the `credentialsIndexer_*` RPC methods.
+	#[arg(long, value_name = "PATH")]
+	pub credentials_indexer: Option<PathBuf>,
+
+	/// Drop the indexer database and index again from genesis.
+	///
+	/// Needs the state of every block, i.e. `--state-pruning archive`.
+	#[arg(long, requires = "credentials_indexer")]
+	pub credentials_indexer_rebuild: bool,
+}
+
+/// Options of dev chains sealed by manual or instant seal.
+#[derive(Debug, Clone, clap::Args)]
+pub struct DevParams {
+	/// Timestamp sealed blocks this many milliseconds ahead of the system time (behind when
+	/// negative), e.g. to test recency logic. Adjustable at runtime with the `dev_*` RPC
+	/// methods.
+	#[arg(long, value_name = "MILLIS", allow_hyphen_values = true, default_value_t = 0)]
+	pub timestamp_offset: i64,
+}
+
 #[derive(Debug, clap::Subcommand)]
 pub enum Subcommand {
 	/// Key management cli utilities
@@ -81,4 +121,8 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Read credentials from the local database.
+	#[command(subcommand)]
+	Credentials(crate::credentials_cmd::CredentialsCmd),
 }
@@ -0,0 +1,160 @@
+//! Reading `pallet-freelance-credentials` storage straight from the client's database.
+//!
+//! Shared by the `credentials_*` RPC methods and the `credentials` subcommand, which both
+//! read credentials and storage-proven portfolio exports at a given block without going
+//! through the runtime.
+
+use codec::Decode;
+use forge_verify::export::{ExportedCredential, PortfolioExport, ProvenEntry};
+use minimal_template_runtime::{
+	interface::{AccountId, Hash, OpaqueBlock},
+	Runtime,
+};
+use pallet_freelance_credentials::{
+	CredentialInfoOf, Credentials as CredentialsStorage, OwnerCredentials, Visibility,
+};
+use polkadot_sdk::{
+	sc_client_api::{Backend, ProofProvider, StorageProvider},
+	sp_blockchain::{self, HeaderBackend},
+	sp_core::{storage::StorageKey, Bytes},
+	*,
+};
+use std::fmt;
+
+/// Errors reading credentials from the client.
+#[derive(Debug)]
+pub enum Error {
+	/// The requested block is not in the database.
+	UnknownBlock(Hash),
+	/// The client failed to read state.
+	Client(sp_blockchain::Error),
+	/// A storage value could not be decoded.
+	Decode(codec::Error),
+	/// An account lists a credential that is not stored.
+	MissingCredential(Hash),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::UnknownBlock(hash) => write!(f, "unknown block {hash:?}"),
+			Self::Client(error) => write!(f, "failed to read chain state: {error}"),
+			Self::Decode(error) => write!(f, "failed to decode storage value: {error}"),
+			Self::MissingCredential(id) => write!(f, "credential {id:?} is listed but missing"),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl From<sp_blockchain::Error> for Error {
+	fn from(error: sp_blockchain::Error) -> Self {
+		Self::Client(error)
+	}
+}
+
+impl From<codec::Error> for Error {
+	fn from(error: codec::Error) -> Self {
+		Self::Decode(error)
+	}
+}
+
+/// Lowercase name of a credential visibility, as used in credential metadata.
+pub fn visibility_name(visibility: Visibility) -> &'static str {
+	match visibility {
+		Visibility::Public => "public",
+		Visibility::Private => "private",
+	}
+}
+
+/// Decoded value of the storage entry under `key` at block `at`.
+fn read<C, B, V>(client: &C, at: Hash, key: Vec<u8>) -> Result<Option<V>, Error>
+where
+	C: StorageProvider<OpaqueBlock, B>,
+	B: Backend<OpaqueBlock>,
+	V: Decode,
+{
+	match client.storage(at, &StorageKey(key))? {
+		Some(data) => Ok(Some(V::decode(&mut &data.0[..])?)),
+		None => Ok(None),
+	}
+}
+
+/// IDs of the credentials owned by `account` at block `at`.
+pub fn owner_credentials<C, B>(
+	client: &C,
+	at: Hash,
+	account: &AccountId,
+) -> Result<Vec<Hash>, Error>
+where
+	C: StorageProvider<OpaqueBlock, B>,
+	B: Backend<OpaqueBlock>,
+{
+	let key = OwnerCredentials::<Runtime>::hashed_key_for(account);
+	Ok(read::<C, B, Vec<Hash>>(client, at, key)?.unwrap_or_default())
+}
+
+/// Credential `credential_id` at block `at`, if it exists.
+pub fn credential<C, B>(
+	client: &C,
+	at: Hash,
+	credential_id: Hash,
+) -> Result<Option<CredentialInfoOf<Runtime>>, Error>
+where
+	C: StorageProvider<OpaqueBlock, B>,
+	B: Backend<OpaqueBlock>,
+{
+	read::<C, B, _>(client, at, CredentialsStorage::<Runtime>::hashed_key_for(credential_id))
+}
+
+/// Read the value under `key` at block `at`, with a proof of it.
+fn prove<C, B>(client: &C, at: Hash, key: Vec<u8>) -> Result<ProvenEntry, Error>
+where
+	C: StorageProvider<OpaqueBlock, B> + ProofProvider<OpaqueBlock>,
+	B: Backend<OpaqueBlock>,
+{
+	let value = client.storage(at, &StorageKey(key.clone()))?.map(|data| Bytes(data.0));
+	let proof = client.read_proof(at, &mut std::iter::once(key.as_slice()))?;
+	Ok(ProvenEntry { key: Bytes(key), value, proof: proof.into_iter_nodes().map(Bytes).collect() })
+}
+
+/// Credentials of `account` at block `at`, with storage proofs of every `OwnerCredentials`
+/// and `Credentials` entry they were read from.
+pub fn export_portfolio<C, B>(
+	client: &C,
+	account: AccountId,
+	at: Hash,
+) -> Result<PortfolioExport, Error>
+where
+	C: HeaderBackend<OpaqueBlock> + StorageProvider<OpaqueBlock, B> + ProofProvider<OpaqueBlock>,
+	B: Backend<OpaqueBlock>,
+{
+	let header = client.header(at)?.ok_or(Error::UnknownBlock(at))?;
+
+	let owner_credentials =
+		prove::<C, B>(client, at, OwnerCredentials::<Runtime>::hashed_key_for(&account))?;
+	let credential_ids = match &owner_credentials.value {
+		Some(value) => Vec::<Hash>::decode(&mut &value[..])?,
+		None => Vec::new(),
+	};
+
+	let credentials = credential_ids
+		.into_iter()
+		.map(|credential_id| {
+			let entry = prove::<C, B>(
+				client,
+				at,
+				CredentialsStorage::<Runtime>::hashed_key_for(credential_id),
+			)?;
+			let value = entry.value.as_ref().ok_or(Error::MissingCredential(credential_id))?;
+			let credential = CredentialInfoOf::<Runtime>::decode(&mut &value[..])?;
+			Ok(ExportedCredential {
+				credential_id,
+				metadata: String::from_utf8_lossy(&credential.metadata).into_owned(),
+				entry,
+			})
+		})
+		.collect::<Result<_, Error>>()?;
+
+	Ok(PortfolioExport { account, at, header, owner_credentials, credentials })
+}
@@ -0,0 +1,187 @@
+//! `credentials` subcommand.
+//!
+//! Reads credentials from the node's database without starting networking or RPC, e.g. to
+//! inspect a stopped node or script exports:
+//!
+//! ```text
+//! minimal-template-node credentials list --owner 5Grw... --dev
+//! minimal-template-node credentials show 0x1234... --at 42 --dev
+//! minimal-template-node credentials export --owner 5Grw... --format vc --dev
+//! ```
+
+use crate::{credentials, rpc::credentials::credential_type_name};
+use forge_verify::vc;
+use minimal_template_runtime::interface::{AccountId, Hash, OpaqueBlock};
+use pallet_freelance_credentials::metadata::ParsedMetadata;
+use polkadot_sdk::{
+	sc_cli::{
+		BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams,
+	},
+	sc_client_api::{Backend, ProofProvider, StorageProvider},
+	sp_blockchain::HeaderBackend,
+	*,
+};
+use serde_json::{json, Value};
+
+/// Options shared by every `credentials` subcommand.
+#[derive(Debug, Clone, clap::Args)]
+pub struct ReadParams {
+	/// Block to read at, by number or hash (the best block by default).
+	#[arg(long, value_name = "HASH or NUMBER")]
+	pub at: Option<BlockNumberOrHash>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+/// Format of `credentials export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+	/// Portfolio export with storage proofs, as returned by `credentials_exportPortfolio`.
+	Json,
+	/// W3C Verifiable Credentials, as returned by `credentials_exportVerifiableCredentials`.
+	Vc,
+}
+
+/// Read credentials from the local database.
+#[derive(Debug, Clone, clap::Subcommand)]
+pub enum CredentialsCmd {
+	/// List the credentials of an account.
+	List {
+		/// Account whose credentials are listed.
+		#[arg(long, value_name = "SS58")]
+		owner: AccountId,
+
+		#[allow(missing_docs)]
+		#[clap(flatten)]
+		params: ReadParams,
+	},
+
+	/// Print a credential as JSON.
+	Show {
+		/// ID of the credential.
+		id: Hash,
+
+		#[allow(missing_docs)]
+		#[clap(flatten)]
+		params: ReadParams,
+	},
+
+	/// Export the credentials of an account with their storage proofs.
+	Export {
+		/// Account whose credentials are exported.
+		#[arg(long, value_name = "SS58")]
+		owner: AccountId,
+
+		/// Output format.
+		#[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+		format: ExportFormat,
+
+		#[allow(missing_docs)]
+		#[clap(flatten)]
+		params: ReadParams,
+	},
+}
+
+impl CredentialsCmd {
+	fn params(&self) -> &ReadParams {
+		match self {
+			Self::List { params, .. } | Self::Show { params, .. } | Self::Export { params, .. } =>
+				params,
+		}
+	}
+
+	/// Run the command against `client`.
+	pub fn run<C, B>(&self, client: &C) -> sc_cli::Result<()>
+	where
+		C: HeaderBackend<OpaqueBlock>
+			+ StorageProvider<OpaqueBlock, B>
+			+ ProofProvider<OpaqueBlock>,
+		B: Backend<OpaqueBlock>,
+	{
+		let at = match &self.params().at {
+			Some(block) => client.expect_block_hash_from_id(&block.parse::<OpaqueBlock>()?)?,
+			None => client.info().best_hash,
+		};
+
+		match self {
+			Self::List { owner, .. } => {
+				let ids = credentials::owner_credentials::<C, B>(client, at, owner)
+					.map_err(read_error)?;
+				println!("ID\tTYPE\tNAME\tISSUER\tVISIBILITY\tMINTED AT");
+				for id in ids {
+					let credential = credentials::credential::<C, B>(client, at, id)
+						.map_err(read_error)?
+						.ok_or_else(|| read_error(credentials::Error::MissingCredential(id)))?;
+					let metadata = ParsedMetadata::parse(&credential.metadata);
+					println!(
+						"{id:?}\t{}\t{}\t{}\t{}\t{}",
+						credential_type_name(metadata.credential_type()),
+						String::from_utf8_lossy(&metadata.name()),
+						String::from_utf8_lossy(&metadata.issuer()),
+						credentials::visibility_name(metadata.visibility()),
+						credential.minted_at,
+					);
+				}
+			},
+			Self::Show { id, .. } => {
+				let credential = credentials::credential::<C, B>(client, at, *id)
+					.map_err(read_error)?
+					.ok_or_else(|| format!("credential {id:?} does not exist at block {at:?}"))?;
+				// Metadata is usually JSON; keep anything else as a plain string
+				let metadata = serde_json::from_slice::<Value>(&credential.metadata)
+					.unwrap_or_else(|_| {
+						Value::String(String::from_utf8_lossy(&credential.metadata).into_owned())
+					});
+				print_json(&json!({
+					"credentialId": id,
+					"owner": credential.owner,
+					"mintedAt": credential.minted_at,
+					"metadata": metadata,
+				}))?;
+			},
+			Self::Export { owner, format, .. } => {
+				let export = credentials::export_portfolio::<C, B>(client, owner.clone(), at)
+					.map_err(read_error)?;
+				match format {
+					ExportFormat::Json => print_json(&export)?,
+					ExportFormat::Vc => print_json(&vc::render_portfolio(&export))?,
+				}
+			},
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for CredentialsCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.params().shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.params().pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.params().database_params)
+	}
+}
+
+fn print_json(value: &impl serde::Serialize) -> sc_cli::Result<()> {
+	let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+	println!("{json}");
+	Ok(())
+}
+
+fn read_error(error: credentials::Error) -> sc_cli::Error {
+	sc_cli::Error::Application(Box::new(error))
+}
@@ -0,0 +1,74 @@
+//! Clock of blocks sealed by manual and instant seal.
+//!
+//! Dev chains can travel in time: the clock reads the system time shifted by an offset, set
+//! with `--timestamp-offset` or the `dev_*` RPC methods, so recency and expiry logic can be
+//! exercised without waiting.
+
+use polkadot_sdk::*;
+use std::sync::{
+	atomic::{AtomicI64, Ordering},
+	Arc,
+};
+
+/// System time shifted by an adjustable offset, shared by the block sealer and the RPC.
+#[derive(Clone, Debug, Default)]
+pub struct DevClock {
+	offset: Arc<AtomicI64>,
+}
+
+impl DevClock {
+	/// Create a clock running `offset` milliseconds ahead of the system time (behind when
+	/// negative).
+	pub fn new(offset: i64) -> Self {
+		Self { offset: Arc::new(AtomicI64::new(offset)) }
+	}
+
+	/// Current offset from the system time, in milliseconds.
+	pub fn offset(&self) -> i64 {
+		self.offset.load(Ordering::Relaxed)
+	}
+
+	/// Shift the clock by `delta` milliseconds, returning the new offset.
+	pub fn shift(&self, delta: i64) -> i64 {
+		self.offset.fetch_add(delta, Ordering::Relaxed).saturating_add(delta)
+	}
+
+	/// Set the clock to `timestamp` milliseconds since the Unix epoch, returning the new
+	/// offset.
+	pub fn set(&self, timestamp: u64) -> i64 {
+		let offset = (timestamp as i64).saturating_sub(*sp_timestamp::Timestamp::current() as i64);
+		self.offset.store(offset, Ordering::Relaxed);
+		offset
+	}
+
+	/// Current time of the clock.
+	pub fn now(&self) -> sp_timestamp::Timestamp {
+		let now = *sp_timestamp::Timestamp::current() as i64;
+		sp_timestamp::Timestamp::new(now.saturating_add(self.offset()).max(0) as u64)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn offsets_accumulate() {
+		let clock = DevClock::new(1_000);
+		assert_eq!(clock.shift(-400), 600);
+		assert_eq!(clock.offset(), 600);
+
+		let before = *sp_timestamp::Timestamp::current();
+		let now = *clock.now();
+		assert!(now >= before + 600);
+	}
+
+	#[test]
+	fn set_moves_to_an_absolute_time() {
+		let clock = DevClock::default();
+		let year_ahead = *sp_timestamp::Timestamp::current() + 365 * 24 * 60 * 60 * 1000;
+		clock.set(year_ahead);
+		assert!(*clock.now() >= year_ahead);
+		assert!(clock.offset() > 0);
+	}
+}
@@ -0,0 +1,295 @@
+//! Embedded indexer of `pallet-freelance-credentials` events.
+//!
+//! Enabled with `--credentials-indexer <path>`. Follows finalized blocks, decodes the
+//! credentials pallet events of each one and records them in a SQLite database (see
+//! [`store`]), which the `credentialsIndexer_*` RPC methods then query.
+//!
+//! Credentials seeded at genesis emit no events; they are read from the genesis state
+//! instead. If the indexed chain diverges from the canonical one (for instance after the
+//! `revert` subcommand), the database is rolled back to the last common block and indexing
+//! resumes from there. `--credentials-indexer-rebuild` drops the database and indexes
+//! again from genesis, which needs the state of every block (`--state-pruning archive`).
+
+pub mod store;
+
+use crate::{credentials::visibility_name, rpc::credentials::credential_type_name};
+use codec::Decode;
+use futures::StreamExt;
+use minimal_template_runtime::{
+	interface::{AccountId, Hash, OpaqueBlock},
+	Runtime, RuntimeEvent,
+};
+use pallet_freelance_credentials::{
+	metadata::ParsedMetadata, CredentialInfoOf, Credentials, Event as CredentialsEvent, Issuers,
+	OwnerCredentials,
+};
+use polkadot_sdk::{
+	frame_support::storage::StoragePrefixedMap,
+	frame_system::EventRecord,
+	sc_client_api::{Backend, BlockchainEvents, StorageProvider},
+	sp_blockchain::HeaderBackend,
+	sp_core::{storage::StorageKey, twox_128},
+	sp_runtime::traits::Header,
+	*,
+};
+use std::{collections::HashSet, marker::PhantomData, sync::Arc};
+use store::{IndexedEvent, Store};
+
+/// Log target of the indexer.
+const LOG_TARGET: &str = "credentials-indexer";
+
+/// Storage key of `frame_system::Events`, which `frame_system` keeps private.
+fn system_events_key() -> Vec<u8> {
+	[twox_128(b"System"), twox_128(b"Events")].concat()
+}
+
+/// Hex representation of a hash, as stored in the database.
+fn hex(hash: &Hash) -> String {
+	format!("{hash:?}")
+}
+
+/// Follows finalized blocks and feeds their credential events into a [`Store`].
+pub struct Indexer<C, B> {
+	client: Arc<C>,
+	store: Arc<Store>,
+	_backend: PhantomData<B>,
+}
+
+impl<C, B> Indexer<C, B>
+where
+	C: HeaderBackend<OpaqueBlock>
+		+ BlockchainEvents<OpaqueBlock>
+		+ StorageProvider<OpaqueBlock, B>
+		+ Send
+		+ Sync
+		+ 'static,
+	B: Backend<OpaqueBlock> + Send + Sync + 'static,
+{
+	/// Create an indexer writing to `store`.
+	///
+	/// With `rebuild`, everything indexed so far is dropped first.
+	pub fn new(client: Arc<C>, store: Arc<Store>, rebuild: bool) -> Result<Self, store::Error> {
+		if rebuild {
+			store.rollback_to(None)?;
+		}
+		Ok(Self { client, store, _backend: PhantomData })
+	}
+
+	/// Index every finalized block, then keep up with finality notifications.
+	pub async fn run(self) {
+		let mut finality_notifications = self.client.finality_notification_stream();
+		loop {
+			if let Err(error) = self.sync() {
+				log::error!(
+					target: LOG_TARGET,
+					"Indexing failed, retrying on next finalized block: {error}",
+				);
+			}
+			if finality_notifications.next().await.is_none() {
+				break;
+			}
+		}
+	}
+
+	/// Index all finalized blocks not indexed yet, rolling back first if needed.
+	fn sync(&self) -> Result<(), store::Error> {
+		let finalized = u64::from(self.client.info().finalized_number);
+
+		let next = match self.store.last_block()? {
+			None => 0,
+			Some((last, _)) => {
+				let ancestor = self.last_canonical_block(last)?;
+				if ancestor != Some(last) {
+					log::warn!(
+						target: LOG_TARGET,
+						"Indexed chain diverged from the canonical chain, rolling back to {ancestor:?}",
+					);
+					self.store.rollback_to(ancestor)?;
+				}
+				ancestor.map_or(0, |number| number + 1)
+			},
+		};
+
+		for number in next..=finalized {
+			let hash = self
+				.client
+				.hash(number.try_into()?)?
+				.ok_or_else(|| format!("finalized block {number} not found"))?;
+			let events =
+				if number == 0 { self.genesis_events(hash)? } else { self.block_events(hash)? };
+			self.store.index_block(number, &hex(&hash), &events)?;
+			if !events.is_empty() {
+				log::debug!(
+					target: LOG_TARGET,
+					"Indexed {} events of block #{number}",
+					events.len(),
+				);
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Newest indexed block, at or below `last`, that is still on the canonical chain.
+	fn last_canonical_block(&self, last: u64) -> Result<Option<u64>, store::Error> {
+		let mut number = last;
+		loop {
+			let canonical = self.client.hash(number.try_into()?)?.map(|hash| hex(&hash));
+			if canonical.is_some() && canonical == self.store.block_hash(number)? {
+				return Ok(Some(number));
+			}
+			match number.checked_sub(1) {
+				Some(previous) => number = previous,
+				None => return Ok(None),
+			}
+		}
+	}
+
+	fn storage<T: Decode>(&self, at: Hash, key: Vec<u8>) -> Result<Option<T>, store::Error> {
+		match self.client.storage(at, &StorageKey(key))? {
+			Some(data) => Ok(Some(T::decode(&mut &data.0[..])?)),
+			None => Ok(None),
+		}
+	}
+
+	fn metadata_at(&self, at: Hash, credential_id: &Hash) -> Result<Option<String>, store::Error> {
+		let credential: Option<CredentialInfoOf<Runtime>> =
+			self.storage(at, Credentials::<Runtime>::hashed_key_for(credential_id))?;
+		Ok(credential.map(|credential| String::from_utf8_lossy(&credential.metadata).into_owned()))
+	}
+
+	fn owner_credentials_at(
+		&self,
+		at: Hash,
+		owner: &AccountId,
+	) -> Result<Vec<Hash>, store::Error> {
+		let credential_ids: Option<Vec<Hash>> =
+			self.storage(at, OwnerCredentials::<Runtime>::hashed_key_for(owner))?;
+		Ok(credential_ids.unwrap_or_default())
+	}
+
+	/// Synthetic events for the credentials and issuers present in the genesis state.
+	fn genesis_events(&self, genesis: Hash) -> Result<Vec<IndexedEvent>, store::Error> {
+		// Map keys are the 32 byte storage prefix, a 16 byte Blake2_128 hash and the key itself
+		let map_key = |key: &StorageKey| key.0.get(48..).map(<[u8]>::to_vec).unwrap_or_default();
+		let mut events = Vec::new();
+
+		let prefix = StorageKey(Issuers::<Runtime>::final_prefix().to_vec());
+		for (key, value) in self.client.storage_pairs(genesis, Some(&prefix), None)? {
+			let issuer = AccountId::decode(&mut &map_key(&key)[..])?;
+			let name = Vec::<u8>::decode(&mut &value.0[..])?;
+			events.push(IndexedEvent::IssuerRegistered {
+				issuer: issuer.to_string(),
+				name: String::from_utf8_lossy(&name).into_owned(),
+			});
+		}
+
+		let prefix = StorageKey(Credentials::<Runtime>::final_prefix().to_vec());
+		for (key, value) in self.client.storage_pairs(genesis, Some(&prefix), None)? {
+			let credential_id = Hash::decode(&mut &map_key(&key)[..])?;
+			let credential = CredentialInfoOf::<Runtime>::decode(&mut &value.0[..])?;
+			let parsed = ParsedMetadata::parse(&credential.metadata);
+			events.push(IndexedEvent::Minted {
+				credential_id: hex(&credential_id),
+				owner: credential.owner.to_string(),
+				credential_type: credential_type_name(parsed.credential_type()).into(),
+				content_hash: hex(&credential_id),
+				visibility: visibility_name(parsed.visibility()).into(),
+				issuer: String::from_utf8_lossy(&parsed.issuer()).into_owned(),
+				metadata: Some(String::from_utf8_lossy(&credential.metadata).into_owned()),
+			});
+		}
+
+		Ok(events)
+	}
+
+	/// Credentials pallet events of a block.
+	fn block_events(&self, hash: Hash) -> Result<Vec<IndexedEvent>, store::Error> {
+		let records: Vec<EventRecord<RuntimeEvent, Hash>> = self
+			.storage(hash, system_events_key())?
+			.ok_or_else(|| format!("state of block {hash:?} is not available"))?;
+		let parent_hash = *self
+			.client
+			.header(hash)?
+			.ok_or_else(|| format!("header of block {hash:?} not found"))?
+			.parent_hash();
+
+		// Credentials already attributed to a purge of this block
+		let mut purged = HashSet::new();
+		let mut events = Vec::new();
+		for record in records {
+			let RuntimeEvent::FreelanceCredentials(event) = record.event else { continue };
+			let event = match event {
+				CredentialsEvent::CredentialMinted {
+					credential_id,
+					owner,
+					credential_type,
+					content_hash,
+					visibility,
+					issuer,
+					..
+				} => IndexedEvent::Minted {
+					credential_id: hex(&credential_id),
+					owner: owner.to_string(),
+					credential_type: credential_type_name(credential_type).into(),
+					content_hash: hex(&content_hash),
+					visibility: visibility_name(visibility).into(),
+					issuer: String::from_utf8_lossy(&issuer).into_owned(),
+					metadata: self.metadata_at(hash, &credential_id)?,
+				},
+				CredentialsEvent::CredentialUpdated {
+					credential_id,
+					owner,
+					credential_type,
+					content_hash,
+					visibility,
+					issuer,
+					changed_fields,
+					..
+				} => IndexedEvent::Updated {
+					credential_id: hex(&credential_id),
+					owner: owner.to_string(),
+					credential_type: credential_type_name(credential_type).into(),
+					content_hash: hex(&content_hash),
+					visibility: visibility_name(visibility).into(),
+					issuer: String::from_utf8_lossy(&issuer).into_owned(),
+					metadata: self.metadata_at(hash, &credential_id)?,
+					changed_fields: changed_fields
+						.iter()
+						.map(|field| format!("{field:?}"))
+						.collect(),
+				},
+				CredentialsEvent::CredentialDeleted { credential_id, owner, .. } =>
+					IndexedEvent::Deleted {
+						credential_id: hex(&credential_id),
+						owner: owner.to_string(),
+					},
+				CredentialsEvent::CredentialsMigrated { from, to, count } =>
+					IndexedEvent::Migrated { from: from.to_string(), to: to.to_string(), count },
+				CredentialsEvent::CredentialsPurged { owner, .. } => {
+					// The event only carries counts: diff the owner's list around the block
+					let remaining: HashSet<Hash> =
+						self.owner_credentials_at(hash, &owner)?.into_iter().collect();
+					let credential_ids = self
+						.owner_credentials_at(parent_hash, &owner)?
+						.into_iter()
+						.filter(|id| !remaining.contains(id) && purged.insert(*id))
+						.map(|id| hex(&id))
+						.collect();
+					IndexedEvent::Purged { owner: owner.to_string(), credential_ids }
+				},
+				CredentialsEvent::IssuerRegistered { issuer, name } =>
+					IndexedEvent::IssuerRegistered {
+						issuer: issuer.to_string(),
+						name: String::from_utf8_lossy(&name).into_owned(),
+					},
+				CredentialsEvent::IssuerRemoved { issuer } =>
+					IndexedEvent::IssuerRemoved { issuer: issuer.to_string() },
+				_ => continue,
+			};
+			events.push(event);
+		}
+
+		Ok(events)
+	}
+}
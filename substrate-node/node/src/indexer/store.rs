@@ -0,0 +1,489 @@
+//! SQLite storage of the credentials indexer.
+//!
+//! `history` is the source of truth: one row per indexed event, in chain order. The
+//! `credentials` and `issuers` tables are derived from it and can always be rebuilt by
+//! replaying `history`, which is how rollbacks are implemented.
+
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde::{Deserialize, Serialize};
+use std::{path::Path, sync::Mutex};
+
+/// Error returned by store operations.
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+const SCHEMA: &str = "
+	CREATE TABLE IF NOT EXISTS blocks (
+		number INTEGER PRIMARY KEY,
+		hash TEXT NOT NULL
+	);
+	CREATE TABLE IF NOT EXISTS history (
+		block_number INTEGER NOT NULL,
+		event_index INTEGER NOT NULL,
+		kind TEXT NOT NULL,
+		credential_id TEXT,
+		account TEXT NOT NULL,
+		other_account TEXT,
+		event TEXT NOT NULL,
+		PRIMARY KEY (block_number, event_index)
+	);
+	CREATE INDEX IF NOT EXISTS history_by_credential ON history (credential_id);
+	CREATE INDEX IF NOT EXISTS history_by_account ON history (account);
+	CREATE INDEX IF NOT EXISTS history_by_other_account ON history (other_account);
+	CREATE TABLE IF NOT EXISTS credentials (
+		id TEXT PRIMARY KEY,
+		owner TEXT NOT NULL,
+		credential_type TEXT NOT NULL,
+		content_hash TEXT NOT NULL,
+		visibility TEXT NOT NULL,
+		issuer TEXT NOT NULL,
+		metadata TEXT,
+		minted_block INTEGER NOT NULL,
+		updated_block INTEGER NOT NULL
+	);
+	CREATE INDEX IF NOT EXISTS credentials_by_owner ON credentials (owner);
+	CREATE INDEX IF NOT EXISTS credentials_by_issuer ON credentials (issuer);
+	CREATE TABLE IF NOT EXISTS issuers (
+		account TEXT PRIMARY KEY,
+		name TEXT NOT NULL,
+		registered_block INTEGER NOT NULL
+	);
+";
+
+/// A credentials pallet event, as recorded in `history`.
+///
+/// Hashes are `0x`-prefixed hex strings and accounts are SS58 addresses.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum IndexedEvent {
+	/// A credential was minted (or seeded at genesis).
+	Minted {
+		credential_id: String,
+		owner: String,
+		credential_type: String,
+		content_hash: String,
+		visibility: String,
+		issuer: String,
+		/// Metadata read from the block's state, if it was still available.
+		metadata: Option<String>,
+	},
+	/// A credential's metadata was replaced.
+	Updated {
+		credential_id: String,
+		owner: String,
+		credential_type: String,
+		content_hash: String,
+		visibility: String,
+		issuer: String,
+		metadata: Option<String>,
+		changed_fields: Vec<String>,
+	},
+	/// A credential was deleted by its owner.
+	Deleted { credential_id: String, owner: String },
+	/// All credentials of `from` moved to `to`.
+	Migrated { from: String, to: String, count: u32 },
+	/// Credentials were purged by their owner.
+	Purged { owner: String, credential_ids: Vec<String> },
+	/// An issuer was registered or renamed.
+	IssuerRegistered { issuer: String, name: String },
+	/// An issuer was removed.
+	IssuerRemoved { issuer: String },
+}
+
+impl IndexedEvent {
+	fn kind(&self) -> &'static str {
+		match self {
+			IndexedEvent::Minted { .. } => "minted",
+			IndexedEvent::Updated { .. } => "updated",
+			IndexedEvent::Deleted { .. } => "deleted",
+			IndexedEvent::Migrated { .. } => "migrated",
+			IndexedEvent::Purged { .. } => "purged",
+			IndexedEvent::IssuerRegistered { .. } => "issuerRegistered",
+			IndexedEvent::IssuerRemoved { .. } => "issuerRemoved",
+		}
+	}
+
+	fn credential_id(&self) -> Option<&str> {
+		match self {
+			IndexedEvent::Minted { credential_id, .. } |
+			IndexedEvent::Updated { credential_id, .. } |
+			IndexedEvent::Deleted { credential_id, .. } => Some(credential_id),
+			_ => None,
+		}
+	}
+
+	fn accounts(&self) -> (&str, Option<&str>) {
+		match self {
+			IndexedEvent::Minted { owner, .. } |
+			IndexedEvent::Updated { owner, .. } |
+			IndexedEvent::Deleted { owner, .. } |
+			IndexedEvent::Purged { owner, .. } => (owner, None),
+			IndexedEvent::Migrated { from, to, .. } => (from, Some(to)),
+			IndexedEvent::IssuerRegistered { issuer, .. } |
+			IndexedEvent::IssuerRemoved { issuer } => (issuer, None),
+		}
+	}
+}
+
+/// A credential as currently indexed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialRow {
+	pub id: String,
+	pub owner: String,
+	pub credential_type: String,
+	pub content_hash: String,
+	pub visibility: String,
+	pub issuer: String,
+	pub metadata: Option<String>,
+	pub minted_block: u64,
+	pub updated_block: u64,
+}
+
+/// A registered issuer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssuerRow {
+	pub account: String,
+	pub name: String,
+	pub registered_block: u64,
+}
+
+/// An entry of the event history.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryRow {
+	pub block_number: u64,
+	pub event_index: u32,
+	pub event: IndexedEvent,
+}
+
+/// Progress of the indexer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexerStatus {
+	/// Number of the last indexed (finalized) block.
+	pub last_block_number: Option<u64>,
+	/// Hash of the last indexed block.
+	pub last_block_hash: Option<String>,
+	/// Number of credentials currently indexed.
+	pub credentials: u64,
+	/// Number of registered issuers.
+	pub issuers: u64,
+}
+
+/// The indexer database.
+pub struct Store {
+	connection: Mutex<Connection>,
+}
+
+impl Store {
+	/// Open (or create) the database at `path`.
+	pub fn open(path: &Path) -> Result<Self, Error> {
+		let connection = Connection::open(path)?;
+		connection.pragma_update(None, "journal_mode", "WAL")?;
+		connection.execute_batch(SCHEMA)?;
+		Ok(Self { connection: Mutex::new(connection) })
+	}
+
+	fn connection(&self) -> Result<std::sync::MutexGuard<'_, Connection>, Error> {
+		self.connection.lock().map_err(|_| "indexer database lock poisoned".into())
+	}
+
+	/// Number and hash of the last indexed block.
+	pub fn last_block(&self) -> Result<Option<(u64, String)>, Error> {
+		Ok(self
+			.connection()?
+			.query_row("SELECT number, hash FROM blocks ORDER BY number DESC LIMIT 1", [], |row| {
+				Ok((row.get(0)?, row.get(1)?))
+			})
+			.optional()?)
+	}
+
+	/// Hash of the indexed block with the given number.
+	pub fn block_hash(&self, number: u64) -> Result<Option<String>, Error> {
+		Ok(self
+			.connection()?
+			.query_row("SELECT hash FROM blocks WHERE number = ?1", [number], |row| row.get(0))
+			.optional()?)
+	}
+
+	/// Record the credential events of a block.
+	pub fn index_block(
+		&self,
+		number: u64,
+		hash: &str,
+		events: &[IndexedEvent],
+	) -> Result<(), Error> {
+		let mut connection = self.connection()?;
+		let tx = connection.transaction()?;
+		tx.execute("INSERT INTO blocks (number, hash) VALUES (?1, ?2)", params![number, hash])?;
+		for (event_index, event) in events.iter().enumerate() {
+			let (account, other_account) = event.accounts();
+			tx.execute(
+				"INSERT INTO history
+					(block_number, event_index, kind, credential_id, account, other_account, event)
+				VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+				params![
+					number,
+					event_index as u32,
+					event.kind(),
+					event.credential_id(),
+					account,
+					other_account,
+					serde_json::to_string(event)?,
+				],
+			)?;
+			apply(&tx, number, event)?;
+		}
+		tx.commit()?;
+		Ok(())
+	}
+
+	/// Forget every block after `number` and rebuild the derived tables.
+	///
+	/// `None` forgets everything, for a rebuild from genesis.
+	pub fn rollback_to(&self, number: Option<u64>) -> Result<(), Error> {
+		let mut connection = self.connection()?;
+		let tx = connection.transaction()?;
+		// -1 is below every block number, including genesis
+		let keep = number.map_or(-1, |number| number as i64);
+		tx.execute("DELETE FROM blocks WHERE number > ?1", [keep])?;
+		tx.execute("DELETE FROM history WHERE block_number > ?1", [keep])?;
+		tx.execute("DELETE FROM credentials", [])?;
+		tx.execute("DELETE FROM issuers", [])?;
+
+		let history = {
+			let mut statement = tx.prepare(
+				"SELECT block_number, event FROM history ORDER BY block_number, event_index",
+			)?;
+			let rows = statement.query_map([], |row| {
+				Ok((row.get::<_, u64>(0)?, row.get::<_, String>(1)?))
+			})?;
+			rows.collect::<Result<Vec<_>, _>>()?
+		};
+		for (block_number, event) in history {
+			apply(&tx, block_number, &serde_json::from_str(&event)?)?;
+		}
+
+		tx.commit()?;
+		Ok(())
+	}
+
+	/// Progress of the indexer.
+	pub fn status(&self) -> Result<IndexerStatus, Error> {
+		let last_block = self.last_block()?;
+		let connection = self.connection()?;
+		let count = |table: &str| -> rusqlite::Result<u64> {
+			connection.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| row.get(0))
+		};
+		Ok(IndexerStatus {
+			last_block_number: last_block.as_ref().map(|(number, _)| *number),
+			last_block_hash: last_block.map(|(_, hash)| hash),
+			credentials: count("credentials")?,
+			issuers: count("issuers")?,
+		})
+	}
+
+	/// Credentials owned by `owner`, oldest first.
+	pub fn credentials_by_owner(
+		&self,
+		owner: &str,
+		include_private: bool,
+	) -> Result<Vec<CredentialRow>, Error> {
+		let connection = self.connection()?;
+		let mut statement = connection.prepare(
+			"SELECT id, owner, credential_type, content_hash, visibility, issuer, metadata,
+				minted_block, updated_block
+			FROM credentials
+			WHERE owner = ?1 AND (?2 OR visibility = 'public')
+			ORDER BY minted_block, rowid",
+		)?;
+		let rows = statement.query_map(params![owner, include_private], credential_row)?;
+		Ok(rows.collect::<Result<_, _>>()?)
+	}
+
+	/// A single credential.
+	pub fn credential(&self, id: &str) -> Result<Option<CredentialRow>, Error> {
+		Ok(self
+			.connection()?
+			.query_row(
+				"SELECT id, owner, credential_type, content_hash, visibility, issuer, metadata,
+					minted_block, updated_block
+				FROM credentials WHERE id = ?1",
+				[id],
+				credential_row,
+			)
+			.optional()?)
+	}
+
+	/// Registered issuers, ordered by name.
+	pub fn issuers(&self) -> Result<Vec<IssuerRow>, Error> {
+		let connection = self.connection()?;
+		let mut statement = connection
+			.prepare("SELECT account, name, registered_block FROM issuers ORDER BY name")?;
+		let rows = statement.query_map([], |row| {
+			Ok(IssuerRow { account: row.get(0)?, name: row.get(1)?, registered_block: row.get(2)? })
+		})?;
+		Ok(rows.collect::<Result<_, _>>()?)
+	}
+
+	/// Most recent events, newest first, optionally restricted to a credential and/or an
+	/// account.
+	pub fn history(
+		&self,
+		credential_id: Option<&str>,
+		account: Option<&str>,
+		limit: u32,
+	) -> Result<Vec<HistoryRow>, Error> {
+		let connection = self.connection()?;
+		let mut statement = connection.prepare(
+			"SELECT block_number, event_index, event FROM history
+			WHERE (?1 IS NULL OR credential_id = ?1)
+				AND (?2 IS NULL OR account = ?2 OR other_account = ?2)
+			ORDER BY block_number DESC, event_index DESC
+			LIMIT ?3",
+		)?;
+		let rows = statement.query_map(params![credential_id, account, limit], |row| {
+			Ok((row.get::<_, u64>(0)?, row.get::<_, u32>(1)?, row.get::<_, String>(2)?))
+		})?;
+		rows.map(|row| -> Result<HistoryRow, Error> {
+			let (block_number, event_index, event) = row?;
+			Ok(HistoryRow { block_number, event_index, event: serde_json::from_str(&event)? })
+		})
+		.collect()
+	}
+}
+
+fn credential_row(row: &rusqlite::Row) -> rusqlite::Result<CredentialRow> {
+	Ok(CredentialRow {
+		id: row.get(0)?,
+		owner: row.get(1)?,
+		credential_type: row.get(2)?,
+		content_hash: row.get(3)?,
+		visibility: row.get(4)?,
+		issuer: row.get(5)?,
+		metadata: row.get(6)?,
+		minted_block: row.get(7)?,
+		updated_block: row.get(8)?,
+	})
+}
+
+/// Apply an event to the derived tables.
+fn apply(tx: &Transaction, block_number: u64, event: &IndexedEvent) -> rusqlite::Result<()> {
+	match event {
+		IndexedEvent::Minted {
+			credential_id,
+			owner,
+			credential_type,
+			content_hash,
+			visibility,
+			issuer,
+			metadata,
+		} => {
+			tx.execute(
+				"INSERT OR REPLACE INTO credentials
+					(id, owner, credential_type, content_hash, visibility, issuer, metadata,
+						minted_block, updated_block)
+				VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8)",
+				params![
+					credential_id,
+					owner,
+					credential_type,
+					content_hash,
+					visibility,
+					issuer,
+					metadata,
+					block_number
+				],
+			)?;
+		},
+		IndexedEvent::Updated {
+			credential_id,
+			credential_type,
+			content_hash,
+			visibility,
+			issuer,
+			metadata,
+			..
+		} => {
+			tx.execute(
+				"UPDATE credentials
+				SET credential_type = ?2, content_hash = ?3, visibility = ?4, issuer = ?5,
+					metadata = ?6, updated_block = ?7
+				WHERE id = ?1",
+				params![
+					credential_id,
+					credential_type,
+					content_hash,
+					visibility,
+					issuer,
+					metadata,
+					block_number
+				],
+			)?;
+		},
+		IndexedEvent::Deleted { credential_id, .. } => {
+			tx.execute("DELETE FROM credentials WHERE id = ?1", [credential_id])?;
+		},
+		IndexedEvent::Migrated { from, to, .. } => {
+			tx.execute("UPDATE credentials SET owner = ?2 WHERE owner = ?1", [from, to])?;
+		},
+		IndexedEvent::Purged { credential_ids, .. } =>
+			for credential_id in credential_ids {
+				tx.execute("DELETE FROM credentials WHERE id = ?1", [credential_id])?;
+			},
+		IndexedEvent::IssuerRegistered { issuer, name } => {
+			tx.execute(
+				"INSERT OR REPLACE INTO issuers (account, name, registered_block)
+				VALUES (?1, ?2, ?3)",
+				params![issuer, name, block_number],
+			)?;
+		},
+		IndexedEvent::IssuerRemoved { issuer } => {
+			tx.execute("DELETE FROM issuers WHERE account = ?1", [issuer])?;
+		},
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn minted(id: &str, owner: &str) -> IndexedEvent {
+		IndexedEvent::Minted {
+			credential_id: id.into(),
+			owner: owner.into(),
+			credential_type: "skill".into(),
+			content_hash: id.into(),
+			visibility: "public".into(),
+			issuer: "Upwork".into(),
+			metadata: None,
+		}
+	}
+
+	#[test]
+	fn rollback_replays_history() {
+		let store = Store::open(Path::new(":memory:")).unwrap();
+		store.index_block(0, "0x00", &[minted("0xa", "alice")]).unwrap();
+		store
+			.index_block(
+				1,
+				"0x01",
+				&[
+					minted("0xb", "alice"),
+					IndexedEvent::Migrated { from: "alice".into(), to: "bob".into(), count: 2 },
+				],
+			)
+			.unwrap();
+		assert_eq!(store.credentials_by_owner("bob", false).unwrap().len(), 2);
+
+		store.rollback_to(Some(0)).unwrap();
+
+		assert_eq!(store.last_block().unwrap(), Some((0, "0x00".into())));
+		let alice = store.credentials_by_owner("alice", false).unwrap();
+		assert_eq!(alice.iter().map(|row| row.id.as_str()).collect::<Vec<_>>(), vec!["0xa"]);
+		assert!(store.credentials_by_owner("bob", false).unwrap().is_empty());
+		assert_eq!(store.history(None, Some("alice"), 10).unwrap().len(), 1);
+	}
+}
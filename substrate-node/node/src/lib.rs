@@ -17,5 +17,9 @@
 
 pub mod chain_spec;
 pub(crate) mod cli;
+pub mod credentials;
+pub mod credentials_cmd;
+pub mod dev_clock;
+pub mod indexer;
 pub mod rpc;
 pub mod service;
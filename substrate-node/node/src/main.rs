@@ -21,6 +21,10 @@
 mod chain_spec;
 mod cli;
 mod command;
+mod credentials;
+mod credentials_cmd;
+mod dev_clock;
+mod indexer;
 mod rpc;
 mod service;
 
@@ -22,9 +22,17 @@
 
 #![warn(missing_docs)]
 
+pub mod credentials;
+pub mod dev;
+pub mod did;
+pub mod indexer;
+
+use futures::channel::mpsc;
 use jsonrpsee::RpcModule;
-use minimal_template_runtime::interface::{AccountId, Nonce, OpaqueBlock};
+use minimal_template_runtime::interface::{AccountId, BlockNumber, Hash, Nonce, OpaqueBlock};
 use polkadot_sdk::{
+	sc_client_api::{Backend, ProofProvider, StorageProvider},
+	sc_consensus_manual_seal::EngineCommand,
 	sc_transaction_pool_api::TransactionPool,
 	sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata},
 	*,
@@ -32,17 +40,27 @@ use polkadot_sdk::{
 use std::sync::Arc;
 
 /// Full client dependencies.
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, P, B> {
 	/// The client instance to use.
 	pub client: Arc<C>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
+	/// The backend instance to use, for offchain database access.
+	pub backend: Arc<B>,
+	/// Database of the credentials indexer, when enabled.
+	pub indexer: Option<Arc<crate::indexer::store::Store>>,
+	/// Channel of manual seal commands, in manual seal modes.
+	pub command_sink: Option<mpsc::Sender<EngineCommand<Hash>>>,
+	/// Whether blocks are sealed on `engine_*` RPC requests sent through `command_sink`.
+	pub engine_rpc: bool,
+	/// Clock of sealed blocks, served by the time travel RPC in manual and instant seal modes.
+	pub clock: Option<crate::dev_clock::DevClock>,
 }
 
 #[docify::export]
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P>(
-	deps: FullDeps<C, P>,
+pub fn create_full<C, P, B>(
+	deps: FullDeps<C, P, B>,
 ) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: Send
@@ -51,16 +69,40 @@ where
 		+ sp_api::ProvideRuntimeApi<OpaqueBlock>
 		+ HeaderBackend<OpaqueBlock>
 		+ HeaderMetadata<OpaqueBlock, Error = BlockChainError>
+		+ StorageProvider<OpaqueBlock, B>
+		+ ProofProvider<OpaqueBlock>
 		+ 'static,
 	C::Api: sp_block_builder::BlockBuilder<OpaqueBlock>,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<OpaqueBlock, AccountId, Nonce>,
+	C::Api: pallet_did::runtime_api::DidApi<OpaqueBlock, AccountId, BlockNumber>,
 	P: TransactionPool + 'static,
+	B: Backend<OpaqueBlock> + Send + Sync + 'static,
 {
-	use polkadot_sdk::substrate_frame_rpc_system::{System, SystemApiServer};
+	use self::{
+		credentials::{Credentials, CredentialsApiServer},
+		dev::{Dev, DevApiServer},
+		did::{DidResolver, DidResolverApiServer},
+		indexer::{CredentialsIndexer, CredentialsIndexerApiServer},
+	};
+	use polkadot_sdk::{
+		sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApiServer},
+		substrate_frame_rpc_system::{System, SystemApiServer},
+	};
 	let mut module = RpcModule::new(());
-	let FullDeps { client, pool } = deps;
+	let FullDeps { client, pool, backend, indexer, command_sink, engine_rpc, clock } = deps;
 
 	module.merge(System::new(client.clone(), pool.clone()).into_rpc())?;
+	module.merge(Credentials::new(client.clone(), backend.offchain_storage()).into_rpc())?;
+	module.merge(DidResolver::new(client.clone()).into_rpc())?;
+	if let Some(store) = indexer {
+		module.merge(CredentialsIndexer::new(store).into_rpc())?;
+	}
+	if let Some(clock) = clock {
+		module.merge(Dev::new(clock, command_sink.clone()).into_rpc())?;
+	}
+	if let Some(command_sink) = command_sink.filter(|_| engine_rpc) {
+		module.merge(ManualSeal::new(command_sink).into_rpc())?;
+	}
 
 	Ok(module)
 }
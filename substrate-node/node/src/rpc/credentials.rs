@@ -0,0 +1,311 @@
+//! Credential search and export RPCs.
+//!
+//! Search answers marketplace queries such as "which accounts hold a Rust skill credential"
+//! from the offchain index written by `pallet-freelance-credentials`, instead of scanning
+//! on-chain storage. The journal is replayed into an in-memory index on demand; see
+//! `pallet_freelance_credentials::offchain_index` for its layout. The node must run with
+//! `--enable-offchain-indexing true` from genesis (or be resynced with it) for the journal
+//! to be complete.
+//!
+//! Portfolio export returns an account's credentials together with Merkle proofs of the
+//! storage entries they were read from and the header of the block they were read at, so
+//! anyone trusting that block's state root can check the export was not forged. The same
+//! export can be rendered as W3C Verifiable Credentials, see `forge_verify::vc`.
+
+use crate::credentials;
+use codec::Decode;
+use forge_verify::{
+	export::PortfolioExport,
+	vc::{self, VerifiableCredential},
+};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{ErrorObject, ErrorObjectOwned},
+};
+use minimal_template_runtime::{
+	interface::{AccountId, Hash, OpaqueBlock},
+	Runtime,
+};
+use pallet_freelance_credentials::{
+	offchain_index::{self, IndexEntry, IndexedCredential},
+	CredentialType, OffchainIndexHead, OffchainIndexTip, Visibility,
+};
+use polkadot_sdk::{
+	sc_client_api::{Backend, ProofProvider, StorageProvider},
+	sp_blockchain::HeaderBackend,
+	sp_core::{
+		offchain::{OffchainStorage, STORAGE_PREFIX},
+		storage::StorageKey,
+	},
+	*,
+};
+use serde::Serialize;
+use std::{
+	collections::BTreeMap,
+	marker::PhantomData,
+	sync::{Arc, Mutex},
+};
+
+/// Error code returned when the offchain index is missing or incomplete.
+const INDEX_UNAVAILABLE: i32 = 1;
+/// Error code returned when chain state or index entries cannot be read.
+const INTERNAL_ERROR: i32 = 2;
+/// Error code returned when the requested block is unknown.
+const UNKNOWN_BLOCK: i32 = 3;
+
+/// A credential matching a search.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialMatch {
+	/// ID of the credential.
+	pub credential_id: Hash,
+	/// Current owner of the credential.
+	pub owner: AccountId,
+	/// Credential type (`skill`, `review`, `payment`, `certification` or `other`).
+	pub credential_type: String,
+	/// Normalized (trimmed, lowercase) credential name.
+	pub name: String,
+	/// Normalized (trimmed, lowercase) issuer name.
+	pub issuer: String,
+}
+
+impl From<&IndexedCredential<AccountId, Hash>> for CredentialMatch {
+	fn from(record: &IndexedCredential<AccountId, Hash>) -> Self {
+		Self {
+			credential_id: record.credential_id,
+			owner: record.owner.clone(),
+			credential_type: credential_type_name(record.credential_type).into(),
+			name: String::from_utf8_lossy(&record.name).into_owned(),
+			issuer: String::from_utf8_lossy(&record.issuer).into_owned(),
+		}
+	}
+}
+
+/// Lowercase name of a credential type, as used in credential metadata.
+pub fn credential_type_name(credential_type: CredentialType) -> &'static str {
+	match credential_type {
+		CredentialType::Skill => "skill",
+		CredentialType::Review => "review",
+		CredentialType::Payment => "payment",
+		CredentialType::Certification => "certification",
+		CredentialType::Other => "other",
+	}
+}
+
+/// Credential search RPC methods.
+#[rpc(server)]
+pub trait CredentialsApi {
+	/// Public skill credentials whose name equals `skill`, ignoring case and surrounding
+	/// whitespace.
+	#[method(name = "credentials_searchBySkill")]
+	fn search_by_skill(&self, skill: String) -> RpcResult<Vec<CredentialMatch>>;
+
+	/// Public credentials of any type whose issuer equals `issuer`, ignoring case and
+	/// surrounding whitespace.
+	#[method(name = "credentials_searchByIssuer")]
+	fn search_by_issuer(&self, issuer: String) -> RpcResult<Vec<CredentialMatch>>;
+
+	/// Credentials of `account` at block `at` (the best block by default), with storage
+	/// proofs of every `OwnerCredentials` and `Credentials` entry they were read from.
+	#[method(name = "credentials_exportPortfolio")]
+	fn export_portfolio(
+		&self,
+		account: AccountId,
+		at: Option<Hash>,
+	) -> RpcResult<PortfolioExport>;
+
+	/// Credentials of `account` at block `at` (the best block by default), rendered as W3C
+	/// Verifiable Credentials whose proofs are the storage proofs of
+	/// `credentials_exportPortfolio`.
+	#[method(name = "credentials_exportVerifiableCredentials")]
+	fn export_verifiable_credentials(
+		&self,
+		account: AccountId,
+		at: Option<Hash>,
+	) -> RpcResult<Vec<VerifiableCredential>>;
+}
+
+/// Current set of credentials, rebuilt from the offchain journal.
+#[derive(Default)]
+struct SearchIndex {
+	/// Number of journal entries applied so far.
+	applied: u64,
+	/// Link of the last entry applied, which tells the journals of different forks apart.
+	link: Hash,
+	/// Latest record of every live credential.
+	credentials: BTreeMap<Hash, IndexedCredential<AccountId, Hash>>,
+}
+
+impl SearchIndex {
+	fn apply(&mut self, entry: IndexEntry<AccountId, Hash>) {
+		self.link = offchain_index::next_link::<<Runtime as frame_system::Config>::Hashing, _>(
+			&self.link, &entry,
+		);
+		match entry {
+			IndexEntry::Upsert(record) => {
+				self.credentials.insert(record.credential_id, record);
+			},
+			IndexEntry::Remove(credential_id) => {
+				self.credentials.remove(&credential_id);
+			},
+		}
+		self.applied += 1;
+	}
+
+	/// Apply the entries following the last one applied until `head` entries are, and
+	/// return whether the index then ends on the entry linked `tip`.
+	///
+	/// Returns `false` if an entry is missing, or if the entries applied so far are not the
+	/// journal of the chain ending on `tip`, such as after a reorg.
+	fn catch_up(
+		&mut self,
+		storage: &impl OffchainStorage,
+		head: u64,
+		tip: Hash,
+	) -> RpcResult<bool> {
+		while self.applied < head {
+			let Some(raw) = storage.get(STORAGE_PREFIX, &offchain_index::entry_key(&self.link))
+			else {
+				return Ok(false);
+			};
+			let entry = IndexEntry::decode(&mut &raw[..]).map_err(internal_error)?;
+			self.apply(entry);
+		}
+		Ok(self.link == tip)
+	}
+}
+
+/// Implementation of [`CredentialsApiServer`].
+pub struct Credentials<C, B: Backend<OpaqueBlock>> {
+	client: Arc<C>,
+	offchain_storage: Option<B::OffchainStorage>,
+	index: Mutex<SearchIndex>,
+	_backend: PhantomData<B>,
+}
+
+impl<C, B: Backend<OpaqueBlock>> Credentials<C, B> {
+	/// Create a new instance reading the journal from `offchain_storage`.
+	///
+	/// `offchain_storage` is `None` when the backend has no offchain database, in which
+	/// case every search fails with an explanatory error.
+	pub fn new(client: Arc<C>, offchain_storage: Option<B::OffchainStorage>) -> Self {
+		Self {
+			client,
+			offchain_storage,
+			index: Mutex::new(SearchIndex::default()),
+			_backend: PhantomData,
+		}
+	}
+}
+
+impl<C, B> Credentials<C, B>
+where
+	C: HeaderBackend<OpaqueBlock> + StorageProvider<OpaqueBlock, B>,
+	B: Backend<OpaqueBlock>,
+{
+	/// Number of journal entries written up to the best block, and the link of the last one.
+	fn journal_head(&self) -> RpcResult<(u64, Hash)> {
+		let best_hash = self.client.info().best_hash;
+		let head = self.read_value(best_hash, OffchainIndexHead::<Runtime>::hashed_key())?;
+		let tip = self.read_value(best_hash, OffchainIndexTip::<Runtime>::hashed_key())?;
+		Ok((head, tip))
+	}
+
+	/// Decoded storage value under `key` at block `at`, or its default if there is none.
+	fn read_value<T: Decode + Default>(&self, at: Hash, key: [u8; 32]) -> RpcResult<T> {
+		match self.client.storage(at, &StorageKey(key.to_vec())).map_err(internal_error)? {
+			Some(data) => T::decode(&mut &data.0[..]).map_err(internal_error),
+			None => Ok(T::default()),
+		}
+	}
+
+	/// Catch up with the journal and return the public credentials matching `filter`.
+	fn search(
+		&self,
+		filter: impl Fn(&IndexedCredential<AccountId, Hash>) -> bool,
+	) -> RpcResult<Vec<CredentialMatch>> {
+		let storage = self.offchain_storage.as_ref().ok_or_else(|| {
+			index_unavailable("the node has no offchain database".into())
+		})?;
+		let (head, tip) = self.journal_head()?;
+
+		let mut index = self.index.lock().map_err(|_| internal_error("search index poisoned"))?;
+		// The best chain moved to another fork: replay the journal of the new best chain from
+		// scratch
+		if !index.catch_up(storage, head, tip)? {
+			*index = SearchIndex::default();
+			if !index.catch_up(storage, head, tip)? {
+				return Err(index_unavailable(format!(
+					"offchain index entry {} is missing; run the node with \
+					 `--enable-offchain-indexing true` and resync",
+					index.applied
+				)));
+			}
+		}
+
+		Ok(index
+			.credentials
+			.values()
+			.filter(|record| record.visibility == Visibility::Public && filter(record))
+			.map(CredentialMatch::from)
+			.collect())
+	}
+}
+
+impl<C, B> CredentialsApiServer for Credentials<C, B>
+where
+	C: HeaderBackend<OpaqueBlock>
+		+ StorageProvider<OpaqueBlock, B>
+		+ ProofProvider<OpaqueBlock>
+		+ Send
+		+ Sync
+		+ 'static,
+	B: Backend<OpaqueBlock> + Send + Sync + 'static,
+{
+	fn search_by_skill(&self, skill: String) -> RpcResult<Vec<CredentialMatch>> {
+		let skill = offchain_index::normalize(skill.as_bytes());
+		self.search(|record| {
+			record.credential_type == CredentialType::Skill && record.name[..] == skill[..]
+		})
+	}
+
+	fn search_by_issuer(&self, issuer: String) -> RpcResult<Vec<CredentialMatch>> {
+		let issuer = offchain_index::normalize(issuer.as_bytes());
+		self.search(|record| record.issuer[..] == issuer[..])
+	}
+
+	fn export_portfolio(
+		&self,
+		account: AccountId,
+		at: Option<Hash>,
+	) -> RpcResult<PortfolioExport> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		credentials::export_portfolio::<C, B>(&*self.client, account, at).map_err(read_error)
+	}
+
+	fn export_verifiable_credentials(
+		&self,
+		account: AccountId,
+		at: Option<Hash>,
+	) -> RpcResult<Vec<VerifiableCredential>> {
+		let export = self.export_portfolio(account, at)?;
+		Ok(vc::render_portfolio(&export))
+	}
+}
+
+fn index_unavailable(message: String) -> ErrorObjectOwned {
+	ErrorObject::owned(INDEX_UNAVAILABLE, message, None::<()>)
+}
+
+fn read_error(error: credentials::Error) -> ErrorObjectOwned {
+	match error {
+		credentials::Error::UnknownBlock(_) =>
+			ErrorObject::owned(UNKNOWN_BLOCK, error.to_string(), None::<()>),
+		error => internal_error(error),
+	}
+}
+
+fn internal_error(error: impl std::fmt::Display) -> ErrorObjectOwned {
+	ErrorObject::owned(INTERNAL_ERROR, error.to_string(), None::<()>)
+}
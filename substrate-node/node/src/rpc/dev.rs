@@ -0,0 +1,129 @@
+//! Dev-chain time travel RPC.
+//!
+//! Served in manual and instant seal modes only. Shifts the clock of sealed blocks (see
+//! [`DevClock`]) and, with manual seal, seals runs of empty blocks to jump ahead in block
+//! numbers. Methods changing the chain are unsafe, i.e. need `--rpc-methods unsafe` on
+//! publicly exposed nodes.
+//!
+//! Every block must be in a later Aura slot than its parent, so the clock can be moved back
+//! but block timestamps never decrease, and each sealed block moves them at least one slot
+//! forward.
+
+use crate::dev_clock::DevClock;
+use futures::{
+	channel::{mpsc, oneshot},
+	SinkExt,
+};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{ErrorObject, ErrorObjectOwned, INVALID_PARAMS_CODE},
+	Extensions,
+};
+use minimal_template_runtime::interface::Hash;
+use polkadot_sdk::{sc_consensus_manual_seal::EngineCommand, sc_rpc_api::check_if_safe, *};
+
+/// Error code returned when blocks cannot be sealed on request.
+const MANUAL_SEAL_UNAVAILABLE: i32 = 1;
+/// Error code returned when sealing a block fails.
+const SEAL_FAILED: i32 = 2;
+
+/// Time travel RPC methods.
+#[rpc(server)]
+pub trait DevApi {
+	/// Offset of the clock of sealed blocks from the system time, in milliseconds.
+	#[method(name = "dev_timestampOffset")]
+	fn timestamp_offset(&self) -> RpcResult<i64>;
+
+	/// Timestamp the next sealed blocks from `timestamp` (milliseconds since the Unix epoch)
+	/// onwards. Returns the new offset from the system time.
+	#[method(name = "dev_setTimestamp", with_extensions)]
+	fn set_timestamp(&self, timestamp: u64) -> RpcResult<i64>;
+
+	/// Move the clock of sealed blocks `delta` milliseconds forward (backward when
+	/// negative). Returns the new offset from the system time.
+	#[method(name = "dev_offsetTimestamp", with_extensions)]
+	fn offset_timestamp(&self, delta: i64) -> RpcResult<i64>;
+
+	/// Seal `count` empty blocks on top of the best block, finalizing them when `finalize`
+	/// is set. Returns the hash of the last one. Manual seal only.
+	#[method(name = "dev_advanceBlocks", with_extensions)]
+	async fn advance_blocks(&self, count: u32, finalize: bool) -> RpcResult<Hash>;
+}
+
+/// Implementation of [`DevApiServer`].
+pub struct Dev {
+	clock: DevClock,
+	command_sink: Option<mpsc::Sender<EngineCommand<Hash>>>,
+}
+
+impl Dev {
+	/// Create a new instance shifting `clock`, and sealing blocks through `command_sink`
+	/// when blocks are sealed by manual seal.
+	pub fn new(
+		clock: DevClock,
+		command_sink: Option<mpsc::Sender<EngineCommand<Hash>>>,
+	) -> Self {
+		Self { clock, command_sink }
+	}
+}
+
+#[jsonrpsee::core::async_trait]
+impl DevApiServer for Dev {
+	fn timestamp_offset(&self) -> RpcResult<i64> {
+		Ok(self.clock.offset())
+	}
+
+	fn set_timestamp(&self, ext: &Extensions, timestamp: u64) -> RpcResult<i64> {
+		check_if_safe(ext)?;
+		Ok(self.clock.set(timestamp))
+	}
+
+	fn offset_timestamp(&self, ext: &Extensions, delta: i64) -> RpcResult<i64> {
+		check_if_safe(ext)?;
+		Ok(self.clock.shift(delta))
+	}
+
+	async fn advance_blocks(
+		&self,
+		ext: &Extensions,
+		count: u32,
+		finalize: bool,
+	) -> RpcResult<Hash> {
+		check_if_safe(ext)?;
+		let mut sink = self.command_sink.clone().ok_or_else(|| {
+			ErrorObject::owned(
+				MANUAL_SEAL_UNAVAILABLE,
+				"blocks can only be advanced with manual seal",
+				None::<()>,
+			)
+		})?;
+		if count == 0 {
+			return Err(ErrorObject::owned(
+				INVALID_PARAMS_CODE,
+				"count must be at least 1",
+				None::<()>,
+			));
+		}
+
+		let mut last = None;
+		for _ in 0..count {
+			let (sender, receiver) = oneshot::channel();
+			sink.send(EngineCommand::SealNewBlock {
+				create_empty: true,
+				finalize,
+				parent_hash: None,
+				sender: Some(sender),
+			})
+			.await
+			.map_err(seal_failed)?;
+			let block = receiver.await.map_err(seal_failed)?.map_err(seal_failed)?;
+			last = Some(block.hash);
+		}
+		Ok(last.expect("at least one block was sealed; qed"))
+	}
+}
+
+fn seal_failed(error: impl std::fmt::Display) -> ErrorObjectOwned {
+	ErrorObject::owned(SEAL_FAILED, error.to_string(), None::<()>)
+}
@@ -0,0 +1,178 @@
+//! DID resolution RPC.
+//!
+//! Resolves `did:forge:<account>` DIDs through `pallet-did`'s runtime API and renders them as
+//! W3C DID Core documents, wrapped in a DID resolution result:
+//!
+//! ```json
+//! { "didDocument": { "@context": [...], "id": "did:forge:5Grw...", ... },
+//!   "didDocumentMetadata": { "deactivated": false, "updated": 42 } }
+//! ```
+//!
+//! The controller account is published as the `#controller` verification method, usable
+//! for every verification relationship except key agreement.
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{ErrorObject, ErrorObjectOwned},
+};
+use minimal_template_runtime::interface::{AccountId, BlockNumber, Hash, OpaqueBlock};
+use pallet_did::{
+	runtime_api::DidApi, DidDocument, PublicKey, VerificationRelationship, CONTROLLER_FRAGMENT,
+};
+use polkadot_sdk::{
+	sp_api::ProvideRuntimeApi, sp_blockchain::HeaderBackend, sp_core::hexdisplay::HexDisplay, *,
+};
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+
+/// Error code returned for malformed DIDs.
+const INVALID_DID: i32 = 1;
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i32 = 2;
+
+/// Context of DID Core documents.
+const DID_CONTEXT: &str = "https://www.w3.org/ns/did/v1";
+
+/// Prefix of `did:forge` DIDs.
+const DID_PREFIX: &str = "did:forge:";
+
+/// DID resolution RPC methods.
+#[rpc(server)]
+pub trait DidResolverApi {
+	/// DID resolution result of `did` (`did:forge:<address>` or a bare address) at block
+	/// `at` (the best block by default).
+	#[method(name = "did_resolve")]
+	fn resolve(&self, did: String, at: Option<Hash>) -> RpcResult<Value>;
+}
+
+/// Implementation of [`DidResolverApiServer`].
+pub struct DidResolver<C> {
+	client: Arc<C>,
+}
+
+impl<C> DidResolver<C> {
+	/// Create a new instance resolving DIDs through `client`.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C> DidResolverApiServer for DidResolver<C>
+where
+	C: ProvideRuntimeApi<OpaqueBlock> + HeaderBackend<OpaqueBlock> + Send + Sync + 'static,
+	C::Api: DidApi<OpaqueBlock, AccountId, BlockNumber>,
+{
+	fn resolve(&self, did: String, at: Option<Hash>) -> RpcResult<Value> {
+		let account = did
+			.strip_prefix(DID_PREFIX)
+			.unwrap_or(&did)
+			.parse::<AccountId>()
+			.map_err(|_| ErrorObject::owned(INVALID_DID, "invalid did:forge DID", Some(&did)))?;
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let document = self.client.runtime_api().resolve(at, account).map_err(runtime_error)?;
+
+		Ok(json!({
+			"didDocument": render(&document),
+			"didDocumentMetadata": {
+				"deactivated": document.deactivated,
+				"updated": document.updated,
+			},
+		}))
+	}
+}
+
+/// DID of an account.
+fn did(account: &AccountId) -> String {
+	format!("{DID_PREFIX}{account}")
+}
+
+/// Render a resolved document as a DID Core document.
+fn render(document: &DidDocument<AccountId, BlockNumber>) -> Value {
+	let subject = did(&document.did);
+	let controller = did(&document.controller);
+	let mut rendered = Map::new();
+	rendered.insert("@context".into(), json!([DID_CONTEXT]));
+	rendered.insert("id".into(), json!(subject));
+	if document.deactivated {
+		return Value::Object(rendered);
+	}
+	rendered.insert("controller".into(), json!(controller));
+
+	let method_id = |fragment: &[u8]| format!("{subject}#{}", String::from_utf8_lossy(fragment));
+	let controller_id = method_id(CONTROLLER_FRAGMENT);
+
+	let mut methods = vec![json!({
+		"id": controller_id,
+		"type": "Sr25519VerificationKey2020",
+		"controller": controller,
+		"publicKeyHex": hex(document.controller.as_ref()),
+	})];
+	let relationships = [
+		(VerificationRelationship::Authentication, "authentication"),
+		(VerificationRelationship::AssertionMethod, "assertionMethod"),
+		(VerificationRelationship::KeyAgreement, "keyAgreement"),
+		(VerificationRelationship::CapabilityInvocation, "capabilityInvocation"),
+		(VerificationRelationship::CapabilityDelegation, "capabilityDelegation"),
+	];
+	let mut references: Vec<Vec<String>> = relationships
+		.iter()
+		.map(|(relationship, _)| match relationship {
+			VerificationRelationship::KeyAgreement => Vec::new(),
+			_ => vec![controller_id.clone()],
+		})
+		.collect();
+
+	for method in &document.verification_methods {
+		let (key_type, public_key): (&str, &[u8]) = match &method.public_key {
+			PublicKey::Sr25519(key) => ("Sr25519VerificationKey2020", &key[..]),
+			PublicKey::Ed25519(key) => ("Ed25519VerificationKey2020", &key[..]),
+			PublicKey::Ecdsa(key) => ("EcdsaSecp256k1VerificationKey2019", &key[..]),
+		};
+		let id = method_id(&method.fragment);
+		methods.push(json!({
+			"id": id,
+			"type": key_type,
+			"controller": controller,
+			"publicKeyHex": hex(public_key),
+		}));
+		for (index, (relationship, _)) in relationships.iter().enumerate() {
+			if method.relationships.contains(relationship) {
+				references[index].push(id.clone());
+			}
+		}
+	}
+
+	rendered.insert("verificationMethod".into(), Value::Array(methods));
+	for ((_, name), ids) in relationships.iter().zip(references) {
+		if !ids.is_empty() {
+			rendered.insert((*name).into(), json!(ids));
+		}
+	}
+
+	if !document.services.is_empty() {
+		let services = document
+			.services
+			.iter()
+			.map(|service| {
+				json!({
+					"id": method_id(&service.fragment),
+					"type": String::from_utf8_lossy(&service.service_type),
+					"serviceEndpoint": String::from_utf8_lossy(&service.endpoint),
+				})
+			})
+			.collect();
+		rendered.insert("service".into(), Value::Array(services));
+	}
+
+	Value::Object(rendered)
+}
+
+fn hex(bytes: &[u8]) -> String {
+	HexDisplay::from(&bytes).to_string()
+}
+
+fn runtime_error(error: impl std::fmt::Display) -> ErrorObjectOwned {
+	ErrorObject::owned(RUNTIME_ERROR, error.to_string(), None::<()>)
+}
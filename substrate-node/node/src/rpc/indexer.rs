@@ -0,0 +1,122 @@
+//! RPC methods querying the embedded credentials indexer.
+//!
+//! Only available when the node runs with `--credentials-indexer <path>`. Results reflect
+//! finalized blocks only.
+
+use crate::indexer::store::{CredentialRow, HistoryRow, IndexerStatus, IssuerRow, Store};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{ErrorObject, ErrorObjectOwned},
+};
+use minimal_template_runtime::interface::AccountId;
+use std::sync::Arc;
+
+/// Error code returned for malformed parameters.
+const INVALID_PARAMS: i32 = 1;
+/// Error code returned when the database cannot be read.
+const DATABASE_ERROR: i32 = 2;
+
+/// Default number of history entries returned.
+const DEFAULT_HISTORY_LIMIT: u32 = 100;
+/// Maximum number of history entries returned.
+const MAX_HISTORY_LIMIT: u32 = 1_000;
+
+/// Credentials indexer RPC methods.
+#[rpc(server)]
+pub trait CredentialsIndexerApi {
+	/// Last indexed block and table sizes.
+	#[method(name = "credentialsIndexer_status")]
+	fn status(&self) -> RpcResult<IndexerStatus>;
+
+	/// Credentials owned by `owner` (SS58 or hex), oldest first.
+	///
+	/// Private credentials are only included when `include_private` is true.
+	#[method(name = "credentialsIndexer_credentialsByOwner")]
+	fn credentials_by_owner(
+		&self,
+		owner: String,
+		include_private: Option<bool>,
+	) -> RpcResult<Vec<CredentialRow>>;
+
+	/// A single credential by its `0x`-prefixed ID.
+	#[method(name = "credentialsIndexer_credential")]
+	fn credential(&self, credential_id: String) -> RpcResult<Option<CredentialRow>>;
+
+	/// Registered issuers.
+	#[method(name = "credentialsIndexer_issuers")]
+	fn issuers(&self) -> RpcResult<Vec<IssuerRow>>;
+
+	/// Most recent credential events, newest first, optionally restricted to a credential
+	/// and/or an account. At most `limit` entries (default 100, capped at 1000).
+	#[method(name = "credentialsIndexer_history")]
+	fn history(
+		&self,
+		credential_id: Option<String>,
+		account: Option<String>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<HistoryRow>>;
+}
+
+/// Implementation of [`CredentialsIndexerApiServer`].
+pub struct CredentialsIndexer {
+	store: Arc<Store>,
+}
+
+impl CredentialsIndexer {
+	/// Create a new instance reading from `store`.
+	pub fn new(store: Arc<Store>) -> Self {
+		Self { store }
+	}
+}
+
+impl CredentialsIndexerApiServer for CredentialsIndexer {
+	fn status(&self) -> RpcResult<IndexerStatus> {
+		self.store.status().map_err(database_error)
+	}
+
+	fn credentials_by_owner(
+		&self,
+		owner: String,
+		include_private: Option<bool>,
+	) -> RpcResult<Vec<CredentialRow>> {
+		let owner = parse_account(&owner)?;
+		self.store
+			.credentials_by_owner(&owner, include_private.unwrap_or(false))
+			.map_err(database_error)
+	}
+
+	fn credential(&self, credential_id: String) -> RpcResult<Option<CredentialRow>> {
+		self.store.credential(&credential_id.to_ascii_lowercase()).map_err(database_error)
+	}
+
+	fn issuers(&self) -> RpcResult<Vec<IssuerRow>> {
+		self.store.issuers().map_err(database_error)
+	}
+
+	fn history(
+		&self,
+		credential_id: Option<String>,
+		account: Option<String>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<HistoryRow>> {
+		let credential_id = credential_id.map(|id| id.to_ascii_lowercase());
+		let account = account.as_deref().map(parse_account).transpose()?;
+		let limit = limit.unwrap_or(DEFAULT_HISTORY_LIMIT).min(MAX_HISTORY_LIMIT);
+		self.store
+			.history(credential_id.as_deref(), account.as_deref(), limit)
+			.map_err(database_error)
+	}
+}
+
+/// Normalize an SS58 or hex account to the SS58 form stored by the indexer.
+fn parse_account(account: &str) -> RpcResult<String> {
+	account
+		.parse::<AccountId>()
+		.map(|account| account.to_string())
+		.map_err(|_| ErrorObject::owned(INVALID_PARAMS, "invalid account", Some(account)))
+}
+
+fn database_error(error: impl std::fmt::Display) -> ErrorObjectOwned {
+	ErrorObject::owned(DATABASE_ERROR, error.to_string(), None::<()>)
+}
@@ -15,19 +15,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::cli::Consensus;
+use crate::{
+	cli::{Consensus, DevParams, IndexerParams},
+	dev_clock::DevClock,
+	indexer::{store::Store, Indexer},
+};
+use codec::Decode;
 use futures::FutureExt;
 use minimal_template_runtime::{interface::OpaqueBlock as Block, RuntimeApi};
 use polkadot_sdk::{
-	sc_client_api::backend::Backend,
+	sc_client_api::{backend::Backend, BlockBackend, StorageProvider},
+	sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams},
+	sc_consensus_grandpa::SharedVoterState,
+	sc_consensus_manual_seal::consensus::aura::AuraConsensusDataProvider,
 	sc_executor::WasmExecutor,
-	sc_service::{error::Error as ServiceError, Configuration, TaskManager},
+	sc_service::{error::Error as ServiceError, Configuration, TaskManager, WarpSyncConfig},
 	sc_telemetry::{Telemetry, TelemetryWorker},
 	sc_transaction_pool_api::OffchainTransactionPoolFactory,
+	sp_consensus_aura::{
+		inherents::InherentDataProvider as SlotInherentDataProvider,
+		sr25519::AuthorityPair as AuraPair, SlotDuration,
+	},
+	sp_core::storage::StorageKey,
 	sp_runtime::traits::Block as BlockT,
 	*,
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 type HostFunctions = sp_io::SubstrateHostFunctions;
 
@@ -35,9 +48,18 @@ type HostFunctions = sp_io::SubstrateHostFunctions;
 pub(crate) type FullClient =
 	sc_service::TFullClient<Block, RuntimeApi, WasmExecutor<HostFunctions>>;
 
-type FullBackend = sc_service::TFullBackend<Block>;
+pub(crate) type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
 
+/// The minimum period of blocks on which justifications will be imported and generated.
+const GRANDPA_JUSTIFICATION_PERIOD: u32 = 512;
+
+/// GRANDPA block import and the link to its voter, used by the `aura` consensus.
+type GrandpaParts = (
+	sc_consensus_grandpa::GrandpaBlockImport<FullBackend, Block, FullClient, FullSelectChain>,
+	sc_consensus_grandpa::LinkHalf<Block, FullClient, FullSelectChain>,
+);
+
 /// Assembly of PartialComponents (enough to run chain ops subcommands)
 pub type Service = sc_service::PartialComponents<
 	FullClient,
@@ -45,10 +67,10 @@ pub type Service = sc_service::PartialComponents<
 	FullSelectChain,
 	sc_consensus::DefaultImportQueue<Block>,
 	sc_transaction_pool::TransactionPoolHandle<Block, FullClient>,
-	Option<Telemetry>,
+	(Option<Telemetry>, Option<GrandpaParts>),
 >;
 
-pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
+pub fn new_partial(config: &Configuration, consensus: &Consensus) -> Result<Service, ServiceError> {
 	let telemetry = config
 		.telemetry_endpoints
 		.clone()
@@ -88,11 +110,48 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 		.build(),
 	);
 
-	let import_queue = sc_consensus_manual_seal::import_queue(
-		Box::new(client.clone()),
-		&task_manager.spawn_essential_handle(),
-		config.prometheus_registry(),
-	);
+	let (import_queue, grandpa) = match consensus {
+		Consensus::Aura => {
+			let (grandpa_block_import, grandpa_link) = sc_consensus_grandpa::block_import(
+				client.clone(),
+				GRANDPA_JUSTIFICATION_PERIOD,
+				&client,
+				select_chain.clone(),
+				telemetry.as_ref().map(|x| x.handle()),
+			)?;
+
+			let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+			let import_queue =
+				sc_consensus_aura::import_queue::<AuraPair, _, _, _, _, _>(ImportQueueParams {
+					block_import: grandpa_block_import.clone(),
+					justification_import: Some(Box::new(grandpa_block_import.clone())),
+					client: client.clone(),
+					create_inherent_data_providers: move |_, ()| async move {
+						let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+						let slot = SlotInherentDataProvider::from_timestamp_and_slot_duration(
+							*timestamp,
+							slot_duration,
+						);
+						Ok((slot, timestamp))
+					},
+					spawner: &task_manager.spawn_essential_handle(),
+					registry: config.prometheus_registry(),
+					check_for_equivocation: Default::default(),
+					telemetry: telemetry.as_ref().map(|x| x.handle()),
+					compatibility_mode: Default::default(),
+				})?;
+
+			(import_queue, Some((grandpa_block_import, grandpa_link)))
+		},
+		_ => {
+			let import_queue = sc_consensus_manual_seal::import_queue(
+				Box::new(client.clone()),
+				&task_manager.spawn_essential_handle(),
+				config.prometheus_registry(),
+			);
+			(import_queue, None)
+		},
+	};
 
 	Ok(sc_service::PartialComponents {
 		client,
@@ -102,15 +161,46 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 		keystore_container,
 		select_chain,
 		transaction_pool,
-		other: (telemetry),
+		other: (telemetry, grandpa),
 	})
 }
 
+/// Inherent timestamp of a block sealed on top of `parent` by manual or instant seal.
+///
+/// Blocks are timestamped with the time of `clock`. Aura requires every block to be in a
+/// later slot than its parent, so a block sealed less than a slot after its parent is
+/// timestamped one slot after the parent instead.
+fn seal_timestamp(
+	client: &FullClient,
+	clock: &DevClock,
+	parent: <Block as BlockT>::Hash,
+	slot_duration: SlotDuration,
+) -> sp_timestamp::InherentDataProvider {
+	let key = StorageKey(frame_support::storage::storage_prefix(b"Timestamp", b"Now").to_vec());
+	let parent_timestamp = client
+		.storage(parent, &key)
+		.ok()
+		.flatten()
+		.and_then(|data| u64::decode(&mut &data.0[..]).ok())
+		.unwrap_or_default();
+	let earliest = sp_timestamp::Timestamp::new(parent_timestamp + slot_duration.as_millis());
+	sp_timestamp::InherentDataProvider::new(clock.now().max(earliest))
+}
+
 /// Builds a new service for a full client.
 pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	config: Configuration,
 	consensus: Consensus,
+	indexer: IndexerParams,
+	dev: DevParams,
 ) -> Result<TaskManager, ServiceError> {
+	if dev.timestamp_offset != 0 && matches!(consensus, Consensus::Aura | Consensus::None) {
+		return Err(ServiceError::Other(
+			"`--timestamp-offset` only applies to manual and instant seal".into(),
+		));
+	}
+	let clock = DevClock::new(dev.timestamp_offset);
+
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -119,10 +209,10 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		keystore_container,
 		select_chain,
 		transaction_pool,
-		other: mut telemetry,
-	} = new_partial(&config)?;
+		other: (mut telemetry, grandpa),
+	} = new_partial(&config, &consensus)?;
 
-	let net_config = sc_network::config::FullNetworkConfiguration::<
+	let mut net_config = sc_network::config::FullNetworkConfiguration::<
 		Block,
 		<Block as BlockT>::Hash,
 		Network,
@@ -134,6 +224,34 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		config.prometheus_config.as_ref().map(|cfg| &cfg.registry),
 	);
 
+	// GRANDPA gossips votes over its own notification protocol and serves warp sync proofs
+	let (grandpa, warp_sync_config) = match grandpa {
+		Some((block_import, link)) => {
+			let protocol_name = sc_consensus_grandpa::protocol_standard_name(
+				&client.block_hash(0).ok().flatten().expect("Genesis block exists; qed"),
+				&config.chain_spec,
+			);
+			let (protocol_config, notification_service) =
+				sc_consensus_grandpa::grandpa_peers_set_config::<_, Network>(
+					protocol_name.clone(),
+					metrics.clone(),
+					net_config.peer_store_handle(),
+				);
+			net_config.add_notification_protocol(protocol_config);
+
+			let warp_sync = Arc::new(sc_consensus_grandpa::warp_proof::NetworkProvider::new(
+				backend.clone(),
+				link.shared_authority_set().clone(),
+				Vec::default(),
+			));
+			(
+				Some((block_import, link, protocol_name, notification_service)),
+				Some(WarpSyncConfig::WithProvider(warp_sync)),
+			)
+		},
+		None => (None, None),
+	};
+
 	let (network, system_rpc_tx, tx_handler_controller, sync_service) =
 		sc_service::build_network(sc_service::BuildNetworkParams {
 			config: &config,
@@ -143,7 +261,7 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 			spawn_handle: task_manager.spawn_handle(),
 			import_queue,
 			block_announce_validator_builder: None,
-			warp_sync_config: None,
+			warp_sync_config,
 			block_relay: None,
 			metrics,
 		})?;
@@ -169,20 +287,70 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		);
 	}
 
+	let indexer_store = match &indexer.credentials_indexer {
+		Some(path) => {
+			let store = Store::open(path).map_err(|e| {
+				ServiceError::Other(format!("Failed to open credentials indexer database: {e}"))
+			})?;
+			let store = Arc::new(store);
+			let worker = Indexer::new(
+				client.clone(),
+				store.clone(),
+				indexer.credentials_indexer_rebuild,
+			)
+			.map_err(|e| ServiceError::Other(format!("Failed to reset credentials indexer: {e}")))?;
+			task_manager.spawn_handle().spawn_blocking(
+				"credentials-indexer",
+				None,
+				worker.run(),
+			);
+			Some(store)
+		},
+		None => None,
+	};
+
+	// Manual seal reads block production commands from a channel, fed by a timer or by the
+	// `engine_*` RPC methods
+	let manual_seal_commands = match consensus {
+		Consensus::ManualSeal(_) | Consensus::ManualSealRpc =>
+			Some(futures::channel::mpsc::channel(1024)),
+		_ => None,
+	};
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
+		let backend = backend.clone();
+		let command_sink = manual_seal_commands.as_ref().map(|(sink, _)| sink.clone());
+		let engine_rpc = matches!(consensus, Consensus::ManualSealRpc);
+		let clock = match consensus {
+			Consensus::ManualSeal(_) | Consensus::ManualSealRpc | Consensus::InstantSeal =>
+				Some(clock.clone()),
+			Consensus::Aura | Consensus::None => None,
+		};
 
 		Box::new(move |_| {
-			let deps = crate::rpc::FullDeps { client: client.clone(), pool: pool.clone() };
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				backend: backend.clone(),
+				indexer: indexer_store.clone(),
+				command_sink: command_sink.clone(),
+				engine_rpc,
+				clock: clock.clone(),
+			};
 			crate::rpc::create_full(deps).map_err(Into::into)
 		})
 	};
 
 	let prometheus_registry = config.prometheus_registry().cloned();
+	let role = config.role;
+	let force_authoring = config.force_authoring;
+	let name = config.network.node_name.clone();
+	let enable_grandpa = !config.disable_grandpa;
 
 	let _rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
-		network,
+		network: network.clone(),
 		client: client.clone(),
 		keystore: keystore_container.keystore(),
 		task_manager: &mut task_manager,
@@ -191,7 +359,7 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		backend,
 		system_rpc_tx,
 		tx_handler_controller,
-		sync_service,
+		sync_service: sync_service.clone(),
 		config,
 		telemetry: telemetry.as_mut(),
 	})?;
@@ -206,15 +374,20 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 
 	match consensus {
 		Consensus::InstantSeal => {
+			let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+			let timestamp_client = client.clone();
+			let clock = clock.clone();
 			let params = sc_consensus_manual_seal::InstantSealParams {
 				block_import: client.clone(),
 				env: proposer,
-				client,
+				client: client.clone(),
 				pool: transaction_pool,
 				select_chain,
-				consensus_data_provider: None,
-				create_inherent_data_providers: move |_, ()| async move {
-					Ok(sp_timestamp::InherentDataProvider::from_system_time())
+				consensus_data_provider: Some(Box::new(AuraConsensusDataProvider::new(client))),
+				create_inherent_data_providers: move |parent, ()| {
+					let timestamp =
+						seal_timestamp(&timestamp_client, &clock, parent, slot_duration);
+					async move { Ok(timestamp) }
 				},
 			};
 
@@ -226,31 +399,40 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 				authorship_future,
 			);
 		},
-		Consensus::ManualSeal(block_time) => {
-			let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
-			task_manager.spawn_handle().spawn("block_authoring", None, async move {
-				loop {
-					futures_timer::Delay::new(std::time::Duration::from_millis(block_time)).await;
-					sink.try_send(sc_consensus_manual_seal::EngineCommand::SealNewBlock {
-						create_empty: true,
-						finalize: true,
-						parent_hash: None,
-						sender: None,
-					})
-					.unwrap();
-				}
-			});
+		Consensus::ManualSeal(_) | Consensus::ManualSealRpc => {
+			let (mut sink, commands_stream) =
+				manual_seal_commands.expect("manual seal modes create the command channel; qed");
+			// Without a timer, blocks are only sealed through the `engine_*` RPC methods
+			if let Consensus::ManualSeal(block_time) = consensus {
+				task_manager.spawn_handle().spawn("block_authoring", None, async move {
+					loop {
+						futures_timer::Delay::new(Duration::from_millis(block_time)).await;
+						sink.try_send(sc_consensus_manual_seal::EngineCommand::SealNewBlock {
+							create_empty: true,
+							finalize: true,
+							parent_hash: None,
+							sender: None,
+						})
+						.unwrap();
+					}
+				});
+			}
 
+			let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+			let timestamp_client = client.clone();
+			let clock = clock.clone();
 			let params = sc_consensus_manual_seal::ManualSealParams {
 				block_import: client.clone(),
 				env: proposer,
-				client,
+				client: client.clone(),
 				pool: transaction_pool,
 				select_chain,
 				commands_stream: Box::pin(commands_stream),
-				consensus_data_provider: None,
-				create_inherent_data_providers: move |_, ()| async move {
-					Ok(sp_timestamp::InherentDataProvider::from_system_time())
+				consensus_data_provider: Some(Box::new(AuraConsensusDataProvider::new(client))),
+				create_inherent_data_providers: move |parent, ()| {
+					let timestamp =
+						seal_timestamp(&timestamp_client, &clock, parent, slot_duration);
+					async move { Ok(timestamp) }
 				},
 			};
 			let authorship_future = sc_consensus_manual_seal::run_manual_seal(params);
@@ -261,7 +443,79 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 				authorship_future,
 			);
 		},
-		_ => {},
+		Consensus::Aura => {
+			let (block_import, link, protocol_name, notification_service) =
+				grandpa.expect("GRANDPA is set up by `new_partial` in aura mode; qed");
+
+			if role.is_authority() {
+				let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+				let aura = sc_consensus_aura::start_aura::<AuraPair, _, _, _, _, _, _, _, _, _, _>(
+					StartAuraParams {
+						slot_duration,
+						client: client.clone(),
+						select_chain,
+						block_import,
+						proposer_factory: proposer,
+						create_inherent_data_providers: move |_, ()| async move {
+							let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+							let slot = SlotInherentDataProvider::from_timestamp_and_slot_duration(
+								*timestamp,
+								slot_duration,
+							);
+							Ok((slot, timestamp))
+						},
+						force_authoring,
+						backoff_authoring_blocks: Option::<()>::None,
+						keystore: keystore_container.keystore(),
+						sync_oracle: sync_service.clone(),
+						justification_sync_link: sync_service.clone(),
+						block_proposal_slot_portion: SlotProportion::new(2f32 / 3f32),
+						max_block_proposal_slot_portion: None,
+						telemetry: telemetry.as_ref().map(|x| x.handle()),
+						compatibility_mode: Default::default(),
+					},
+				)?;
+
+				task_manager.spawn_essential_handle().spawn_blocking(
+					"aura",
+					Some("block-authoring"),
+					aura,
+				);
+			}
+
+			if enable_grandpa {
+				// Non-authorities follow finality without voting
+				let keystore = role.is_authority().then(|| keystore_container.keystore());
+				let grandpa_params = sc_consensus_grandpa::GrandpaParams {
+					config: sc_consensus_grandpa::Config {
+						gossip_duration: Duration::from_millis(333),
+						justification_generation_period: GRANDPA_JUSTIFICATION_PERIOD,
+						name: Some(name),
+						observer_enabled: false,
+						keystore,
+						local_role: role,
+						telemetry: telemetry.as_ref().map(|x| x.handle()),
+						protocol_name,
+					},
+					link,
+					network,
+					sync: Arc::new(sync_service),
+					notification_service,
+					voting_rule: sc_consensus_grandpa::VotingRulesBuilder::default().build(),
+					prometheus_registry,
+					shared_voter_state: SharedVoterState::empty(),
+					telemetry: telemetry.as_ref().map(|x| x.handle()),
+					offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(transaction_pool),
+				};
+
+				task_manager.spawn_essential_handle().spawn_blocking(
+					"grandpa-voter",
+					None,
+					sc_consensus_grandpa::run_grandpa_voter(grandpa_params)?,
+				);
+			}
+		},
+		Consensus::None => {},
 	}
 
 	Ok(task_manager)
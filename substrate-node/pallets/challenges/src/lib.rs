@@ -0,0 +1,1330 @@
+//! # FreelanceForge Challenges Pallet
+//!
+//! Lets anyone dispute a credential before a jury: a challenger puts down a deposit, jurors
+//! drawn from a bonded pool vote on the credential's validity within a window, and the
+//! outcome is recorded on-chain.
+//!
+//! ## Features
+//!
+//! - **Jury pool**: Accounts join the pool by reserving [`Config::JurorBond`], and leave it
+//!   once they no longer sit on any jury
+//! - **Challenges**: [`Pallet::challenge_credential`] reserves [`Config::ChallengeDeposit`];
+//!   [`Config::DrawDelay`] blocks later, [`Config::JurySize`] jurors are drawn from the pool,
+//!   leaving out the challenger and the credential's owner
+//! - **Voting**: Jurors vote the credential valid or invalid within [`Config::VotingPeriod`]
+//!   blocks of the draw; the challenge can be resolved by anyone once every juror voted or
+//!   the window closed
+//! - **Outcomes**:
+//!   - With at least two thirds of the votes invalid, the challenge is upheld: the credential
+//!     is marked [`CredentialStatus::Invalid`] and reported to [`Config::OnInvalidated`],
+//!     the stake behind it is slashed through [`Config::Stakes`], and the deposit is returned
+//!   - With a smaller majority of invalid votes, the credential is marked
+//!     [`CredentialStatus::Disputed`] and the deposit is returned
+//!   - Otherwise the challenge is rejected, and the deposit goes to the credential's owner
+//!   - If nobody voted, nothing is decided and the deposit is returned
+//! - **Penalties**: Jurors who did not vote forfeit [`Config::NoShowPenalty`] of their bond,
+//!   which is burned; jurors whose bond is used up are removed from the pool
+//!
+//! Juries are drawn with [`Config::Randomness`] at the start of a later block rather than in
+//! the challenge itself, so challengers cannot wait for randomness that gives them a friendly
+//! jury, nor make the challenge only when it does. If too few eligible jurors are left in the
+//! pool by then, the challenge is cancelled and the deposit returned.
+//!
+//! [`ParentHashRandomness`] serves runtimes without a randomness source. With a
+//! `DrawDelay` of two blocks or more it is unknown when the challenge is made, but the
+//! author of the block before the draw can still predict it, and influence it by withholding
+//! that block. Runtimes with a VRF-based source should use it instead.
+//!
+//! ## Storage
+//!
+//! - `JuryPool`: Accounts that can be drawn as jurors (max `MaxJurors`)
+//! - `JurorDuties`: Maps jurors to the number of unresolved challenges they sit on
+//! - `JurorPenalties`: Maps jurors of the pool to the part of their bond they forfeited
+//! - `Challenges`: Maps challenge IDs to unresolved [`Challenge`] records (credential,
+//!   owner, challenger, deposit, jurors, vote counts, draw block, end of the voting window)
+//! - `PendingDraws`: Maps blocks to the challenges whose jury is drawn in them
+//! - `Votes`: Maps (challenge ID, juror) pairs to their [`Vote`]
+//! - `ActiveChallenges`: Maps challenged credential IDs to their unresolved challenge
+//! - `Results`: Maps challenge IDs to the [`ChallengeResult`] of resolved challenges
+//! - `CredentialStatuses`: Maps credential IDs to their [`CredentialStatus`], if a challenge
+//!   against them was not rejected
+//! - `NextChallengeId`: ID given to the next challenge
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::DecodeWithMemTracking;
+use core::marker::PhantomData;
+use frame::prelude::*;
+use pallet_freelance_credentials::{
+	CredentialDisputes, CredentialRegistry, OnCredentialInvalidated,
+};
+use pallet_reputation_staking::StakeSlashing;
+use polkadot_sdk::{
+	frame_support::traits::{BalanceStatus, Currency, Randomness, ReservableCurrency},
+	polkadot_sdk_frame as frame,
+	sp_runtime::traits::{Hash as HashT, Zero},
+};
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+// Re-export all pallet parts for runtime integration
+pub use pallet::*;
+
+/// Identifier of a challenge
+pub type ChallengeId = u32;
+
+/// Balance of the currency deposits and bonds are reserved in
+pub type BalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as polkadot_sdk::frame_system::Config>::AccountId,
+>>::Balance;
+
+/// Vote of a juror on a challenged credential
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum Vote {
+	/// The credential is genuine
+	Valid,
+	/// The credential is false or misleading
+	Invalid,
+}
+
+/// Outcome of a resolved challenge
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum ChallengeOutcome {
+	/// Two thirds of the votes or more were invalid: the credential is invalid
+	Upheld,
+	/// A majority under two thirds of the votes were invalid: the credential is disputed
+	Disputed,
+	/// Invalid votes were no majority: the deposit went to the credential's owner
+	Rejected,
+	/// Nobody voted: the deposit was returned
+	NoVotes,
+}
+
+/// Mark left on a credential by a challenge that was not rejected
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum CredentialStatus {
+	/// A jury was divided over the credential; it can be challenged again
+	Disputed,
+	/// A jury found the credential invalid; it no longer counts towards trust scores
+	Invalid,
+}
+
+/// On-chain record of an unresolved challenge
+#[derive(
+	Encode,
+	Decode,
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Challenge<T: Config> {
+	/// Challenged credential
+	pub credential_id: T::Hash,
+	/// Owner of the credential when it was challenged
+	pub owner: T::AccountId,
+	/// Account that challenged the credential
+	pub challenger: T::AccountId,
+	/// Amount reserved from the challenger
+	pub deposit: BalanceOf<T>,
+	/// Jurors drawn for the challenge, empty until the draw
+	pub jurors: BoundedVec<T::AccountId, T::JurySize>,
+	/// Number of valid votes cast
+	pub valid: u32,
+	/// Number of invalid votes cast
+	pub invalid: u32,
+	/// Block in which the jury is drawn
+	pub draw_at: BlockNumberFor<T>,
+	/// Last block in which jurors can vote
+	pub ends_at: BlockNumberFor<T>,
+}
+
+/// On-chain record of a resolved challenge
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ChallengeResult<Hash, AccountId> {
+	/// Challenged credential
+	pub credential_id: Hash,
+	/// Account that challenged the credential
+	pub challenger: AccountId,
+	/// Outcome of the challenge
+	pub outcome: ChallengeOutcome,
+	/// Number of valid votes cast
+	pub valid: u32,
+	/// Number of invalid votes cast
+	pub invalid: u32,
+}
+
+/// [`ChallengeResult`] specialised for a runtime
+pub type ChallengeResultOf<T> = ChallengeResult<
+	<T as polkadot_sdk::frame_system::Config>::Hash,
+	<T as polkadot_sdk::frame_system::Config>::AccountId,
+>;
+
+/// Randomness derived from the parent block hash, for runtimes without a randomness source
+///
+/// Unknown until the parent block is built, but its author can predict it, and influence it
+/// by withholding the block.
+pub struct ParentHashRandomness<T>(PhantomData<T>);
+
+impl<T: polkadot_sdk::frame_system::Config> Randomness<T::Hash, BlockNumberFor<T>>
+	for ParentHashRandomness<T>
+{
+	fn random(subject: &[u8]) -> (T::Hash, BlockNumberFor<T>) {
+		let parent_hash = polkadot_sdk::frame_system::Pallet::<T>::parent_hash();
+		let block_number = polkadot_sdk::frame_system::Pallet::<T>::block_number();
+		(T::Hashing::hash(&(subject, parent_hash).encode()), block_number)
+	}
+}
+
+#[frame::pallet]
+pub mod pallet {
+	use super::*;
+
+	/// Configuration trait for this pallet
+	#[pallet::config]
+	pub trait Config: polkadot_sdk::frame_system::Config {
+		/// The overarching runtime event type
+		type RuntimeEvent: From<Event<Self>>
+			+ IsType<<Self as polkadot_sdk::frame_system::Config>::RuntimeEvent>;
+
+		/// Currency deposits and juror bonds are reserved in
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Credentials that can be challenged
+		type Credentials: CredentialRegistry<Self::AccountId, Self::Hash>;
+
+		/// Stake behind credentials, slashed when a challenge is upheld
+		type Stakes: StakeSlashing<Self::AccountId, Self::Hash>;
+
+		/// Told about credentials found invalid, e.g. so indexes can leave them out
+		type OnInvalidated: OnCredentialInvalidated<Self::Hash>;
+
+		/// Source of randomness juries are drawn with
+		type Randomness: Randomness<Self::Hash, BlockNumberFor<Self>>;
+
+		/// Amount reserved from challengers until their challenge is resolved
+		#[pallet::constant]
+		type ChallengeDeposit: Get<BalanceOf<Self>>;
+
+		/// Amount reserved from jurors while they are in the pool
+		#[pallet::constant]
+		type JurorBond: Get<BalanceOf<Self>>;
+
+		/// Part of the juror bond slashed from jurors who did not vote on a challenge they
+		/// sat on
+		#[pallet::constant]
+		type NoShowPenalty: Get<BalanceOf<Self>>;
+
+		/// Number of jurors drawn per challenge
+		#[pallet::constant]
+		type JurySize: Get<u32>;
+
+		/// Maximum number of accounts in the jury pool
+		#[pallet::constant]
+		type MaxJurors: Get<u32>;
+
+		/// Number of blocks jurors have to vote, from the draw
+		#[pallet::constant]
+		type VotingPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Number of blocks between a challenge and the draw of its jury, so the randomness
+		/// the jury is drawn with is unknown when the challenge is made (at least two for
+		/// [`ParentHashRandomness`])
+		#[pallet::constant]
+		type DrawDelay: Get<BlockNumberFor<Self>>;
+
+		/// Maximum number of juries drawn in a block
+		#[pallet::constant]
+		type MaxDrawsPerBlock: Get<u32>;
+	}
+
+	/// The pallet struct
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Accounts that can be drawn as jurors
+	#[pallet::storage]
+	#[pallet::getter(fn jury_pool)]
+	pub type JuryPool<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxJurors>, ValueQuery>;
+
+	/// Storage map of the duties of jurors
+	/// Maps juror -> number of unresolved challenges they sit on
+	#[pallet::storage]
+	#[pallet::getter(fn juror_duties)]
+	pub type JurorDuties<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Storage map of the penalties of jurors
+	/// Maps juror -> part of their bond slashed for not voting
+	#[pallet::storage]
+	#[pallet::getter(fn juror_penalties)]
+	pub type JurorPenalties<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// Storage map of unresolved challenges
+	/// Maps challenge_id -> Challenge (credential, owner, challenger, deposit, jurors, votes,
+	/// draw_at, ends_at)
+	#[pallet::storage]
+	#[pallet::getter(fn challenges)]
+	pub type Challenges<T: Config> =
+		StorageMap<_, Twox64Concat, ChallengeId, Challenge<T>, OptionQuery>;
+
+	/// Storage map of the juries to draw
+	/// Maps block number -> IDs of the challenges whose jury is drawn in that block
+	#[pallet::storage]
+	#[pallet::getter(fn pending_draws)]
+	pub type PendingDraws<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<ChallengeId, T::MaxDrawsPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Storage double map of the votes cast on unresolved challenges
+	/// Maps (challenge_id, juror) -> Vote
+	#[pallet::storage]
+	#[pallet::getter(fn votes)]
+	pub type Votes<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ChallengeId,
+		Blake2_128Concat,
+		T::AccountId,
+		Vote,
+		OptionQuery,
+	>;
+
+	/// Storage map of challenged credentials
+	/// Maps credential_id -> ID of the unresolved challenge against it
+	#[pallet::storage]
+	#[pallet::getter(fn active_challenges)]
+	pub type ActiveChallenges<T: Config> =
+		StorageMap<_, Identity, T::Hash, ChallengeId, OptionQuery>;
+
+	/// Storage map of resolved challenges
+	/// Maps challenge_id -> ChallengeResult (credential, challenger, outcome, votes)
+	#[pallet::storage]
+	#[pallet::getter(fn results)]
+	pub type Results<T: Config> =
+		StorageMap<_, Twox64Concat, ChallengeId, ChallengeResultOf<T>, OptionQuery>;
+
+	/// Storage map of the marks left by challenges
+	/// Maps credential_id -> CredentialStatus (Disputed or Invalid)
+	#[pallet::storage]
+	#[pallet::getter(fn credential_statuses)]
+	pub type CredentialStatuses<T: Config> =
+		StorageMap<_, Identity, T::Hash, CredentialStatus, OptionQuery>;
+
+	/// ID given to the next challenge
+	#[pallet::storage]
+	#[pallet::getter(fn next_challenge_id)]
+	pub type NextChallengeId<T: Config> = StorageValue<_, ChallengeId, ValueQuery>;
+
+	/// Events emitted by the pallet
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An account joined the jury pool, reserving the juror bond
+		/// [juror]
+		JurorJoined { juror: T::AccountId },
+
+		/// An account left the jury pool, and its bond was returned
+		/// [juror]
+		JurorLeft { juror: T::AccountId },
+
+		/// A juror did not vote on a challenge, and `penalty` of its bond was slashed
+		/// [juror, challenge_id, penalty]
+		JurorPenalized { juror: T::AccountId, challenge_id: ChallengeId, penalty: BalanceOf<T> },
+
+		/// Penalties used up the bond of a juror, so it was removed from the jury pool
+		/// [juror]
+		JurorRemoved { juror: T::AccountId },
+
+		/// A credential was challenged; its jury is drawn in block `draw_at`
+		/// [challenge_id, credential_id, challenger, draw_at, ends_at]
+		CredentialChallenged {
+			challenge_id: ChallengeId,
+			credential_id: T::Hash,
+			challenger: T::AccountId,
+			draw_at: BlockNumberFor<T>,
+			ends_at: BlockNumberFor<T>,
+		},
+
+		/// The jury of a challenge was drawn
+		/// [challenge_id, jurors]
+		JuryDrawn { challenge_id: ChallengeId, jurors: BoundedVec<T::AccountId, T::JurySize> },
+
+		/// Too few eligible jurors were left to draw the jury of a challenge, so it was
+		/// cancelled and the deposit returned
+		/// [challenge_id, credential_id]
+		ChallengeCancelled { challenge_id: ChallengeId, credential_id: T::Hash },
+
+		/// A juror voted on a challenge
+		/// [challenge_id, juror, vote]
+		Voted { challenge_id: ChallengeId, juror: T::AccountId, vote: Vote },
+
+		/// A challenge was resolved
+		/// [challenge_id, credential_id, outcome, valid, invalid]
+		ChallengeResolved {
+			challenge_id: ChallengeId,
+			credential_id: T::Hash,
+			outcome: ChallengeOutcome,
+			valid: u32,
+			invalid: u32,
+		},
+	}
+
+	/// Errors that can occur when calling pallet extrinsics
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The caller is already in the jury pool
+		AlreadyJuror,
+		/// The caller is not in the jury pool
+		NotJuror,
+		/// The jury pool is full
+		TooManyJurors,
+		/// The caller sits on the jury of an unresolved challenge
+		JurorOnDuty,
+		/// The caller cannot reserve the bond or deposit
+		InsufficientBalance,
+		/// The credential does not exist
+		CredentialNotFound,
+		/// Owners cannot challenge their own credentials
+		OwnCredential,
+		/// The credential already has an unresolved challenge
+		AlreadyChallenged,
+		/// The credential was already found invalid
+		AlreadyInvalid,
+		/// The pool has fewer eligible jurors than `JurySize`
+		NotEnoughJurors,
+		/// All challenge IDs are taken
+		NoChallengeIdAvailable,
+		/// `MaxDrawsPerBlock` juries are already drawn in the block the draw would fall in
+		TooManyDraws,
+		/// The challenge does not exist or was resolved
+		ChallengeNotFound,
+		/// The caller does not sit on the challenge's jury
+		NotOnJury,
+		/// The caller already voted
+		AlreadyVoted,
+		/// The voting window has closed
+		VotingClosed,
+		/// Jurors can still vote
+		VotingOpen,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let challenge_ids = PendingDraws::<T>::take(now);
+			for challenge_id in challenge_ids.iter() {
+				Self::draw(*challenge_id);
+			}
+			T::DbWeight::get()
+				.reads_writes(1, 1)
+				.saturating_add(Self::draw_weight().saturating_mul(challenge_ids.len() as u64))
+		}
+
+		fn integrity_test() {
+			assert!(
+				!T::DrawDelay::get().is_zero(),
+				"juries must be drawn after the challenge, with randomness unknown to challengers"
+			);
+		}
+	}
+
+	/// Dispatchable extrinsics (functions) that can be called by users
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Join the jury pool, reserving the juror bond
+		///
+		/// Emits:
+		/// - `JurorJoined` event with the juror
+		///
+		/// Errors:
+		/// - `AlreadyJuror`: If the caller is already in the pool
+		/// - `TooManyJurors`: If the pool has `MaxJurors` accounts
+		/// - `InsufficientBalance`: If the caller cannot reserve the bond
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2) + Weight::from_parts(50_000, 0))]
+		pub fn join_jury_pool(origin: OriginFor<T>) -> DispatchResult {
+			let juror = ensure_signed(origin)?;
+			let mut pool = JuryPool::<T>::get();
+
+			ensure!(!pool.contains(&juror), Error::<T>::AlreadyJuror);
+			pool.try_push(juror.clone()).map_err(|_| Error::<T>::TooManyJurors)?;
+			T::Currency::reserve(&juror, T::JurorBond::get())
+				.map_err(|_| Error::<T>::InsufficientBalance)?;
+			JuryPool::<T>::put(pool);
+
+			Self::deposit_event(Event::JurorJoined { juror });
+
+			Ok(())
+		}
+
+		/// Leave the jury pool, returning what is left of the juror bond
+		///
+		/// Emits:
+		/// - `JurorLeft` event with the juror
+		///
+		/// Errors:
+		/// - `NotJuror`: If the caller is not in the pool
+		/// - `JurorOnDuty`: If the caller sits on the jury of an unresolved challenge
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(4, 3) + Weight::from_parts(50_000, 0))]
+		pub fn leave_jury_pool(origin: OriginFor<T>) -> DispatchResult {
+			let juror = ensure_signed(origin)?;
+			let mut pool = JuryPool::<T>::get();
+
+			let index =
+				pool.iter().position(|member| *member == juror).ok_or(Error::<T>::NotJuror)?;
+			ensure!(JurorDuties::<T>::get(&juror) == 0, Error::<T>::JurorOnDuty);
+			pool.swap_remove(index);
+			JuryPool::<T>::put(pool);
+			let penalties = JurorPenalties::<T>::take(&juror);
+			T::Currency::unreserve(&juror, T::JurorBond::get().saturating_sub(penalties));
+
+			Self::deposit_event(Event::JurorLeft { juror });
+
+			Ok(())
+		}
+
+		/// Challenge a credential, reserving the challenge deposit
+		///
+		/// The jury is drawn `DrawDelay` blocks later, and jurors can vote for
+		/// `VotingPeriod` blocks from then.
+		///
+		/// Parameters:
+		/// - `credential_id`: Credential to challenge
+		///
+		/// Emits:
+		/// - `CredentialChallenged` event with the challenge ID, credential ID, challenger,
+		///   draw block and end of the voting window
+		///
+		/// Errors:
+		/// - `CredentialNotFound`: If the credential does not exist
+		/// - `OwnCredential`: If the caller owns the credential
+		/// - `AlreadyChallenged`: If the credential has an unresolved challenge
+		/// - `AlreadyInvalid`: If the credential was already found invalid
+		/// - `NotEnoughJurors`: If the pool has fewer than `JurySize` eligible jurors
+		/// - `InsufficientBalance`: If the caller cannot reserve the deposit
+		/// - `NoChallengeIdAvailable`: If all challenge IDs are taken
+		/// - `TooManyDraws`: If `MaxDrawsPerBlock` juries are already drawn in the draw block
+		#[pallet::call_index(2)]
+		#[pallet::weight(
+			T::DbWeight::get().reads_writes(7, 5)
+				+ Weight::from_parts(100_000 + 1_000 * T::MaxJurors::get() as u64, 0)
+		)]
+		pub fn challenge_credential(
+			origin: OriginFor<T>,
+			credential_id: T::Hash,
+		) -> DispatchResult {
+			let challenger = ensure_signed(origin)?;
+			let owner =
+				T::Credentials::owner(&credential_id).ok_or(Error::<T>::CredentialNotFound)?;
+
+			ensure!(owner != challenger, Error::<T>::OwnCredential);
+			ensure!(
+				!ActiveChallenges::<T>::contains_key(credential_id),
+				Error::<T>::AlreadyChallenged
+			);
+			ensure!(
+				Self::credential_statuses(credential_id) != Some(CredentialStatus::Invalid),
+				Error::<T>::AlreadyInvalid
+			);
+
+			// Jurors may still leave before the draw, which then cancels the challenge
+			ensure!(
+				Self::eligible_jurors(&owner, &challenger).len() >= T::JurySize::get() as usize,
+				Error::<T>::NotEnoughJurors
+			);
+
+			let challenge_id = NextChallengeId::<T>::get();
+			let next = challenge_id.checked_add(1).ok_or(Error::<T>::NoChallengeIdAvailable)?;
+			let draw_at = polkadot_sdk::frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::DrawDelay::get());
+			PendingDraws::<T>::try_append(draw_at, challenge_id)
+				.map_err(|_| Error::<T>::TooManyDraws)?;
+			let deposit = T::ChallengeDeposit::get();
+			T::Currency::reserve(&challenger, deposit)
+				.map_err(|_| Error::<T>::InsufficientBalance)?;
+
+			let ends_at = draw_at.saturating_add(T::VotingPeriod::get());
+			Challenges::<T>::insert(
+				challenge_id,
+				Challenge {
+					credential_id,
+					owner,
+					challenger: challenger.clone(),
+					deposit,
+					jurors: BoundedVec::new(),
+					valid: 0,
+					invalid: 0,
+					draw_at,
+					ends_at,
+				},
+			);
+			ActiveChallenges::<T>::insert(credential_id, challenge_id);
+			NextChallengeId::<T>::put(next);
+
+			Self::deposit_event(Event::CredentialChallenged {
+				challenge_id,
+				credential_id,
+				challenger,
+				draw_at,
+				ends_at,
+			});
+
+			Ok(())
+		}
+
+		/// Vote on a challenge the caller sits on the jury of
+		///
+		/// Parameters:
+		/// - `challenge_id`: Challenge to vote on
+		/// - `vote`: Whether the credential is valid
+		///
+		/// Emits:
+		/// - `Voted` event with the challenge ID, juror and vote
+		///
+		/// Errors:
+		/// - `ChallengeNotFound`: If the challenge does not exist or was resolved
+		/// - `NotOnJury`: If the caller does not sit on the jury
+		/// - `AlreadyVoted`: If the caller already voted
+		/// - `VotingClosed`: If the voting window has closed
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2) + Weight::from_parts(25_000, 0))]
+		pub fn vote(origin: OriginFor<T>, challenge_id: ChallengeId, vote: Vote) -> DispatchResult {
+			let juror = ensure_signed(origin)?;
+			let mut challenge =
+				Self::challenges(challenge_id).ok_or(Error::<T>::ChallengeNotFound)?;
+
+			ensure!(challenge.jurors.contains(&juror), Error::<T>::NotOnJury);
+			ensure!(!Votes::<T>::contains_key(challenge_id, &juror), Error::<T>::AlreadyVoted);
+			ensure!(
+				polkadot_sdk::frame_system::Pallet::<T>::block_number() <= challenge.ends_at,
+				Error::<T>::VotingClosed
+			);
+
+			match vote {
+				Vote::Valid => challenge.valid += 1,
+				Vote::Invalid => challenge.invalid += 1,
+			}
+			Challenges::<T>::insert(challenge_id, challenge);
+			Votes::<T>::insert(challenge_id, &juror, vote);
+
+			Self::deposit_event(Event::Voted { challenge_id, juror, vote });
+
+			Ok(())
+		}
+
+		/// Resolve a challenge once every juror voted or the voting window closed
+		///
+		/// Anyone can resolve a challenge. Jurors who did not vote are not counted, and forfeit
+		/// `NoShowPenalty` of their bond.
+		///
+		/// Parameters:
+		/// - `challenge_id`: Challenge to resolve
+		///
+		/// Emits:
+		/// - `ChallengeResolved` event with the challenge ID, credential ID, outcome and
+		///   vote counts
+		/// - `JurorPenalized` event for every juror who did not vote
+		/// - `JurorRemoved` event for every juror whose bond is used up
+		///
+		/// Errors:
+		/// - `ChallengeNotFound`: If the challenge does not exist or was resolved
+		/// - `VotingOpen`: If jurors can still vote
+		#[pallet::call_index(4)]
+		#[pallet::weight(
+			T::DbWeight::get().reads_writes(9 + 3 * T::JurySize::get() as u64, 9 + 4 * T::JurySize::get() as u64)
+				+ Weight::from_parts(150_000 + 1_000 * T::MaxJurors::get() as u64, 0)
+				+ T::OnInvalidated::on_credential_invalidated_weight()
+		)]
+		pub fn resolve_challenge(
+			origin: OriginFor<T>,
+			challenge_id: ChallengeId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let challenge = Self::challenges(challenge_id).ok_or(Error::<T>::ChallengeNotFound)?;
+
+			let all_voted = !challenge.jurors.is_empty() &&
+				(challenge.valid + challenge.invalid) as usize == challenge.jurors.len();
+			ensure!(
+				all_voted
+					|| polkadot_sdk::frame_system::Pallet::<T>::block_number() > challenge.ends_at,
+				Error::<T>::VotingOpen
+			);
+
+			let outcome = Self::outcome(challenge.valid, challenge.invalid);
+			let credential_id = challenge.credential_id;
+			match outcome {
+				ChallengeOutcome::Upheld => {
+					CredentialStatuses::<T>::insert(credential_id, CredentialStatus::Invalid);
+					T::OnInvalidated::on_credential_invalidated(&credential_id);
+					T::Stakes::slash(&credential_id, &challenge.challenger);
+					T::Currency::unreserve(&challenge.challenger, challenge.deposit);
+				},
+				ChallengeOutcome::Disputed => {
+					CredentialStatuses::<T>::insert(credential_id, CredentialStatus::Disputed);
+					T::Currency::unreserve(&challenge.challenger, challenge.deposit);
+				},
+				ChallengeOutcome::Rejected => {
+					// Whatever cannot be paid to the owner, e.g. below its existential deposit,
+					// goes back to the challenger
+					let unpaid = T::Currency::repatriate_reserved(
+						&challenge.challenger,
+						&challenge.owner,
+						challenge.deposit,
+						BalanceStatus::Free,
+					)
+					.unwrap_or(challenge.deposit);
+					if !unpaid.is_zero() {
+						T::Currency::unreserve(&challenge.challenger, unpaid);
+					}
+				},
+				ChallengeOutcome::NoVotes => {
+					T::Currency::unreserve(&challenge.challenger, challenge.deposit);
+				},
+			}
+
+			let mut pool = JuryPool::<T>::get();
+			for juror in challenge.jurors.iter() {
+				if !Votes::<T>::contains_key(challenge_id, juror) {
+					Self::penalize(juror, challenge_id, &mut pool);
+				}
+			}
+			JuryPool::<T>::put(pool);
+
+			for juror in challenge.jurors.iter() {
+				JurorDuties::<T>::mutate_exists(juror, |duties| {
+					*duties = duties.and_then(|duties| duties.checked_sub(1)).filter(|d| *d > 0);
+				});
+			}
+			let _ = Votes::<T>::clear_prefix(challenge_id, T::JurySize::get(), None);
+			Challenges::<T>::remove(challenge_id);
+			ActiveChallenges::<T>::remove(credential_id);
+			Results::<T>::insert(
+				challenge_id,
+				ChallengeResult {
+					credential_id,
+					challenger: challenge.challenger,
+					outcome,
+					valid: challenge.valid,
+					invalid: challenge.invalid,
+				},
+			);
+
+			Self::deposit_event(Event::ChallengeResolved {
+				challenge_id,
+				credential_id,
+				outcome,
+				valid: challenge.valid,
+				invalid: challenge.invalid,
+			});
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Outcome of a challenge with `valid` and `invalid` votes cast
+	pub fn outcome(valid: u32, invalid: u32) -> ChallengeOutcome {
+		if valid == 0 && invalid == 0 {
+			ChallengeOutcome::NoVotes
+		} else if invalid <= valid {
+			ChallengeOutcome::Rejected
+		} else if invalid >= valid.saturating_mul(2) {
+			// At least two thirds of the votes cast
+			ChallengeOutcome::Upheld
+		} else {
+			ChallengeOutcome::Disputed
+		}
+	}
+
+	/// Weight of drawing the jury of one challenge in `on_initialize`
+	fn draw_weight() -> Weight {
+		T::DbWeight::get()
+			.reads_writes(3, 2 + T::JurySize::get() as u64)
+			.saturating_add(Weight::from_parts(50_000 + 10_000 * T::MaxJurors::get() as u64, 0))
+	}
+
+	/// Draw the jury of `challenge_id`, or cancel the challenge if too few eligible jurors
+	/// are left in the pool
+	fn draw(challenge_id: ChallengeId) {
+		let Some(mut challenge) = Self::challenges(challenge_id) else { return };
+
+		match Self::draw_jury(challenge_id, &challenge.owner, &challenge.challenger) {
+			Ok(jurors) => {
+				for juror in jurors.iter() {
+					JurorDuties::<T>::mutate(juror, |duties| *duties = duties.saturating_add(1));
+				}
+				challenge.jurors = jurors.clone();
+				Challenges::<T>::insert(challenge_id, challenge);
+
+				Self::deposit_event(Event::JuryDrawn { challenge_id, jurors });
+			},
+			Err(_) => {
+				T::Currency::unreserve(&challenge.challenger, challenge.deposit);
+				Challenges::<T>::remove(challenge_id);
+				ActiveChallenges::<T>::remove(challenge.credential_id);
+
+				Self::deposit_event(Event::ChallengeCancelled {
+					challenge_id,
+					credential_id: challenge.credential_id,
+				});
+			},
+		}
+	}
+
+	/// Slash `NoShowPenalty` from the bond of `juror`, who did not vote on `challenge_id`,
+	/// removing it from `pool` once its bond is used up
+	fn penalize(
+		juror: &T::AccountId,
+		challenge_id: ChallengeId,
+		pool: &mut BoundedVec<T::AccountId, T::MaxJurors>,
+	) {
+		// Jurors removed by an earlier penalty have no bond left
+		let Some(index) = pool.iter().position(|member| member == juror) else { return };
+
+		let forfeited = JurorPenalties::<T>::get(juror);
+		let bond = T::JurorBond::get().saturating_sub(forfeited);
+		let penalty = T::NoShowPenalty::get().min(bond);
+		// The slashed amount is burned
+		let _ = T::Currency::slash_reserved(juror, penalty);
+		Self::deposit_event(Event::JurorPenalized { juror: juror.clone(), challenge_id, penalty });
+
+		if penalty < bond {
+			JurorPenalties::<T>::insert(juror, forfeited.saturating_add(penalty));
+		} else {
+			pool.swap_remove(index);
+			JurorPenalties::<T>::remove(juror);
+			Self::deposit_event(Event::JurorRemoved { juror: juror.clone() });
+		}
+	}
+
+	/// Accounts of the pool that can sit on a jury between `owner` and `challenger`
+	fn eligible_jurors(owner: &T::AccountId, challenger: &T::AccountId) -> Vec<T::AccountId> {
+		JuryPool::<T>::get()
+			.into_iter()
+			.filter(|juror| juror != owner && juror != challenger)
+			.collect()
+	}
+
+	/// Draw `JurySize` jurors from the pool for `challenge_id`, leaving out the parties
+	fn draw_jury(
+		challenge_id: ChallengeId,
+		owner: &T::AccountId,
+		challenger: &T::AccountId,
+	) -> Result<BoundedVec<T::AccountId, T::JurySize>, Error<T>> {
+		let mut eligible = Self::eligible_jurors(owner, challenger);
+		let size = T::JurySize::get() as usize;
+		ensure!(eligible.len() >= size, Error::<T>::NotEnoughJurors);
+
+		// Partial Fisher-Yates shuffle: the first `size` eligible accounts are the jury
+		let (seed, _) = T::Randomness::random(&(b"ff/jury", challenge_id).encode());
+		for i in 0..size {
+			let entropy = T::Hashing::hash(&(seed, i as u32).encode());
+			let mut bytes = [0u8; 4];
+			bytes.copy_from_slice(&entropy.as_ref()[..4]);
+			let j = i + u32::from_le_bytes(bytes) as usize % (eligible.len() - i);
+			eligible.swap(i, j);
+		}
+		eligible.truncate(size);
+
+		eligible.try_into().map_err(|_| Error::<T>::NotEnoughJurors)
+	}
+}
+
+impl<T: Config> CredentialDisputes<T::Hash> for Pallet<T> {
+	fn is_invalid(credential_id: &T::Hash) -> bool {
+		Self::credential_statuses(credential_id) == Some(CredentialStatus::Invalid)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame::testing_prelude::*;
+	use polkadot_sdk::pallet_balances;
+
+	// Configure a mock runtime to test the pallet
+	construct_runtime!(
+		pub enum Test {
+			System: frame_system,
+			Balances: pallet_balances,
+			Challenges: crate,
+		}
+	);
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = MockBlock<Test>;
+		type AccountId = u64;
+		type AccountData = pallet_balances::AccountData<u64>;
+	}
+
+	#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+	impl pallet_balances::Config for Test {
+		type AccountStore = System;
+	}
+
+	type Hash = <Test as frame_system::Config>::Hash;
+
+	parameter_types! {
+		// Stakes slashed, with the challenger rewarded
+		pub static Slashed: Vec<(Hash, u64)> = vec![];
+		// Credentials reported invalid
+		pub static Invalidated: Vec<Hash> = vec![];
+	}
+
+	impl Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type Currency = Balances;
+		type Credentials = MockCredentials;
+		type Stakes = MockStakes;
+		type OnInvalidated = MockInvalidations;
+		type Randomness = ParentHashRandomness<Test>;
+		type ChallengeDeposit = ConstU64<50>;
+		type JurorBond = ConstU64<20>;
+		type NoShowPenalty = ConstU64<8>;
+		type JurySize = ConstU32<5>;
+		type MaxJurors = ConstU32<8>;
+		type VotingPeriod = ConstU64<10>;
+		type DrawDelay = ConstU64<2>;
+		type MaxDrawsPerBlock = ConstU32<2>;
+	}
+
+	const OWNER: u64 = 1;
+	const CHALLENGER: u64 = 2;
+
+	// Every credential of the mock runtime but `credential(0)` is owned by `OWNER`
+	pub struct MockCredentials;
+
+	impl CredentialRegistry<u64, Hash> for MockCredentials {
+		fn owner(credential_id: &Hash) -> Option<u64> {
+			(*credential_id != credential(0)).then_some(OWNER)
+		}
+	}
+
+	pub struct MockStakes;
+
+	impl StakeSlashing<u64, Hash> for MockStakes {
+		fn slash(credential_id: &Hash, challenger: &u64) {
+			Slashed::mutate(|slashed| slashed.push((*credential_id, *challenger)));
+		}
+	}
+
+	pub struct MockInvalidations;
+
+	impl OnCredentialInvalidated<Hash> for MockInvalidations {
+		fn on_credential_invalidated(credential_id: &Hash) {
+			Invalidated::mutate(|invalidated| invalidated.push(*credential_id));
+		}
+
+		fn on_credential_invalidated_weight() -> Weight {
+			Weight::zero()
+		}
+	}
+
+	// ID of the `n`th credential
+	fn credential(n: u8) -> Hash {
+		BlakeTwo256::hash(&[n])
+	}
+
+	// Build genesis storage according to the mock runtime
+	pub fn new_test_ext() -> TestExternalities {
+		let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Test> {
+			balances: (1..=20).map(|account| (account, 100)).collect(),
+			..Default::default()
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+		let mut ext: TestExternalities = storage.into();
+		// Events are only recorded after the genesis block
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	// Put accounts 10 to 15 in the jury pool
+	fn fill_pool() {
+		for juror in 10..=15 {
+			assert_ok!(Challenges::join_jury_pool(RuntimeOrigin::signed(juror)));
+		}
+	}
+
+	// Move on to block `n`, drawing the juries due in it
+	fn run_to(n: u64) {
+		System::set_block_number(n);
+		Challenges::on_initialize(n);
+	}
+
+	// Challenge `credential(n)`, draw the jury and have the jurors cast `votes`, in order
+	fn challenge_with_votes(n: u8, votes: &[Vote]) -> ChallengeId {
+		let challenge_id = Challenges::next_challenge_id();
+		assert_ok!(Challenges::challenge_credential(
+			RuntimeOrigin::signed(CHALLENGER),
+			credential(n)
+		));
+		run_to(Challenges::challenges(challenge_id).unwrap().draw_at);
+		let jurors = Challenges::challenges(challenge_id).unwrap().jurors;
+		for (juror, vote) in jurors.iter().zip(votes) {
+			assert_ok!(Challenges::vote(RuntimeOrigin::signed(*juror), challenge_id, *vote));
+		}
+		challenge_id
+	}
+
+	#[test]
+	fn test_jury_pool() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Challenges::join_jury_pool(RuntimeOrigin::signed(10)));
+			System::assert_last_event(Event::JurorJoined { juror: 10 }.into());
+			assert_eq!(Balances::reserved_balance(10), 20);
+			assert_noop!(
+				Challenges::join_jury_pool(RuntimeOrigin::signed(10)),
+				Error::<Test>::AlreadyJuror
+			);
+
+			assert_ok!(Challenges::leave_jury_pool(RuntimeOrigin::signed(10)));
+			System::assert_last_event(Event::JurorLeft { juror: 10 }.into());
+			assert_eq!(Balances::reserved_balance(10), 0);
+			assert_noop!(
+				Challenges::leave_jury_pool(RuntimeOrigin::signed(10)),
+				Error::<Test>::NotJuror
+			);
+
+			for juror in 10..18 {
+				assert_ok!(Challenges::join_jury_pool(RuntimeOrigin::signed(juror)));
+			}
+			assert_noop!(
+				Challenges::join_jury_pool(RuntimeOrigin::signed(18)),
+				Error::<Test>::TooManyJurors
+			);
+		});
+	}
+
+	#[test]
+	fn test_challenge_credential() {
+		new_test_ext().execute_with(|| {
+			// Neither the owner nor the challenger can sit on the jury
+			for juror in [OWNER, CHALLENGER, 10, 11, 12, 13] {
+				assert_ok!(Challenges::join_jury_pool(RuntimeOrigin::signed(juror)));
+			}
+			assert_noop!(
+				Challenges::challenge_credential(RuntimeOrigin::signed(CHALLENGER), credential(1)),
+				Error::<Test>::NotEnoughJurors
+			);
+			for juror in [14, 15] {
+				assert_ok!(Challenges::join_jury_pool(RuntimeOrigin::signed(juror)));
+			}
+
+			assert_noop!(
+				Challenges::challenge_credential(RuntimeOrigin::signed(CHALLENGER), credential(0)),
+				Error::<Test>::CredentialNotFound
+			);
+			assert_noop!(
+				Challenges::challenge_credential(RuntimeOrigin::signed(OWNER), credential(1)),
+				Error::<Test>::OwnCredential
+			);
+
+			assert_ok!(Challenges::challenge_credential(
+				RuntimeOrigin::signed(CHALLENGER),
+				credential(1)
+			));
+			System::assert_last_event(
+				Event::CredentialChallenged {
+					challenge_id: 0,
+					credential_id: credential(1),
+					challenger: CHALLENGER,
+					draw_at: 3,
+					ends_at: 13,
+				}
+				.into(),
+			);
+			assert_eq!(Balances::reserved_balance(CHALLENGER), 20 + 50);
+			assert_eq!(Challenges::active_challenges(credential(1)), Some(0));
+			assert_eq!(Challenges::pending_draws(3).into_inner(), vec![0]);
+			assert_noop!(
+				Challenges::challenge_credential(RuntimeOrigin::signed(3), credential(1)),
+				Error::<Test>::AlreadyChallenged
+			);
+
+			// The jury is not known until the draw
+			assert!(Challenges::challenges(0).unwrap().jurors.is_empty());
+			assert_noop!(
+				Challenges::resolve_challenge(RuntimeOrigin::signed(3), 0),
+				Error::<Test>::VotingOpen
+			);
+			run_to(2);
+			assert!(Challenges::challenges(0).unwrap().jurors.is_empty());
+			run_to(3);
+			let challenge = Challenges::challenges(0).unwrap();
+			System::assert_last_event(
+				Event::JuryDrawn { challenge_id: 0, jurors: challenge.jurors.clone() }.into(),
+			);
+			assert_eq!(Challenges::pending_draws(3).len(), 0);
+			let mut jurors = challenge.jurors.into_inner();
+			jurors.sort();
+			jurors.dedup();
+			assert_eq!(jurors.len(), 5);
+			assert!(jurors.iter().all(|juror| (10..=15).contains(juror)));
+			assert!(jurors.iter().all(|juror| Challenges::juror_duties(juror) == 1));
+			assert_noop!(
+				Challenges::leave_jury_pool(RuntimeOrigin::signed(jurors[0])),
+				Error::<Test>::JurorOnDuty
+			);
+		});
+	}
+
+	#[test]
+	fn test_draws() {
+		new_test_ext().execute_with(|| {
+			fill_pool();
+
+			// At most two draws per block
+			assert_ok!(Challenges::challenge_credential(RuntimeOrigin::signed(3), credential(1)));
+			assert_ok!(Challenges::challenge_credential(RuntimeOrigin::signed(4), credential(2)));
+			assert_noop!(
+				Challenges::challenge_credential(RuntimeOrigin::signed(5), credential(3)),
+				Error::<Test>::TooManyDraws
+			);
+
+			// Jurors leaving before the draw cancel the challenges they leave short
+			assert_ok!(Challenges::leave_jury_pool(RuntimeOrigin::signed(10)));
+			assert_ok!(Challenges::leave_jury_pool(RuntimeOrigin::signed(11)));
+			run_to(3);
+			System::assert_has_event(
+				Event::ChallengeCancelled { challenge_id: 0, credential_id: credential(1) }.into(),
+			);
+			assert_eq!(Challenges::challenges(0), None);
+			assert_eq!(Challenges::active_challenges(credential(1)), None);
+			assert_eq!(Balances::reserved_balance(3), 0);
+			assert_eq!(Balances::free_balance(3), 100);
+			assert!((12..=15).all(|juror| !JurorDuties::<Test>::contains_key(juror)));
+		});
+	}
+
+	#[test]
+	fn test_voting() {
+		new_test_ext().execute_with(|| {
+			fill_pool();
+			let challenge_id = challenge_with_votes(1, &[Vote::Invalid]);
+			let jurors = Challenges::challenges(challenge_id).unwrap().jurors;
+			let outsider = (10..=15).find(|juror| !jurors.contains(juror)).unwrap();
+
+			System::assert_last_event(
+				Event::Voted { challenge_id, juror: jurors[0], vote: Vote::Invalid }.into(),
+			);
+			assert_eq!(Challenges::votes(challenge_id, jurors[0]), Some(Vote::Invalid));
+			assert_noop!(
+				Challenges::vote(RuntimeOrigin::signed(jurors[0]), challenge_id, Vote::Valid),
+				Error::<Test>::AlreadyVoted
+			);
+			assert_noop!(
+				Challenges::vote(RuntimeOrigin::signed(outsider), challenge_id, Vote::Valid),
+				Error::<Test>::NotOnJury
+			);
+			assert_noop!(
+				Challenges::vote(RuntimeOrigin::signed(jurors[1]), 7, Vote::Valid),
+				Error::<Test>::ChallengeNotFound
+			);
+			assert_noop!(
+				Challenges::resolve_challenge(RuntimeOrigin::signed(3), challenge_id),
+				Error::<Test>::VotingOpen
+			);
+
+			System::set_block_number(14);
+			assert_noop!(
+				Challenges::vote(RuntimeOrigin::signed(jurors[1]), challenge_id, Vote::Valid),
+				Error::<Test>::VotingClosed
+			);
+			// Jurors who did not vote are not counted
+			assert_ok!(Challenges::resolve_challenge(RuntimeOrigin::signed(3), challenge_id));
+			assert_eq!(
+				Challenges::results(challenge_id).unwrap().outcome,
+				ChallengeOutcome::Upheld
+			);
+			assert_eq!(Challenges::votes(challenge_id, jurors[0]), None);
+			assert!(jurors.iter().all(|juror| !JurorDuties::<Test>::contains_key(juror)));
+		});
+	}
+
+	#[test]
+	fn test_upheld_challenge() {
+		new_test_ext().execute_with(|| {
+			fill_pool();
+			let challenge_id = challenge_with_votes(
+				1,
+				&[Vote::Invalid, Vote::Invalid, Vote::Invalid, Vote::Invalid, Vote::Valid],
+			);
+
+			// Resolved as soon as every juror voted
+			assert_ok!(Challenges::resolve_challenge(RuntimeOrigin::signed(3), challenge_id));
+			System::assert_last_event(
+				Event::ChallengeResolved {
+					challenge_id,
+					credential_id: credential(1),
+					outcome: ChallengeOutcome::Upheld,
+					valid: 1,
+					invalid: 4,
+				}
+				.into(),
+			);
+			assert_eq!(
+				Challenges::credential_statuses(credential(1)),
+				Some(CredentialStatus::Invalid)
+			);
+			assert!(<Challenges as CredentialDisputes<Hash>>::is_invalid(&credential(1)));
+			assert_eq!(Invalidated::get(), vec![credential(1)]);
+			assert_eq!(Slashed::get(), vec![(credential(1), CHALLENGER)]);
+			assert_eq!(Balances::free_balance(CHALLENGER), 100);
+			assert_eq!(Challenges::challenges(challenge_id), None);
+			assert_eq!(Challenges::active_challenges(credential(1)), None);
+			assert_eq!(
+				Challenges::results(challenge_id),
+				Some(ChallengeResult {
+					credential_id: credential(1),
+					challenger: CHALLENGER,
+					outcome: ChallengeOutcome::Upheld,
+					valid: 1,
+					invalid: 4,
+				})
+			);
+
+			assert_noop!(
+				Challenges::challenge_credential(RuntimeOrigin::signed(CHALLENGER), credential(1)),
+				Error::<Test>::AlreadyInvalid
+			);
+			assert_noop!(
+				Challenges::resolve_challenge(RuntimeOrigin::signed(3), challenge_id),
+				Error::<Test>::ChallengeNotFound
+			);
+		});
+	}
+
+	#[test]
+	fn test_disputed_and_rejected_challenges() {
+		new_test_ext().execute_with(|| {
+			fill_pool();
+
+			// A narrow majority marks the credential disputed
+			let disputed = challenge_with_votes(
+				1,
+				&[Vote::Invalid, Vote::Invalid, Vote::Invalid, Vote::Valid, Vote::Valid],
+			);
+			assert_ok!(Challenges::resolve_challenge(RuntimeOrigin::signed(3), disputed));
+			assert_eq!(Challenges::results(disputed).unwrap().outcome, ChallengeOutcome::Disputed);
+			assert_eq!(
+				Challenges::credential_statuses(credential(1)),
+				Some(CredentialStatus::Disputed)
+			);
+			assert!(!<Challenges as CredentialDisputes<Hash>>::is_invalid(&credential(1)));
+			assert_eq!(Balances::free_balance(CHALLENGER), 100);
+			assert_eq!(Slashed::get(), vec![]);
+			assert_eq!(Invalidated::get(), vec![]);
+
+			// Without a majority of invalid votes, the deposit goes to the owner
+			let rejected = challenge_with_votes(2, &[Vote::Invalid, Vote::Valid]);
+			System::set_block_number(16);
+			assert_ok!(Challenges::resolve_challenge(RuntimeOrigin::signed(3), rejected));
+			assert_eq!(Challenges::results(rejected).unwrap().outcome, ChallengeOutcome::Rejected);
+			assert_eq!(Challenges::credential_statuses(credential(2)), None);
+			assert_eq!(Balances::free_balance(CHALLENGER), 50);
+			assert_eq!(Balances::reserved_balance(CHALLENGER), 0);
+			assert_eq!(Balances::free_balance(OWNER), 150);
+		});
+	}
+
+	#[test]
+	fn test_jurors_who_do_not_vote() {
+		new_test_ext().execute_with(|| {
+			fill_pool();
+
+			// Nobody voting decides nothing: the deposit is returned
+			let challenge_id = challenge_with_votes(1, &[]);
+			let jurors = Challenges::challenges(challenge_id).unwrap().jurors;
+			System::set_block_number(14);
+			assert_ok!(Challenges::resolve_challenge(RuntimeOrigin::signed(3), challenge_id));
+			assert_eq!(Challenges::results(challenge_id).unwrap().outcome, ChallengeOutcome::NoVotes);
+			assert_eq!(Challenges::credential_statuses(credential(1)), None);
+			assert_eq!(Balances::free_balance(CHALLENGER), 100);
+			assert_eq!(Balances::free_balance(OWNER), 100);
+
+			// Every juror forfeited part of its bond, which is burned
+			System::assert_has_event(
+				Event::JurorPenalized { juror: jurors[0], challenge_id, penalty: 8 }.into(),
+			);
+			for juror in jurors.iter() {
+				assert_eq!(Balances::reserved_balance(juror), 12);
+				assert_eq!(Challenges::juror_penalties(juror), 8);
+			}
+			assert_eq!(Balances::total_issuance(), 20 * 100 - 5 * 8);
+
+			// Jurors whose bond is used up are removed from the pool
+			for n in 2..=3 {
+				let challenge_id = challenge_with_votes(n, &[]);
+				System::set_block_number(Challenges::challenges(challenge_id).unwrap().ends_at + 1);
+				assert_ok!(Challenges::resolve_challenge(RuntimeOrigin::signed(3), challenge_id));
+			}
+			let pool = Challenges::jury_pool();
+			assert!(pool.len() < 6);
+			for juror in (10..=15).filter(|juror| !pool.contains(juror)) {
+				System::assert_has_event(Event::JurorRemoved { juror }.into());
+				assert_eq!(Balances::reserved_balance(juror), 0);
+				assert_eq!(Balances::free_balance(juror), 80);
+				assert!(!JurorPenalties::<Test>::contains_key(juror));
+			}
+
+			// Leaving returns what is left of the bond
+			let juror = pool[0];
+			let left = 20 - Challenges::juror_penalties(juror);
+			assert_ok!(Challenges::leave_jury_pool(RuntimeOrigin::signed(juror)));
+			assert_eq!(Balances::reserved_balance(juror), 0);
+			assert_eq!(Balances::free_balance(juror), 80 + left);
+			assert!(!JurorPenalties::<Test>::contains_key(juror));
+		});
+	}
+
+	#[test]
+	fn test_outcome() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(Challenges::outcome(0, 0), ChallengeOutcome::NoVotes);
+			assert_eq!(Challenges::outcome(2, 2), ChallengeOutcome::Rejected);
+			assert_eq!(Challenges::outcome(2, 3), ChallengeOutcome::Disputed);
+			assert_eq!(Challenges::outcome(1, 2), ChallengeOutcome::Upheld);
+			assert_eq!(Challenges::outcome(0, 1), ChallengeOutcome::Upheld);
+		});
+	}
+}
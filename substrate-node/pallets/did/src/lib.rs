@@ -0,0 +1,832 @@
+//! # FreelanceForge DID Pallet
+//!
+//! Decentralized identifiers of the form `did:forge:<account>` for freelancers, clients and
+//! credential issuers.
+//!
+//! Every account implicitly has a DID document: it is its own controller and has no
+//! verification methods or services besides the controller account itself. The pallet only
+//! stores documents that were changed from that default, so resolution never fails.
+//!
+//! ## Features
+//!
+//! - **Verification methods**: Additional public keys (sr25519, ed25519, ecdsa) with the
+//!   DID Core verification relationships they may be used for
+//! - **Key rotation**: The public key of a verification method can be replaced in place
+//! - **Controller transfer**: Control of a DID can be handed to another account, e.g. a
+//!   new key after the original one was compromised
+//! - **Service endpoints**: Portfolio, messaging or website endpoints tied to the DID
+//! - **Deactivation**: A DID can be permanently deactivated; it then resolves to an empty
+//!   document and other pallets stop accepting it
+//!
+//! ## Storage
+//!
+//! - `Dids`: Maps DID subjects to [`DidDetails`] records (controller, verification methods,
+//!   services, last update, deactivation flag)
+//!
+//! ## Runtime API
+//!
+//! [`runtime_api::DidApi`] resolves the [`DidDocument`] of any account.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::DecodeWithMemTracking;
+use frame::prelude::*;
+use polkadot_sdk::polkadot_sdk_frame as frame;
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+pub mod runtime_api;
+
+// Re-export all pallet parts for runtime integration
+pub use pallet::*;
+
+/// Maximum number of verification methods per DID, besides the controller
+pub const MAX_VERIFICATION_METHODS: u32 = 10;
+
+/// Maximum number of service endpoints per DID
+pub const MAX_SERVICES: u32 = 10;
+
+/// Fragment of the implicit verification method backed by the controller account
+pub const CONTROLLER_FRAGMENT: &[u8] = b"controller";
+
+/// Fragment identifying a verification method or service within a DID document
+/// (the part after `#`), made of ASCII letters, digits, `-` and `_`
+pub type Fragment = BoundedVec<u8, ConstU32<32>>;
+
+/// Type of a service, e.g. `LinkedDomains` or `FreelancePortfolio`
+pub type ServiceType = BoundedVec<u8, ConstU32<64>>;
+
+/// URL of a service endpoint
+pub type ServiceEndpoint = BoundedVec<u8, ConstU32<256>>;
+
+/// Verification relationships a verification method is authorised for
+pub type Relationships = BoundedVec<VerificationRelationship, ConstU32<5>>;
+
+/// Public key material of a verification method
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum PublicKey {
+	/// Schnorrkel/Ristretto public key
+	Sr25519([u8; 32]),
+	/// Edwards curve public key
+	Ed25519([u8; 32]),
+	/// Compressed secp256k1 public key
+	Ecdsa([u8; 33]),
+}
+
+/// DID Core verification relationship
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum VerificationRelationship {
+	/// Proving control of the DID, e.g. logging in
+	Authentication,
+	/// Issuing credentials and other statements
+	AssertionMethod,
+	/// Establishing encrypted channels
+	KeyAgreement,
+	/// Invoking capabilities granted to the DID
+	CapabilityInvocation,
+	/// Delegating capabilities to others
+	CapabilityDelegation,
+}
+
+/// A public key published in a DID document
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct VerificationMethod {
+	/// Fragment of the method's DID URL
+	pub fragment: Fragment,
+	/// Public key of the method
+	pub public_key: PublicKey,
+	/// What the key may be used for
+	pub relationships: Relationships,
+}
+
+/// A service endpoint published in a DID document
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct Service {
+	/// Fragment of the service's DID URL
+	pub fragment: Fragment,
+	/// Type of the service
+	pub service_type: ServiceType,
+	/// Where the service can be reached
+	pub endpoint: ServiceEndpoint,
+}
+
+/// On-chain record of a DID document that differs from the implicit one
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DidDetails<AccountId, BlockNumber> {
+	/// Account allowed to change the document
+	pub controller: AccountId,
+	/// Verification methods besides the controller account
+	pub verification_methods: BoundedVec<VerificationMethod, ConstU32<MAX_VERIFICATION_METHODS>>,
+	/// Service endpoints
+	pub services: BoundedVec<Service, ConstU32<MAX_SERVICES>>,
+	/// Block in which the document was last changed
+	pub updated: BlockNumber,
+	/// Whether the DID was deactivated (irreversible)
+	pub deactivated: bool,
+}
+
+/// [`DidDetails`] specialised for a runtime
+pub type DidDetailsOf<T> = DidDetails<
+	<T as polkadot_sdk::frame_system::Config>::AccountId,
+	BlockNumberFor<T>,
+>;
+
+/// Resolved DID document, as returned by [`runtime_api::DidApi`]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DidDocument<AccountId, BlockNumber> {
+	/// Subject of the DID
+	pub did: AccountId,
+	/// Account controlling the DID; also an implicit verification method
+	/// (see [`CONTROLLER_FRAGMENT`]) unless the DID is deactivated
+	pub controller: AccountId,
+	/// Verification methods besides the controller account
+	pub verification_methods: Vec<VerificationMethod>,
+	/// Service endpoints
+	pub services: Vec<Service>,
+	/// Block in which the document was last changed, `None` for implicit documents
+	pub updated: Option<BlockNumber>,
+	/// Whether the DID was deactivated
+	pub deactivated: bool,
+}
+
+/// Status of `did:forge` DIDs, for pallets that refer to accounts by their DID
+pub trait DidRegistry<AccountId> {
+	/// Whether the DID of `who` was deactivated
+	fn is_deactivated(who: &AccountId) -> bool;
+}
+
+/// No DID registry: no DID is ever deactivated
+impl<AccountId> DidRegistry<AccountId> for () {
+	fn is_deactivated(_who: &AccountId) -> bool {
+		false
+	}
+}
+
+#[frame::pallet]
+pub mod pallet {
+	use super::*;
+
+	/// Configuration trait for this pallet
+	#[pallet::config]
+	pub trait Config: polkadot_sdk::frame_system::Config {
+		/// The overarching runtime event type
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as polkadot_sdk::frame_system::Config>::RuntimeEvent>;
+	}
+
+	/// The pallet struct
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Storage map of DID documents changed from their implicit default
+	/// Maps did_subject -> DidDetails (controller, verification methods, services, updated, deactivated)
+	#[pallet::storage]
+	#[pallet::getter(fn dids)]
+	pub type Dids<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		DidDetailsOf<T>,
+		OptionQuery,
+	>;
+
+	/// Events emitted by the pallet
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A verification method was added to a DID document
+		/// [did, fragment]
+		VerificationMethodAdded { did: T::AccountId, fragment: Fragment },
+
+		/// A verification method was removed from a DID document
+		/// [did, fragment]
+		VerificationMethodRemoved { did: T::AccountId, fragment: Fragment },
+
+		/// The public key of a verification method was replaced
+		/// [did, fragment]
+		KeyRotated { did: T::AccountId, fragment: Fragment },
+
+		/// Control of a DID was handed to another account
+		/// [did, controller]
+		ControllerChanged { did: T::AccountId, controller: T::AccountId },
+
+		/// A service endpoint was added to a DID document
+		/// [did, fragment]
+		ServiceAdded { did: T::AccountId, fragment: Fragment },
+
+		/// A service endpoint was removed from a DID document
+		/// [did, fragment]
+		ServiceRemoved { did: T::AccountId, fragment: Fragment },
+
+		/// A DID was permanently deactivated
+		/// [did]
+		DidDeactivated { did: T::AccountId },
+	}
+
+	/// Errors that can occur when calling pallet extrinsics
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The caller is not the controller of the DID
+		NotController,
+		/// The DID was deactivated and can no longer change
+		DidDeactivated,
+		/// The fragment is empty, reserved or contains characters other than ASCII letters,
+		/// digits, `-` and `_`
+		InvalidFragment,
+		/// Another verification method or service already uses this fragment
+		DuplicateFragment,
+		/// No verification method or service uses this fragment
+		FragmentNotFound,
+		/// Another verification method already publishes this public key
+		DuplicateKey,
+		/// A verification method must have at least one verification relationship
+		NoRelationships,
+		/// The DID already has the maximum of 10 verification methods
+		TooManyVerificationMethods,
+		/// The DID already has the maximum of 10 services
+		TooManyServices,
+		/// The DID is already controlled by this account
+		AlreadyController,
+	}
+
+	/// Dispatchable extrinsics (functions) that can be called by users
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Publish a public key in a DID document
+		///
+		/// Must be signed by the DID's controller.
+		///
+		/// Parameters:
+		/// - `did`: Subject of the DID to change
+		/// - `method`: Verification method to add
+		///
+		/// Emits:
+		/// - `VerificationMethodAdded` event with the DID and the method's fragment
+		///
+		/// Errors:
+		/// - `NotController`: If the caller does not control the DID
+		/// - `DidDeactivated`: If the DID was deactivated
+		/// - `InvalidFragment`: If the fragment is malformed or reserved
+		/// - `DuplicateFragment`: If the fragment is already used in the document
+		/// - `DuplicateKey`: If the key is already published in the document
+		/// - `NoRelationships`: If the method has no verification relationship
+		/// - `TooManyVerificationMethods`: If the document already has 10 methods
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1) + Weight::from_parts(30_000, 0))]
+		pub fn add_verification_method(
+			origin: OriginFor<T>,
+			did: T::AccountId,
+			method: VerificationMethod,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut details = Self::controlled_details(&who, &did)?;
+
+			Self::ensure_fragment_available(&details, &method.fragment)?;
+			ensure!(!method.relationships.is_empty(), Error::<T>::NoRelationships);
+			ensure!(
+				details.verification_methods.iter().all(|existing| existing.public_key != method.public_key),
+				Error::<T>::DuplicateKey
+			);
+
+			let fragment = method.fragment.clone();
+			details
+				.verification_methods
+				.try_push(method)
+				.map_err(|_| Error::<T>::TooManyVerificationMethods)?;
+			Self::store(&did, details);
+
+			Self::deposit_event(Event::VerificationMethodAdded { did, fragment });
+
+			Ok(())
+		}
+
+		/// Remove a public key from a DID document
+		///
+		/// Parameters:
+		/// - `did`: Subject of the DID to change
+		/// - `fragment`: Fragment of the verification method to remove
+		///
+		/// Emits:
+		/// - `VerificationMethodRemoved` event with the DID and the method's fragment
+		///
+		/// Errors:
+		/// - `NotController`: If the caller does not control the DID
+		/// - `DidDeactivated`: If the DID was deactivated
+		/// - `FragmentNotFound`: If the document has no method with this fragment
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1) + Weight::from_parts(25_000, 0))]
+		pub fn remove_verification_method(
+			origin: OriginFor<T>,
+			did: T::AccountId,
+			fragment: Fragment,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut details = Self::controlled_details(&who, &did)?;
+
+			let index = details
+				.verification_methods
+				.iter()
+				.position(|method| method.fragment == fragment)
+				.ok_or(Error::<T>::FragmentNotFound)?;
+			details.verification_methods.remove(index);
+			Self::store(&did, details);
+
+			Self::deposit_event(Event::VerificationMethodRemoved { did, fragment });
+
+			Ok(())
+		}
+
+		/// Replace the public key of a verification method
+		///
+		/// The method keeps its fragment and relationships, so documents and credentials
+		/// referring to it stay valid while the old key stops being accepted.
+		///
+		/// Parameters:
+		/// - `did`: Subject of the DID to change
+		/// - `fragment`: Fragment of the verification method to rotate
+		/// - `new_key`: Replacement public key
+		///
+		/// Emits:
+		/// - `KeyRotated` event with the DID and the method's fragment
+		///
+		/// Errors:
+		/// - `NotController`: If the caller does not control the DID
+		/// - `DidDeactivated`: If the DID was deactivated
+		/// - `FragmentNotFound`: If the document has no method with this fragment
+		/// - `DuplicateKey`: If the key is already published in the document
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1) + Weight::from_parts(25_000, 0))]
+		pub fn rotate_key(
+			origin: OriginFor<T>,
+			did: T::AccountId,
+			fragment: Fragment,
+			new_key: PublicKey,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut details = Self::controlled_details(&who, &did)?;
+
+			ensure!(
+				details.verification_methods.iter().all(|method| method.public_key != new_key),
+				Error::<T>::DuplicateKey
+			);
+			let method = details
+				.verification_methods
+				.iter_mut()
+				.find(|method| method.fragment == fragment)
+				.ok_or(Error::<T>::FragmentNotFound)?;
+			method.public_key = new_key;
+			Self::store(&did, details);
+
+			Self::deposit_event(Event::KeyRotated { did, fragment });
+
+			Ok(())
+		}
+
+		/// Hand control of a DID to another account
+		///
+		/// The new controller replaces the old one as the implicit `#controller` verification
+		/// method. Use this to recover a DID whose controller key is compromised while it can
+		/// still sign, or to let an organisation manage its issuer DID from a multisig.
+		///
+		/// Parameters:
+		/// - `did`: Subject of the DID to change
+		/// - `controller`: New controller account
+		///
+		/// Emits:
+		/// - `ControllerChanged` event with the DID and the new controller
+		///
+		/// Errors:
+		/// - `NotController`: If the caller does not control the DID
+		/// - `DidDeactivated`: If the DID was deactivated
+		/// - `AlreadyController`: If `controller` already controls the DID
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1) + Weight::from_parts(20_000, 0))]
+		pub fn set_controller(
+			origin: OriginFor<T>,
+			did: T::AccountId,
+			controller: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut details = Self::controlled_details(&who, &did)?;
+
+			ensure!(details.controller != controller, Error::<T>::AlreadyController);
+
+			details.controller = controller.clone();
+			Self::store(&did, details);
+
+			Self::deposit_event(Event::ControllerChanged { did, controller });
+
+			Ok(())
+		}
+
+		/// Publish a service endpoint in a DID document
+		///
+		/// Parameters:
+		/// - `did`: Subject of the DID to change
+		/// - `service`: Service to add
+		///
+		/// Emits:
+		/// - `ServiceAdded` event with the DID and the service's fragment
+		///
+		/// Errors:
+		/// - `NotController`: If the caller does not control the DID
+		/// - `DidDeactivated`: If the DID was deactivated
+		/// - `InvalidFragment`: If the fragment is malformed or reserved
+		/// - `DuplicateFragment`: If the fragment is already used in the document
+		/// - `TooManyServices`: If the document already has 10 services
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1) + Weight::from_parts(30_000, 0))]
+		pub fn add_service(
+			origin: OriginFor<T>,
+			did: T::AccountId,
+			service: Service,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut details = Self::controlled_details(&who, &did)?;
+
+			Self::ensure_fragment_available(&details, &service.fragment)?;
+
+			let fragment = service.fragment.clone();
+			details.services.try_push(service).map_err(|_| Error::<T>::TooManyServices)?;
+			Self::store(&did, details);
+
+			Self::deposit_event(Event::ServiceAdded { did, fragment });
+
+			Ok(())
+		}
+
+		/// Remove a service endpoint from a DID document
+		///
+		/// Parameters:
+		/// - `did`: Subject of the DID to change
+		/// - `fragment`: Fragment of the service to remove
+		///
+		/// Emits:
+		/// - `ServiceRemoved` event with the DID and the service's fragment
+		///
+		/// Errors:
+		/// - `NotController`: If the caller does not control the DID
+		/// - `DidDeactivated`: If the DID was deactivated
+		/// - `FragmentNotFound`: If the document has no service with this fragment
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1) + Weight::from_parts(25_000, 0))]
+		pub fn remove_service(
+			origin: OriginFor<T>,
+			did: T::AccountId,
+			fragment: Fragment,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut details = Self::controlled_details(&who, &did)?;
+
+			let index = details
+				.services
+				.iter()
+				.position(|service| service.fragment == fragment)
+				.ok_or(Error::<T>::FragmentNotFound)?;
+			details.services.remove(index);
+			Self::store(&did, details);
+
+			Self::deposit_event(Event::ServiceRemoved { did, fragment });
+
+			Ok(())
+		}
+
+		/// Permanently deactivate a DID
+		///
+		/// Verification methods and services are dropped, and the DID can never be changed
+		/// or reactivated. Pallets using [`DidRegistry`] stop accepting it, e.g. credentials
+		/// can no longer be minted for or issued by it.
+		///
+		/// Parameters:
+		/// - `did`: Subject of the DID to deactivate
+		///
+		/// Emits:
+		/// - `DidDeactivated` event with the DID
+		///
+		/// Errors:
+		/// - `NotController`: If the caller does not control the DID
+		/// - `DidDeactivated`: If the DID was already deactivated
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1) + Weight::from_parts(20_000, 0))]
+		pub fn deactivate(origin: OriginFor<T>, did: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut details = Self::controlled_details(&who, &did)?;
+
+			details.verification_methods = Default::default();
+			details.services = Default::default();
+			details.deactivated = true;
+			Self::store(&did, details);
+
+			Self::deposit_event(Event::DidDeactivated { did });
+
+			Ok(())
+		}
+	}
+
+	/// Helper functions for the pallet
+	impl<T: Config> Pallet<T> {
+		/// Resolve the DID document of `did`
+		///
+		/// Accounts whose document was never changed resolve to the implicit document.
+		pub fn resolve(did: T::AccountId) -> DidDocument<T::AccountId, BlockNumberFor<T>> {
+			match Dids::<T>::get(&did) {
+				Some(details) => DidDocument {
+					did,
+					controller: details.controller,
+					verification_methods: details.verification_methods.into_inner(),
+					services: details.services.into_inner(),
+					updated: Some(details.updated),
+					deactivated: details.deactivated,
+				},
+				None => DidDocument {
+					controller: did.clone(),
+					did,
+					verification_methods: Vec::new(),
+					services: Vec::new(),
+					updated: None,
+					deactivated: false,
+				},
+			}
+		}
+
+		/// Current record of `did`, checking that `who` may change it
+		fn controlled_details(
+			who: &T::AccountId,
+			did: &T::AccountId,
+		) -> Result<DidDetailsOf<T>, DispatchError> {
+			let details = Dids::<T>::get(did).unwrap_or_else(|| DidDetails {
+				controller: did.clone(),
+				verification_methods: Default::default(),
+				services: Default::default(),
+				updated: Default::default(),
+				deactivated: false,
+			});
+
+			ensure!(!details.deactivated, Error::<T>::DidDeactivated);
+			ensure!(details.controller == *who, Error::<T>::NotController);
+
+			Ok(details)
+		}
+
+		/// Check that `fragment` is well formed and unused in `details`
+		fn ensure_fragment_available(details: &DidDetailsOf<T>, fragment: &Fragment) -> DispatchResult {
+			ensure!(
+				!fragment.is_empty() &&
+					&fragment[..] != CONTROLLER_FRAGMENT &&
+					fragment.iter().all(|c| c.is_ascii_alphanumeric() || *c == b'-' || *c == b'_'),
+				Error::<T>::InvalidFragment
+			);
+			ensure!(
+				details.verification_methods.iter().all(|method| method.fragment != *fragment) &&
+					details.services.iter().all(|service| service.fragment != *fragment),
+				Error::<T>::DuplicateFragment
+			);
+			Ok(())
+		}
+
+		/// Write back a changed record, stamping it with the current block
+		fn store(did: &T::AccountId, mut details: DidDetailsOf<T>) {
+			details.updated = polkadot_sdk::frame_system::Pallet::<T>::block_number();
+			Dids::<T>::insert(did, details);
+		}
+	}
+
+	impl<T: Config> DidRegistry<T::AccountId> for Pallet<T> {
+		fn is_deactivated(who: &T::AccountId) -> bool {
+			Dids::<T>::get(who).is_some_and(|details| details.deactivated)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame::testing_prelude::*;
+
+	// Configure a mock runtime to test the pallet
+	construct_runtime!(
+		pub enum Test {
+			System: frame_system,
+			Did: crate,
+		}
+	);
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = MockBlock<Test>;
+		type AccountId = u64;
+	}
+
+	impl Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+	}
+
+	// Build genesis storage according to the mock runtime
+	pub fn new_test_ext() -> TestExternalities {
+		let mut ext: TestExternalities =
+			frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into();
+		// Events are only recorded after the genesis block
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn fragment(name: &str) -> Fragment {
+		name.as_bytes().to_vec().try_into().unwrap()
+	}
+
+	fn method(name: &str, key: u8) -> VerificationMethod {
+		VerificationMethod {
+			fragment: fragment(name),
+			public_key: PublicKey::Sr25519([key; 32]),
+			relationships: vec![VerificationRelationship::Authentication].try_into().unwrap(),
+		}
+	}
+
+	fn service(name: &str) -> Service {
+		Service {
+			fragment: fragment(name),
+			service_type: b"FreelancePortfolio".to_vec().try_into().unwrap(),
+			endpoint: b"https://freelanceforge.example/alice".to_vec().try_into().unwrap(),
+		}
+	}
+
+	#[test]
+	fn test_implicit_document() {
+		new_test_ext().execute_with(|| {
+			let document = Did::resolve(1);
+
+			assert_eq!(document.did, 1);
+			assert_eq!(document.controller, 1);
+			assert!(document.verification_methods.is_empty());
+			assert!(document.services.is_empty());
+			assert_eq!(document.updated, None);
+			assert!(!document.deactivated);
+			assert!(!<Did as DidRegistry<u64>>::is_deactivated(&1));
+		});
+	}
+
+	#[test]
+	fn test_add_rotate_and_remove_verification_method() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Did::add_verification_method(RuntimeOrigin::signed(1), 1, method("key-1", 7)));
+			System::assert_last_event(
+				Event::VerificationMethodAdded { did: 1, fragment: fragment("key-1") }.into(),
+			);
+			assert_eq!(Did::resolve(1).updated, Some(1));
+
+			// Fragments and keys are unique within a document
+			assert_noop!(
+				Did::add_verification_method(RuntimeOrigin::signed(1), 1, method("key-1", 8)),
+				Error::<Test>::DuplicateFragment
+			);
+			assert_noop!(
+				Did::add_verification_method(RuntimeOrigin::signed(1), 1, method("key-2", 7)),
+				Error::<Test>::DuplicateKey
+			);
+			assert_noop!(
+				Did::add_verification_method(RuntimeOrigin::signed(1), 1, method("controller", 8)),
+				Error::<Test>::InvalidFragment
+			);
+			assert_noop!(
+				Did::add_verification_method(RuntimeOrigin::signed(1), 1, method("key 2", 8)),
+				Error::<Test>::InvalidFragment
+			);
+
+			// Only the controller may change the document
+			assert_noop!(
+				Did::rotate_key(RuntimeOrigin::signed(2), 1, fragment("key-1"), PublicKey::Ed25519([9; 32])),
+				Error::<Test>::NotController
+			);
+
+			assert_ok!(Did::rotate_key(
+				RuntimeOrigin::signed(1),
+				1,
+				fragment("key-1"),
+				PublicKey::Ed25519([9; 32])
+			));
+			System::assert_last_event(Event::KeyRotated { did: 1, fragment: fragment("key-1") }.into());
+			let document = Did::resolve(1);
+			assert_eq!(document.verification_methods[0].public_key, PublicKey::Ed25519([9; 32]));
+			assert_eq!(document.verification_methods[0].relationships.len(), 1);
+
+			assert_ok!(Did::remove_verification_method(RuntimeOrigin::signed(1), 1, fragment("key-1")));
+			assert!(Did::resolve(1).verification_methods.is_empty());
+			assert_noop!(
+				Did::remove_verification_method(RuntimeOrigin::signed(1), 1, fragment("key-1")),
+				Error::<Test>::FragmentNotFound
+			);
+		});
+	}
+
+	#[test]
+	fn test_services() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Did::add_service(RuntimeOrigin::signed(1), 1, service("portfolio")));
+			System::assert_last_event(Event::ServiceAdded { did: 1, fragment: fragment("portfolio") }.into());
+			assert_eq!(Did::resolve(1).services, vec![service("portfolio")]);
+
+			// Services and verification methods share the fragment namespace
+			assert_noop!(
+				Did::add_verification_method(RuntimeOrigin::signed(1), 1, method("portfolio", 7)),
+				Error::<Test>::DuplicateFragment
+			);
+
+			for n in 1..MAX_SERVICES {
+				assert_ok!(Did::add_service(RuntimeOrigin::signed(1), 1, service(&format!("service-{n}"))));
+			}
+			assert_noop!(
+				Did::add_service(RuntimeOrigin::signed(1), 1, service("one-too-many")),
+				Error::<Test>::TooManyServices
+			);
+
+			assert_ok!(Did::remove_service(RuntimeOrigin::signed(1), 1, fragment("portfolio")));
+			System::assert_last_event(Event::ServiceRemoved { did: 1, fragment: fragment("portfolio") }.into());
+			assert_eq!(Did::resolve(1).services.len(), (MAX_SERVICES - 1) as usize);
+		});
+	}
+
+	#[test]
+	fn test_controller_transfer() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Did::set_controller(RuntimeOrigin::signed(1), 1, 2));
+			System::assert_last_event(Event::ControllerChanged { did: 1, controller: 2 }.into());
+			assert_eq!(Did::resolve(1).controller, 2);
+
+			// The subject itself lost control
+			assert_noop!(
+				Did::add_service(RuntimeOrigin::signed(1), 1, service("portfolio")),
+				Error::<Test>::NotController
+			);
+			assert_ok!(Did::add_service(RuntimeOrigin::signed(2), 1, service("portfolio")));
+			assert_noop!(
+				Did::set_controller(RuntimeOrigin::signed(2), 1, 2),
+				Error::<Test>::AlreadyController
+			);
+		});
+	}
+
+	#[test]
+	fn test_deactivation_is_permanent() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Did::add_verification_method(RuntimeOrigin::signed(1), 1, method("key-1", 7)));
+			assert_ok!(Did::add_service(RuntimeOrigin::signed(1), 1, service("portfolio")));
+
+			assert_noop!(Did::deactivate(RuntimeOrigin::signed(2), 1), Error::<Test>::NotController);
+			assert_ok!(Did::deactivate(RuntimeOrigin::signed(1), 1));
+			System::assert_last_event(Event::DidDeactivated { did: 1 }.into());
+
+			let document = Did::resolve(1);
+			assert!(document.deactivated);
+			assert!(document.verification_methods.is_empty());
+			assert!(document.services.is_empty());
+			assert!(<Did as DidRegistry<u64>>::is_deactivated(&1));
+
+			assert_noop!(Did::deactivate(RuntimeOrigin::signed(1), 1), Error::<Test>::DidDeactivated);
+			assert_noop!(
+				Did::set_controller(RuntimeOrigin::signed(1), 1, 2),
+				Error::<Test>::DidDeactivated
+			);
+		});
+	}
+}
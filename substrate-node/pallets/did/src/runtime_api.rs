@@ -0,0 +1,17 @@
+//! Runtime API resolving `did:forge` DID documents.
+
+use crate::DidDocument;
+use codec::Codec;
+use polkadot_sdk::polkadot_sdk_frame as frame;
+
+frame::deps::sp_api::decl_runtime_apis! {
+	/// Resolution of DID documents
+	pub trait DidApi<AccountId, BlockNumber>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// DID document of `did`; the implicit document if it was never changed
+		fn resolve(did: AccountId) -> DidDocument<AccountId, BlockNumber>;
+	}
+}
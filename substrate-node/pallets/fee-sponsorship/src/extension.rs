@@ -0,0 +1,185 @@
+//! Transaction extension charging fees to sponsors.
+
+use crate::{BalanceOf, Config, Event, Pallet};
+use codec::DecodeWithMemTracking;
+use core::fmt;
+use frame::prelude::*;
+use polkadot_sdk::{
+	frame_support::{
+		dispatch::{DispatchInfo, PostDispatchInfo},
+		traits::Contains,
+	},
+	pallet_transaction_payment::{self, ChargeTransactionPayment, OnChargeTransaction},
+	polkadot_sdk_frame as frame,
+	sp_runtime::{
+		traits::{
+			AsSystemOriginSigner, DispatchInfoOf, Dispatchable, Implication, PostDispatchInfoOf,
+			TransactionExtension, ValidateResult,
+		},
+		transaction_validity::{TransactionSource, TransactionValidityError, ValidTransaction},
+		FixedPointOperand,
+	},
+};
+
+/// Drop-in replacement for [`ChargeTransactionPayment`] that charges fees to sponsors.
+///
+/// Signed calls matched by [`Config::SponsoredCalls`] are paid by the signer's sponsor when
+/// the budgets of the current period cover the fee (including the tip) and the sponsor can
+/// afford it. Everything else is handed to [`ChargeTransactionPayment`].
+///
+/// Encoded like [`ChargeTransactionPayment`] and under the same identifier, so wallets and
+/// client libraries sign transactions exactly as before.
+#[derive(Encode, Decode, DecodeWithMemTracking, CloneNoBound, EqNoBound, PartialEqNoBound, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ChargeSponsoredTransactionPayment<T: Config>(#[codec(compact)] BalanceOf<T>);
+
+impl<T: Config> ChargeSponsoredTransactionPayment<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+{
+	/// Create an instance including a `tip` for the block author.
+	pub fn from(tip: BalanceOf<T>) -> Self {
+		Self(tip)
+	}
+
+	/// The wrapped extension, charging the signer.
+	fn inner(&self) -> ChargeTransactionPayment<T> {
+		ChargeTransactionPayment::from(self.0)
+	}
+}
+
+impl<T: Config> fmt::Debug for ChargeSponsoredTransactionPayment<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ChargeSponsoredTransactionPayment<{:?}>", self.0)
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+		Ok(())
+	}
+}
+
+/// Values carried from validation to preparation.
+pub enum Val<T: Config> {
+	/// The sponsor pays `fee` for `user`.
+	Sponsored { sponsor: T::AccountId, user: T::AccountId, fee: BalanceOf<T> },
+	/// The signer pays, as decided by [`ChargeTransactionPayment`].
+	Unsponsored(pallet_transaction_payment::Val<T>),
+}
+
+/// Values carried from preparation to post dispatch.
+pub enum Pre<T: Config> {
+	/// The sponsor paid `fee` for `user`, withdrawn as `imbalance`.
+	Sponsored {
+		sponsor: T::AccountId,
+		user: T::AccountId,
+		fee: BalanceOf<T>,
+		tip: BalanceOf<T>,
+		imbalance: <T::OnChargeTransaction as OnChargeTransaction<T>>::LiquidityInfo,
+	},
+	/// The signer paid, as decided by [`ChargeTransactionPayment`].
+	Unsponsored(pallet_transaction_payment::Pre<T>),
+}
+
+impl<T: Config> TransactionExtension<T::RuntimeCall> for ChargeSponsoredTransactionPayment<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	<T::RuntimeCall as Dispatchable>::RuntimeOrigin: AsSystemOriginSigner<T::AccountId> + Clone,
+	BalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
+{
+	// Same as `ChargeTransactionPayment`, which this extension replaces
+	const IDENTIFIER: &'static str = "ChargeTransactionPayment";
+	type Implicit = ();
+	type Val = Val<T>;
+	type Pre = Pre<T>;
+
+	fn weight(&self, call: &T::RuntimeCall) -> Weight {
+		// Sponsor and sponsorship lookups and spending updates
+		self.inner().weight(call).saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+
+	fn validate(
+		&self,
+		origin: <T::RuntimeCall as Dispatchable>::RuntimeOrigin,
+		call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		len: usize,
+		self_implicit: (),
+		inherited_implication: &impl Implication,
+		source: TransactionSource,
+	) -> ValidateResult<Self::Val, T::RuntimeCall> {
+		if let Some(user) = origin.as_system_origin_signer().cloned() {
+			if T::SponsoredCalls::contains(call) {
+				let tip = self.0;
+				let fee = pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, info, tip);
+				let sponsor = Pallet::<T>::sponsor_for(&user, fee).filter(|sponsor| {
+					T::OnChargeTransaction::can_withdraw_fee(sponsor, call, info, fee, tip).is_ok()
+				});
+				if let Some(sponsor) = sponsor {
+					let priority = ChargeTransactionPayment::<T>::get_priority(info, len, tip, fee);
+					let validity = ValidTransaction { priority, ..Default::default() };
+					return Ok((validity, Val::Sponsored { sponsor, user, fee }, origin));
+				}
+			}
+		}
+
+		let (validity, val, origin) = self.inner().validate(
+			origin,
+			call,
+			info,
+			len,
+			self_implicit,
+			inherited_implication,
+			source,
+		)?;
+		Ok((validity, Val::Unsponsored(val), origin))
+	}
+
+	fn prepare(
+		self,
+		val: Self::Val,
+		origin: &<T::RuntimeCall as Dispatchable>::RuntimeOrigin,
+		call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		match val {
+			Val::Sponsored { sponsor, user, fee } => {
+				let tip = self.0;
+				let imbalance = T::OnChargeTransaction::withdraw_fee(&sponsor, call, info, fee, tip)?;
+				Pallet::<T>::charge(&sponsor, &user, fee);
+				Ok(Pre::Sponsored { sponsor, user, fee, tip, imbalance })
+			},
+			Val::Unsponsored(val) =>
+				self.inner().prepare(val, origin, call, info, len).map(Pre::Unsponsored),
+		}
+	}
+
+	fn post_dispatch_details(
+		pre: Self::Pre,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		post_info: &PostDispatchInfoOf<T::RuntimeCall>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<Weight, TransactionValidityError> {
+		let (sponsor, user, fee, tip, imbalance) = match pre {
+			Pre::Sponsored { sponsor, user, fee, tip, imbalance } =>
+				(sponsor, user, fee, tip, imbalance),
+			Pre::Unsponsored(pre) =>
+				return ChargeTransactionPayment::<T>::post_dispatch_details(
+					pre, info, post_info, len, result,
+				),
+		};
+
+		let actual_fee =
+			pallet_transaction_payment::Pallet::<T>::compute_actual_fee(len as u32, info, post_info, tip);
+		T::OnChargeTransaction::correct_and_deposit_fee(
+			&sponsor, info, post_info, actual_fee, tip, imbalance,
+		)?;
+		Pallet::<T>::refund(&sponsor, &user, fee.saturating_sub(actual_fee));
+		Pallet::<T>::deposit_event(Event::SponsoredFeePaid { sponsor, user, actual_fee, tip });
+
+		Ok(Weight::zero())
+	}
+}
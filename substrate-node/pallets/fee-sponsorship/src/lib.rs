@@ -0,0 +1,758 @@
+//! # FreelanceForge Fee Sponsorship Pallet
+//!
+//! Lets a sponsor, e.g. a platform onboarding freelancers or an issuer minting credentials
+//! for its graduates, pay the transaction fees of other accounts, so new users can manage
+//! their credentials without holding tokens first.
+//!
+//! Fees are charged by the [`ChargeSponsoredTransactionPayment`] transaction extension,
+//! which takes the place of `pallet_transaction_payment::ChargeTransactionPayment` in the
+//! runtime. For calls matched by [`Config::SponsoredCalls`] it charges the sponsor of the
+//! signer, as long as both the sponsor's and the user's budget for the current period
+//! cover the fee. Every other transaction, and every sponsored transaction the budgets or
+//! the sponsor's balance cannot cover, is paid by its signer as usual.
+//!
+//! ## Features
+//!
+//! - **Sponsors**: Any account can become a sponsor by setting the budget it spends on fees
+//!   per period (a number of blocks)
+//! - **Per-user budgets**: Sponsors pick the accounts they pay for and how much each of them
+//!   may spend per period
+//! - **Opting out**: Sponsored accounts can leave a sponsorship to pay their own fees
+//!
+//! ## Storage
+//!
+//! - `Sponsors`: Maps sponsors to their [`SponsorDetails`] (period, budget, spending)
+//! - `Sponsorships`: Maps sponsored accounts to their [`Sponsorship`] (sponsor, budget,
+//!   spending)
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame::prelude::*;
+use polkadot_sdk::{
+	frame_support::traits::Contains,
+	pallet_transaction_payment::{self, OnChargeTransaction},
+	polkadot_sdk_frame as frame,
+};
+
+mod extension;
+
+// Re-export all pallet parts for runtime integration
+pub use extension::ChargeSponsoredTransactionPayment;
+pub use pallet::*;
+
+/// Balance type fees are paid in
+pub type BalanceOf<T> = <<T as pallet_transaction_payment::Config>::OnChargeTransaction as OnChargeTransaction<
+	T,
+>>::Balance;
+
+/// Fees paid within a budget period
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Spending<Balance, BlockNumber> {
+	/// First block of the period the fees were paid in
+	pub period_start: BlockNumber,
+	/// Fees paid so far in that period
+	pub spent: Balance,
+}
+
+impl<Balance, BlockNumber> Spending<Balance, BlockNumber>
+where
+	Balance: AtLeast32BitUnsigned + Copy,
+	BlockNumber: PartialEq,
+{
+	/// Part of `budget` left in the period starting at `period_start`
+	pub fn remaining(&self, budget: Balance, period_start: &BlockNumber) -> Balance {
+		if self.period_start == *period_start {
+			budget.saturating_sub(self.spent)
+		} else {
+			budget
+		}
+	}
+
+	/// Add `fee` to the spending of the period starting at `period_start`, which resets
+	/// the spending of earlier periods
+	pub fn charge(&mut self, fee: Balance, period_start: BlockNumber) {
+		if self.period_start != period_start {
+			self.period_start = period_start;
+			self.spent = Zero::zero();
+		}
+		self.spent = self.spent.saturating_add(fee);
+	}
+
+	/// Give back `refund` of the fees charged in the period starting at `period_start`
+	pub fn refund(&mut self, refund: Balance, period_start: &BlockNumber) {
+		if self.period_start == *period_start {
+			self.spent = self.spent.saturating_sub(refund);
+		}
+	}
+}
+
+/// On-chain record of a sponsor
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SponsorDetails<Balance, BlockNumber> {
+	/// Length of a budget period, in blocks
+	pub period: BlockNumber,
+	/// Fees the sponsor pays per period, across all its users
+	pub budget: Balance,
+	/// Fees paid in the current or last active period
+	pub spending: Spending<Balance, BlockNumber>,
+}
+
+/// [`SponsorDetails`] specialised for a runtime
+pub type SponsorDetailsOf<T> = SponsorDetails<BalanceOf<T>, BlockNumberFor<T>>;
+
+/// On-chain record of a sponsored account
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Sponsorship<AccountId, Balance, BlockNumber> {
+	/// Account paying the fees
+	pub sponsor: AccountId,
+	/// Fees the sponsor pays for this account per period
+	pub budget: Balance,
+	/// Fees paid for this account in the current or last active period
+	pub spending: Spending<Balance, BlockNumber>,
+}
+
+/// [`Sponsorship`] specialised for a runtime
+pub type SponsorshipOf<T> = Sponsorship<
+	<T as polkadot_sdk::frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	BlockNumberFor<T>,
+>;
+
+#[frame::pallet]
+pub mod pallet {
+	use super::*;
+
+	/// Configuration trait for this pallet
+	#[pallet::config]
+	pub trait Config:
+		polkadot_sdk::frame_system::Config + pallet_transaction_payment::Config
+	{
+		/// The overarching runtime event type
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as polkadot_sdk::frame_system::Config>::RuntimeEvent>;
+
+		/// Calls whose fees sponsors pay
+		type SponsoredCalls: Contains<<Self as polkadot_sdk::frame_system::Config>::RuntimeCall>;
+	}
+
+	/// The pallet struct
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Storage map of sponsors
+	/// Maps sponsor -> SponsorDetails (period, budget, spending)
+	#[pallet::storage]
+	#[pallet::getter(fn sponsors)]
+	pub type Sponsors<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, SponsorDetailsOf<T>, OptionQuery>;
+
+	/// Storage map of sponsored accounts
+	/// Maps user -> Sponsorship (sponsor, budget, spending)
+	#[pallet::storage]
+	#[pallet::getter(fn sponsorships)]
+	pub type Sponsorships<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, SponsorshipOf<T>, OptionQuery>;
+
+	/// Events emitted by the pallet
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An account registered as a sponsor or changed its budget
+		/// [sponsor, period, budget]
+		SponsorBudgetSet { sponsor: T::AccountId, period: BlockNumberFor<T>, budget: BalanceOf<T> },
+
+		/// A sponsor stopped paying fees
+		/// [sponsor]
+		SponsorUnregistered { sponsor: T::AccountId },
+
+		/// A sponsor started paying the fees of an account or changed its budget
+		/// [sponsor, user, budget]
+		UserSponsored { sponsor: T::AccountId, user: T::AccountId, budget: BalanceOf<T> },
+
+		/// A sponsor stopped paying the fees of an account
+		/// [sponsor, user]
+		SponsorshipEnded { sponsor: T::AccountId, user: T::AccountId },
+
+		/// A sponsor paid the fee of a transaction
+		/// [sponsor, user, actual_fee, tip]
+		SponsoredFeePaid {
+			sponsor: T::AccountId,
+			user: T::AccountId,
+			actual_fee: BalanceOf<T>,
+			tip: BalanceOf<T>,
+		},
+	}
+
+	/// Errors that can occur when calling pallet extrinsics
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Budget periods must be at least one block long
+		ZeroPeriod,
+		/// The caller is not a registered sponsor
+		NotSponsor,
+		/// Sponsors cannot sponsor themselves
+		CannotSponsorSelf,
+		/// The account is already sponsored by another sponsor
+		AlreadySponsored,
+		/// The account is not sponsored by the caller
+		NotSponsoredByCaller,
+		/// The caller is not sponsored
+		NotSponsored,
+	}
+
+	/// Dispatchable extrinsics (functions) that can be called by users
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register as a sponsor, or change the budget of a registered sponsor
+		///
+		/// Fees paid in the current period count against the new budget, unless the
+		/// length of the period changes.
+		///
+		/// Parameters:
+		/// - `period`: Length of a budget period, in blocks
+		/// - `budget`: Fees the caller pays per period, across all its users
+		///
+		/// Emits:
+		/// - `SponsorBudgetSet` event with the sponsor, period and budget
+		///
+		/// Errors:
+		/// - `ZeroPeriod`: If the period is zero blocks long
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1) + Weight::from_parts(20_000, 0))]
+		pub fn set_sponsor_budget(
+			origin: OriginFor<T>,
+			period: BlockNumberFor<T>,
+			budget: BalanceOf<T>,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(!period.is_zero(), Error::<T>::ZeroPeriod);
+
+			Sponsors::<T>::mutate(&sponsor, |details| match details {
+				Some(details) => {
+					if details.period != period {
+						details.spending = Default::default();
+					}
+					details.period = period;
+					details.budget = budget;
+				},
+				None => {
+					*details =
+						Some(SponsorDetails { period, budget, spending: Default::default() })
+				},
+			});
+
+			Self::deposit_event(Event::SponsorBudgetSet { sponsor, period, budget });
+
+			Ok(())
+		}
+
+		/// Stop paying fees
+		///
+		/// The sponsorships of the caller's users stay in storage but are no longer used,
+		/// and take effect again if the caller registers anew.
+		///
+		/// Emits:
+		/// - `SponsorUnregistered` event with the sponsor
+		///
+		/// Errors:
+		/// - `NotSponsor`: If the caller is not a registered sponsor
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1) + Weight::from_parts(15_000, 0))]
+		pub fn unregister_sponsor(origin: OriginFor<T>) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(Sponsors::<T>::take(&sponsor).is_some(), Error::<T>::NotSponsor);
+
+			Self::deposit_event(Event::SponsorUnregistered { sponsor });
+
+			Ok(())
+		}
+
+		/// Pay the fees of an account, or change its budget
+		///
+		/// The sponsorship counts as a sufficient reference of the account, so accounts
+		/// without any balance exist and can sign sponsored transactions.
+		///
+		/// Parameters:
+		/// - `user`: Account whose fees the caller pays
+		/// - `budget`: Fees the caller pays for the account per period
+		///
+		/// Emits:
+		/// - `UserSponsored` event with the sponsor, account and budget
+		///
+		/// Errors:
+		/// - `NotSponsor`: If the caller is not a registered sponsor
+		/// - `CannotSponsorSelf`: If the account is the caller
+		/// - `AlreadySponsored`: If another sponsor pays the account's fees
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 2) + Weight::from_parts(20_000, 0))]
+		pub fn sponsor_user(
+			origin: OriginFor<T>,
+			user: T::AccountId,
+			budget: BalanceOf<T>,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(Sponsors::<T>::contains_key(&sponsor), Error::<T>::NotSponsor);
+			ensure!(sponsor != user, Error::<T>::CannotSponsorSelf);
+
+			Sponsorships::<T>::try_mutate(&user, |sponsorship| -> DispatchResult {
+				match sponsorship {
+					Some(sponsorship) => {
+						ensure!(sponsorship.sponsor == sponsor, Error::<T>::AlreadySponsored);
+						sponsorship.budget = budget;
+					},
+					None => {
+						// Keeps unfunded users alive, so their transactions pass the nonce check
+						frame_system::Pallet::<T>::inc_sufficients(&user);
+						*sponsorship = Some(Sponsorship {
+							sponsor: sponsor.clone(),
+							budget,
+							spending: Default::default(),
+						})
+					},
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::UserSponsored { sponsor, user, budget });
+
+			Ok(())
+		}
+
+		/// Stop paying the fees of an account
+		///
+		/// Parameters:
+		/// - `user`: Account whose fees the caller no longer pays
+		///
+		/// Emits:
+		/// - `SponsorshipEnded` event with the sponsor and account
+		///
+		/// Errors:
+		/// - `NotSponsoredByCaller`: If the caller does not pay the account's fees
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2) + Weight::from_parts(15_000, 0))]
+		pub fn unsponsor_user(origin: OriginFor<T>, user: T::AccountId) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(
+				Self::sponsorships(&user).is_some_and(|sponsorship| sponsorship.sponsor == sponsor),
+				Error::<T>::NotSponsoredByCaller
+			);
+			Sponsorships::<T>::remove(&user);
+			frame_system::Pallet::<T>::dec_sufficients(&user);
+
+			Self::deposit_event(Event::SponsorshipEnded { sponsor, user });
+
+			Ok(())
+		}
+
+		/// Leave a sponsorship and pay one's own fees again
+		///
+		/// Emits:
+		/// - `SponsorshipEnded` event with the sponsor and account
+		///
+		/// Errors:
+		/// - `NotSponsored`: If nobody pays the caller's fees
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2) + Weight::from_parts(15_000, 0))]
+		pub fn leave_sponsorship(origin: OriginFor<T>) -> DispatchResult {
+			let user = ensure_signed(origin)?;
+			let sponsorship = Sponsorships::<T>::take(&user).ok_or(Error::<T>::NotSponsored)?;
+			frame_system::Pallet::<T>::dec_sufficients(&user);
+
+			Self::deposit_event(Event::SponsorshipEnded { sponsor: sponsorship.sponsor, user });
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// First block of the current period of `period` blocks
+	fn period_start(period: BlockNumberFor<T>) -> BlockNumberFor<T> {
+		let now = frame_system::Pallet::<T>::block_number();
+		now.saturating_sub(now % period)
+	}
+
+	/// Sponsor paying `fee` for a call of `user`, if `user` is sponsored and both the
+	/// sponsor's and the user's budget for the current period cover the fee
+	pub fn sponsor_for(user: &T::AccountId, fee: BalanceOf<T>) -> Option<T::AccountId> {
+		let sponsorship = Self::sponsorships(user)?;
+		let details = Self::sponsors(&sponsorship.sponsor)?;
+		let period_start = Self::period_start(details.period);
+
+		let covered = details.spending.remaining(details.budget, &period_start) >= fee &&
+			sponsorship.spending.remaining(sponsorship.budget, &period_start) >= fee;
+		covered.then_some(sponsorship.sponsor)
+	}
+
+	/// Count `fee` against the budgets of `sponsor` and `user`
+	pub(crate) fn charge(sponsor: &T::AccountId, user: &T::AccountId, fee: BalanceOf<T>) {
+		Sponsors::<T>::mutate(sponsor, |details| {
+			if let Some(details) = details {
+				details.spending.charge(fee, Self::period_start(details.period));
+			}
+		});
+		let Some(period) = Self::sponsors(sponsor).map(|details| details.period) else {
+			return;
+		};
+		Sponsorships::<T>::mutate(user, |sponsorship| {
+			if let Some(sponsorship) = sponsorship {
+				sponsorship.spending.charge(fee, Self::period_start(period));
+			}
+		});
+	}
+
+	/// Give back `refund` of the fees counted against the budgets of `sponsor` and `user`
+	pub(crate) fn refund(sponsor: &T::AccountId, user: &T::AccountId, refund: BalanceOf<T>) {
+		let Some(period) = Self::sponsors(sponsor).map(|details| details.period) else {
+			return;
+		};
+		let period_start = Self::period_start(period);
+		Sponsors::<T>::mutate(sponsor, |details| {
+			if let Some(details) = details {
+				details.spending.refund(refund, &period_start);
+			}
+		});
+		Sponsorships::<T>::mutate(user, |sponsorship| {
+			if let Some(sponsorship) = sponsorship {
+				sponsorship.spending.refund(refund, &period_start);
+			}
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame::testing_prelude::*;
+	use polkadot_sdk::{
+		frame_support::{
+			dispatch::{DispatchClass, DispatchInfo, Pays, PostDispatchInfo},
+			weights::IdentityFee,
+		},
+		pallet_balances,
+		sp_runtime::traits::TransactionExtension,
+	};
+
+	type Extension = ChargeSponsoredTransactionPayment<Test>;
+
+	// Configure a mock runtime to test the pallet
+	construct_runtime!(
+		pub enum Test {
+			System: frame_system,
+			Balances: pallet_balances,
+			TransactionPayment: pallet_transaction_payment,
+			FeeSponsorship: crate,
+		}
+	);
+
+	parameter_types! {
+		// Extrinsics have no base weight, so fees are weight plus length
+		pub BlockWeights: frame_system::limits::BlockWeights =
+			frame_system::limits::BlockWeights::builder()
+				.base_block(Weight::zero())
+				.for_class(DispatchClass::all(), |weights| weights.base_extrinsic = Weight::zero())
+				.for_class(DispatchClass::non_mandatory(), |weights| {
+					weights.max_total = Some(Weight::from_parts(1_000_000, u64::MAX))
+				})
+				.build_or_panic();
+	}
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = MockBlock<Test>;
+		type AccountId = u64;
+		type AccountData = pallet_balances::AccountData<u64>;
+		type BlockWeights = BlockWeights;
+	}
+
+	#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+	impl pallet_balances::Config for Test {
+		type AccountStore = System;
+	}
+
+	#[derive_impl(pallet_transaction_payment::config_preludes::TestDefaultConfig)]
+	impl pallet_transaction_payment::Config for Test {
+		type OnChargeTransaction = pallet_transaction_payment::FungibleAdapter<Balances, ()>;
+		type WeightToFee = IdentityFee<u64>;
+		type LengthToFee = IdentityFee<u64>;
+	}
+
+	/// Only remarks are sponsored
+	pub struct Remarks;
+	impl Contains<RuntimeCall> for Remarks {
+		fn contains(call: &RuntimeCall) -> bool {
+			matches!(call, RuntimeCall::System(frame_system::Call::remark { .. }))
+		}
+	}
+
+	impl Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type SponsoredCalls = Remarks;
+	}
+
+	const SPONSOR: u64 = 1;
+	const USER: u64 = 2;
+	const OTHER: u64 = 3;
+	const NEWCOMER: u64 = 4;
+
+	// Build genesis storage according to the mock runtime
+	pub fn new_test_ext() -> TestExternalities {
+		let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(SPONSOR, 10_000), (USER, 10_000), (OTHER, 10_000)],
+			..Default::default()
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+		let mut ext: TestExternalities = storage.into();
+		// Events are only recorded after the genesis block
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn remark() -> RuntimeCall {
+		RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+	}
+
+	fn transfer() -> RuntimeCall {
+		RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death { dest: OTHER, value: 1 })
+	}
+
+	fn info(weight: u64) -> DispatchInfo {
+		DispatchInfo { call_weight: Weight::from_parts(weight, 0), ..Default::default() }
+	}
+
+	/// Run `call` signed by `who` with a dispatch weight of `weight` through the extension,
+	/// refunding `unused` of the weight after dispatch
+	fn submit(who: u64, call: RuntimeCall, weight: u64, unused: u64) -> DispatchResult {
+		let info = info(weight);
+		let post_info = PostDispatchInfo {
+			actual_weight: Some(Weight::from_parts(weight - unused, 0)),
+			pays_fee: Pays::Yes,
+		};
+		Extension::from(0)
+			.test_run(RuntimeOrigin::signed(who), &call, &info, 10, 0, |_| Ok(post_info))
+			.map_err(|_| DispatchError::Other("invalid transaction"))?
+			.map(|_| ())
+			.map_err(|error| error.error)
+	}
+
+	fn setup() {
+		assert_ok!(FeeSponsorship::set_sponsor_budget(RuntimeOrigin::signed(SPONSOR), 10, 1_000));
+		assert_ok!(FeeSponsorship::sponsor_user(RuntimeOrigin::signed(SPONSOR), USER, 500));
+	}
+
+	#[test]
+	fn test_set_sponsor_budget() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				FeeSponsorship::set_sponsor_budget(RuntimeOrigin::signed(SPONSOR), 0, 1_000),
+				Error::<Test>::ZeroPeriod
+			);
+			assert_ok!(FeeSponsorship::set_sponsor_budget(RuntimeOrigin::signed(SPONSOR), 10, 1_000));
+
+			let details = FeeSponsorship::sponsors(SPONSOR).unwrap();
+			assert_eq!(details.period, 10);
+			assert_eq!(details.budget, 1_000);
+			System::assert_last_event(
+				Event::SponsorBudgetSet { sponsor: SPONSOR, period: 10, budget: 1_000 }.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn test_sponsor_user() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				FeeSponsorship::sponsor_user(RuntimeOrigin::signed(SPONSOR), USER, 500),
+				Error::<Test>::NotSponsor
+			);
+			assert_ok!(FeeSponsorship::set_sponsor_budget(RuntimeOrigin::signed(SPONSOR), 10, 1_000));
+			assert_noop!(
+				FeeSponsorship::sponsor_user(RuntimeOrigin::signed(SPONSOR), SPONSOR, 500),
+				Error::<Test>::CannotSponsorSelf
+			);
+			assert_ok!(FeeSponsorship::sponsor_user(RuntimeOrigin::signed(SPONSOR), USER, 500));
+			System::assert_last_event(
+				Event::UserSponsored { sponsor: SPONSOR, user: USER, budget: 500 }.into(),
+			);
+
+			// Only the current sponsor can change the budget
+			assert_ok!(FeeSponsorship::set_sponsor_budget(RuntimeOrigin::signed(OTHER), 10, 1_000));
+			assert_noop!(
+				FeeSponsorship::sponsor_user(RuntimeOrigin::signed(OTHER), USER, 500),
+				Error::<Test>::AlreadySponsored
+			);
+			assert_ok!(FeeSponsorship::sponsor_user(RuntimeOrigin::signed(SPONSOR), USER, 200));
+			assert_eq!(FeeSponsorship::sponsorships(USER).unwrap().budget, 200);
+		});
+	}
+
+	#[test]
+	fn test_end_sponsorship() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_noop!(
+				FeeSponsorship::unsponsor_user(RuntimeOrigin::signed(OTHER), USER),
+				Error::<Test>::NotSponsoredByCaller
+			);
+			assert_ok!(FeeSponsorship::unsponsor_user(RuntimeOrigin::signed(SPONSOR), USER));
+			assert!(FeeSponsorship::sponsorships(USER).is_none());
+			System::assert_last_event(Event::SponsorshipEnded { sponsor: SPONSOR, user: USER }.into());
+
+			assert_noop!(
+				FeeSponsorship::leave_sponsorship(RuntimeOrigin::signed(USER)),
+				Error::<Test>::NotSponsored
+			);
+			assert_ok!(FeeSponsorship::sponsor_user(RuntimeOrigin::signed(SPONSOR), USER, 500));
+			assert_ok!(FeeSponsorship::leave_sponsorship(RuntimeOrigin::signed(USER)));
+			assert!(FeeSponsorship::sponsorships(USER).is_none());
+
+			assert_ok!(FeeSponsorship::unregister_sponsor(RuntimeOrigin::signed(SPONSOR)));
+			assert_noop!(
+				FeeSponsorship::unregister_sponsor(RuntimeOrigin::signed(SPONSOR)),
+				Error::<Test>::NotSponsor
+			);
+		});
+	}
+
+	#[test]
+	fn test_sponsor_pays_fee() {
+		new_test_ext().execute_with(|| {
+			setup();
+
+			// Weight 100 + length 10
+			assert_ok!(submit(USER, remark(), 100, 0));
+			assert_eq!(Balances::free_balance(SPONSOR), 10_000 - 110);
+			assert_eq!(Balances::free_balance(USER), 10_000);
+			assert_eq!(FeeSponsorship::sponsors(SPONSOR).unwrap().spending.spent, 110);
+			assert_eq!(FeeSponsorship::sponsorships(USER).unwrap().spending.spent, 110);
+			System::assert_has_event(
+				Event::SponsoredFeePaid { sponsor: SPONSOR, user: USER, actual_fee: 110, tip: 0 }
+					.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn test_unused_weight_is_refunded_to_budget() {
+		new_test_ext().execute_with(|| {
+			setup();
+
+			assert_ok!(submit(USER, remark(), 100, 40));
+			assert_eq!(Balances::free_balance(SPONSOR), 10_000 - 70);
+			assert_eq!(FeeSponsorship::sponsors(SPONSOR).unwrap().spending.spent, 70);
+			assert_eq!(FeeSponsorship::sponsorships(USER).unwrap().spending.spent, 70);
+		});
+	}
+
+	#[test]
+	fn test_unsponsored_calls_are_paid_by_signer() {
+		new_test_ext().execute_with(|| {
+			setup();
+
+			// Calls outside of `SponsoredCalls` (`submit` only charges fees, it does not
+			// dispatch the transfer)
+			assert_ok!(submit(USER, transfer(), 100, 0));
+			assert_eq!(Balances::free_balance(SPONSOR), 10_000);
+			assert_eq!(Balances::free_balance(USER), 10_000 - 110);
+
+			// Accounts without a sponsor
+			assert_ok!(submit(OTHER, remark(), 100, 0));
+			assert_eq!(Balances::free_balance(SPONSOR), 10_000);
+			assert_eq!(Balances::free_balance(OTHER), 10_000 - 110);
+		});
+	}
+
+	#[test]
+	fn test_budgets_are_enforced_per_period() {
+		new_test_ext().execute_with(|| {
+			setup();
+
+			// The user's budget of 500 covers four fees of 110
+			for _ in 0..4 {
+				assert_ok!(submit(USER, remark(), 100, 0));
+			}
+			assert_eq!(Balances::free_balance(SPONSOR), 10_000 - 440);
+			assert_eq!(FeeSponsorship::sponsor_for(&USER, 110), None);
+
+			// The fifth one is paid by the user
+			assert_ok!(submit(USER, remark(), 100, 0));
+			assert_eq!(Balances::free_balance(SPONSOR), 10_000 - 440);
+			assert_eq!(Balances::free_balance(USER), 10_000 - 110);
+
+			// Budgets are replenished in the next period, starting at block 10
+			System::set_block_number(10);
+			assert_eq!(FeeSponsorship::sponsor_for(&USER, 110), Some(SPONSOR));
+			assert_ok!(submit(USER, remark(), 100, 0));
+			assert_eq!(Balances::free_balance(SPONSOR), 10_000 - 550);
+			assert_eq!(FeeSponsorship::sponsorships(USER).unwrap().spending.spent, 110);
+		});
+	}
+
+	#[test]
+	fn test_sponsor_budget_is_shared_by_users() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(FeeSponsorship::set_sponsor_budget(RuntimeOrigin::signed(SPONSOR), 10, 200));
+			assert_ok!(FeeSponsorship::sponsor_user(RuntimeOrigin::signed(SPONSOR), USER, 500));
+			assert_ok!(FeeSponsorship::sponsor_user(RuntimeOrigin::signed(SPONSOR), OTHER, 500));
+
+			assert_ok!(submit(USER, remark(), 100, 0));
+			assert_eq!(FeeSponsorship::sponsor_for(&OTHER, 110), None);
+			assert_ok!(submit(OTHER, remark(), 100, 0));
+			assert_eq!(Balances::free_balance(SPONSOR), 10_000 - 110);
+			assert_eq!(Balances::free_balance(OTHER), 10_000 - 110);
+		});
+	}
+
+	#[test]
+	fn test_unregistered_sponsor_pays_nothing() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_ok!(FeeSponsorship::unregister_sponsor(RuntimeOrigin::signed(SPONSOR)));
+
+			assert_ok!(submit(USER, remark(), 100, 0));
+			assert_eq!(Balances::free_balance(SPONSOR), 10_000);
+			assert_eq!(Balances::free_balance(USER), 10_000 - 110);
+		});
+	}
+
+	#[test]
+	fn test_unfunded_user_can_transact() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(FeeSponsorship::set_sponsor_budget(RuntimeOrigin::signed(SPONSOR), 10, 1_000));
+			let extensions = (frame_system::CheckNonce::<Test>::from(0), Extension::from(0));
+			let post_info = PostDispatchInfo { actual_weight: None, pays_fee: Pays::Yes };
+			let run = |extensions: (frame_system::CheckNonce<Test>, Extension)| {
+				extensions.test_run(RuntimeOrigin::signed(NEWCOMER), &remark(), &info(100), 10, 0, |_| {
+					Ok(post_info)
+				})
+			};
+
+			// Accounts without balance do not pass the nonce check on their own
+			assert!(run(extensions.clone()).is_err());
+
+			// Sponsored, they exist and the sponsor pays their fees
+			assert_ok!(FeeSponsorship::sponsor_user(RuntimeOrigin::signed(SPONSOR), NEWCOMER, 500));
+			assert_eq!(System::account(NEWCOMER).sufficients, 1);
+			assert_ok!(run(extensions).unwrap());
+			assert_eq!(System::account_nonce(NEWCOMER), 1);
+			assert_eq!(Balances::free_balance(SPONSOR), 10_000 - 110);
+			assert_eq!(Balances::free_balance(NEWCOMER), 0);
+
+			// Ending the sponsorship releases the reference
+			assert_ok!(FeeSponsorship::leave_sponsorship(RuntimeOrigin::signed(NEWCOMER)));
+			assert_eq!(System::account(NEWCOMER).sufficients, 0);
+		});
+	}
+
+	#[test]
+	fn test_encoding_matches_charge_transaction_payment() {
+		let sponsored = Extension::from(42);
+		let plain = pallet_transaction_payment::ChargeTransactionPayment::<Test>::from(42);
+		assert_eq!(sponsored.encode(), plain.encode());
+		assert_eq!(
+			<Extension as TransactionExtension<RuntimeCall>>::IDENTIFIER,
+			"ChargeTransactionPayment"
+		);
+	}
+}
@@ -0,0 +1,318 @@
+//! # Credential metadata inspection
+//!
+//! Credentials store free-form metadata, but the frontend writes it in one of three shapes:
+//!
+//! - Full JSON: `{"credential_type":"skill","name":"Rust","issuer":"Acme",...}`
+//! - Compact JSON with short keys: `{"t":"skil","n":"Rust","i":"Acme","v":1,...}`
+//! - Pipe-separated text: `type|name|issuer|timestamp|visibility`
+//!
+//! This module extracts the well-known fields from any of them so events and indexes can
+//! carry them. Unknown or malformed metadata is still accepted by the pallet; it simply
+//! yields default values here.
+
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+use codec::DecodeWithMemTracking;
+use frame::prelude::*;
+use pallet_skills::SkillId;
+use polkadot_sdk::polkadot_sdk_frame as frame;
+use serde_json::{Map, Number, Value};
+
+/// Maximum length of the issuer name carried in events
+pub const MAX_ISSUER_LEN: u32 = 128;
+
+/// Maximum length of the credential name extracted from metadata
+pub const MAX_NAME_LEN: u32 = 128;
+
+/// Issuer name as carried in events, truncated to [`MAX_ISSUER_LEN`] bytes
+pub type IssuerName = BoundedVec<u8, ConstU32<MAX_ISSUER_LEN>>;
+
+/// Credential name, truncated to [`MAX_NAME_LEN`] bytes
+pub type CredentialName = BoundedVec<u8, ConstU32<MAX_NAME_LEN>>;
+
+/// Metadata fields reported as changed by `CredentialUpdated`
+pub type ChangedFields = BoundedVec<CredentialField, ConstU32<10>>;
+
+/// Kind of credential, as declared in its metadata
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum CredentialType {
+	/// A skill the freelancer claims or had endorsed
+	Skill,
+	/// A client review
+	Review,
+	/// A record of a completed payment
+	Payment,
+	/// A certification from an external body
+	Certification,
+	/// Missing or unrecognised type
+	Other,
+}
+
+/// Whether a credential is shown on public portfolios
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum Visibility {
+	/// Shown on public portfolios (the default when unspecified)
+	Public,
+	/// Hidden from public portfolios
+	Private,
+}
+
+/// Well-known metadata field
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum CredentialField {
+	/// `credential_type` / `type` / `t`
+	CredentialType,
+	/// `name` / `n`
+	Name,
+	/// `description` / `d`
+	Description,
+	/// `issuer` / `i`
+	Issuer,
+	/// `timestamp` / `ts`
+	Timestamp,
+	/// `visibility` / `v`
+	Visibility,
+	/// `rating` / `r`
+	Rating,
+	/// `proof_hash` / `p`
+	ProofHash,
+	/// Any field not listed above
+	Other,
+	/// `skill_id` / `sk`
+	SkillId,
+}
+
+impl CredentialField {
+	/// Every field with a known key, in the order they are reported
+	const KNOWN: [CredentialField; 9] = [
+		CredentialField::CredentialType,
+		CredentialField::Name,
+		CredentialField::Description,
+		CredentialField::Issuer,
+		CredentialField::Timestamp,
+		CredentialField::Visibility,
+		CredentialField::Rating,
+		CredentialField::ProofHash,
+		CredentialField::SkillId,
+	];
+
+	/// JSON keys the frontend uses for this field, full form first
+	fn keys(&self) -> &'static [&'static str] {
+		match self {
+			CredentialField::CredentialType => &["credential_type", "type", "t"],
+			CredentialField::Name => &["name", "n"],
+			CredentialField::Description => &["description", "d"],
+			CredentialField::Issuer => &["issuer", "i"],
+			CredentialField::Timestamp => &["timestamp", "ts"],
+			CredentialField::Visibility => &["visibility", "v"],
+			CredentialField::Rating => &["rating", "r"],
+			CredentialField::ProofHash => &["proof_hash", "p"],
+			CredentialField::Other => &[],
+			CredentialField::SkillId => &["skill_id", "sk"],
+		}
+	}
+
+	/// Whether `key` belongs to one of the known fields
+	fn is_known_key(key: &str) -> bool {
+		Self::KNOWN.iter().any(|field| field.keys().contains(&key))
+	}
+}
+
+/// Metadata decoded into a JSON object
+pub struct ParsedMetadata {
+	fields: Map<String, Value>,
+}
+
+impl ParsedMetadata {
+	/// Parse raw metadata bytes, falling back to an empty object when the format is unknown
+	pub fn parse(raw: &[u8]) -> Self {
+		let fields = match serde_json::from_slice::<Value>(raw) {
+			Ok(Value::Object(fields)) => fields,
+			_ => Self::parse_pipe_format(raw).unwrap_or_default(),
+		};
+		Self { fields }
+	}
+
+	/// Parse the `type|name|issuer|timestamp|visibility` text format
+	fn parse_pipe_format(raw: &[u8]) -> Option<Map<String, Value>> {
+		let text = core::str::from_utf8(raw).ok()?;
+		let parts: Vec<&str> = text.split('|').collect();
+		if parts.len() < 5 {
+			return None;
+		}
+
+		let mut fields = Map::new();
+		fields.insert("credential_type".into(), Value::String(parts[0].into()));
+		fields.insert("name".into(), Value::String(parts[1].into()));
+		fields.insert("issuer".into(), Value::String(parts[2].into()));
+		fields.insert("timestamp".into(), Value::String(parts[3].into()));
+		let visibility = if parts[4] == "0" { "private" } else { "public" };
+		fields.insert("visibility".into(), Value::String(visibility.into()));
+		Some(fields)
+	}
+
+	/// Raw JSON value of a well-known field
+	pub fn field(&self, field: CredentialField) -> Option<&Value> {
+		field.keys().iter().find_map(|key| self.fields.get(*key))
+	}
+
+	/// String value of a well-known field
+	fn str_field(&self, field: CredentialField) -> Option<&str> {
+		self.field(field).and_then(Value::as_str)
+	}
+
+	/// Declared credential type
+	pub fn credential_type(&self) -> CredentialType {
+		match self.str_field(CredentialField::CredentialType).map(|t| t.to_ascii_lowercase()) {
+			Some(t) => match t.as_str() {
+				"skill" | "skil" | "s" => CredentialType::Skill,
+				"review" | "revi" | "r" => CredentialType::Review,
+				"payment" | "paym" | "p" => CredentialType::Payment,
+				"certification" | "cert" | "c" => CredentialType::Certification,
+				_ => CredentialType::Other,
+			},
+			None => CredentialType::Other,
+		}
+	}
+
+	/// Declared visibility, defaulting to public like the frontend does
+	pub fn visibility(&self) -> Visibility {
+		match self.field(CredentialField::Visibility) {
+			Some(Value::String(v)) if v.eq_ignore_ascii_case("private") => Visibility::Private,
+			Some(Value::Number(v)) if v.as_u64() == Some(0) => Visibility::Private,
+			Some(Value::Bool(false)) => Visibility::Private,
+			_ => Visibility::Public,
+		}
+	}
+
+	/// Issuer name, truncated to [`MAX_ISSUER_LEN`] bytes
+	pub fn issuer(&self) -> IssuerName {
+		let issuer = self.str_field(CredentialField::Issuer).unwrap_or_default();
+		BoundedVec::truncate_from(issuer.as_bytes().to_vec())
+	}
+
+	/// Credential name, truncated to [`MAX_NAME_LEN`] bytes
+	pub fn name(&self) -> CredentialName {
+		let name = self.str_field(CredentialField::Name).unwrap_or_default();
+		BoundedVec::truncate_from(name.as_bytes().to_vec())
+	}
+
+	/// Canonical skill ID the credential declares, if any
+	pub fn skill_id(&self) -> Option<SkillId> {
+		self.field(CredentialField::SkillId)
+			.and_then(Value::as_u64)
+			.and_then(|id| SkillId::try_from(id).ok())
+	}
+
+	/// Review rating in tenths of a star (1 to 50), if rated
+	///
+	/// The full `rating` key holds stars (e.g. `4.5`), while the compact `r` key holds
+	/// tenths of a star (e.g. `45`).
+	pub fn rating_tenths(&self) -> Option<u32> {
+		let tenths = match (self.fields.get("rating"), self.fields.get("r")) {
+			(Some(Value::Number(stars)), _) => stars_to_tenths(stars)?,
+			(Some(_), _) => return None,
+			(None, Some(tenths)) => u32::try_from(tenths.as_u64()?).ok()?,
+			(None, None) => return None,
+		};
+		(tenths > 0).then_some(tenths.min(50))
+	}
+
+	/// Fields whose values differ between `self` and `other`
+	///
+	/// Every differing field outside the well-known set is reported once as
+	/// [`CredentialField::Other`].
+	pub fn changed_fields(&self, other: &ParsedMetadata) -> ChangedFields {
+		let mut changed: Vec<CredentialField> = CredentialField::KNOWN
+			.iter()
+			.copied()
+			.filter(|field| self.field(*field) != other.field(*field))
+			.collect();
+
+		let other_changed = self
+			.fields
+			.iter()
+			.chain(other.fields.iter())
+			.filter(|(key, _)| !CredentialField::is_known_key(key))
+			.any(|(key, _)| self.fields.get(key) != other.fields.get(key));
+		if other_changed {
+			changed.push(CredentialField::Other);
+		}
+
+		BoundedVec::truncate_from(changed)
+	}
+}
+
+/// A number of stars in tenths of a star, rounded to the nearest tenth
+///
+/// Works on the shortest decimal form of the number (e.g. `4.45` or `1e-7`) with integer
+/// arithmetic only, so every node rounds alike. Negative ratings saturate to zero.
+fn stars_to_tenths(stars: &Number) -> Option<u32> {
+	if let Some(stars) = stars.as_u64() {
+		return Some(u32::try_from(stars.saturating_mul(10)).unwrap_or(u32::MAX));
+	}
+	let decimal = stars.to_string();
+	if decimal.starts_with('-') {
+		return Some(0);
+	}
+
+	let (mantissa, exponent) = decimal.split_once(['e', 'E']).unwrap_or((&decimal, "0"));
+	let exponent: i32 = exponent.parse().ok()?;
+	let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+	// Number of digits down to and including the tenths digit
+	let tenths_digits = (whole.len() as i32).saturating_add(exponent).saturating_add(1);
+
+	let mut tenths: u32 = 0;
+	let mut round_up = false;
+	let mut digits = 0;
+	for (index, digit) in whole.chars().chain(fraction.chars()).enumerate() {
+		let digit = digit.to_digit(10)?;
+		let position = index as i32;
+		if position < tenths_digits {
+			tenths = tenths.saturating_mul(10).saturating_add(digit);
+		} else if position == tenths_digits {
+			round_up = digit >= 5;
+		}
+		digits = position + 1;
+	}
+	// Zeros implied by a positive exponent
+	let padding = u32::try_from(tenths_digits.saturating_sub(digits)).unwrap_or(0);
+	tenths = tenths.saturating_mul(10u32.saturating_pow(padding));
+
+	Some(tenths.saturating_add(round_up as u32))
+}
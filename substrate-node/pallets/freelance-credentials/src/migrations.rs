@@ -0,0 +1,210 @@
+//! # Storage migrations for the credentials pallet
+//!
+//! Every change to the layout of `Credentials` or `OwnerCredentials` must bump the
+//! pallet's `STORAGE_VERSION` and ship a migration here.
+//!
+//! ## Versions
+//!
+//! - **v0**: `Credentials` stored `(owner, metadata)` tuples
+//! - **v1**: `Credentials` stores [`CredentialInfo`] records with the mint block
+//!
+//! Each version offers two migration paths:
+//!
+//! - A single-block [`VersionedMigration`] for runtimes with little live data
+//! - A multi-block [`SteppedMigration`] for `pallet-migrations`, for states too large
+//!   to translate within one block
+
+use crate::{CredentialInfo, Config, Credentials, Pallet};
+use frame::prelude::*;
+use polkadot_sdk::{
+	frame_support::{
+		migrations::{MigrationId, SteppedMigration, SteppedMigrationError, VersionedMigration},
+		storage_alias,
+		traits::{GetStorageVersion, UncheckedOnRuntimeUpgrade},
+		weights::WeightMeter,
+	},
+	polkadot_sdk_frame as frame,
+	sp_runtime::traits::Zero,
+};
+
+#[cfg(feature = "try-runtime")]
+use alloc::vec::Vec;
+#[cfg(feature = "try-runtime")]
+use polkadot_sdk::sp_runtime::TryRuntimeError;
+
+/// Identifier prefix of this pallet's multi-block migrations
+const PALLET_MIGRATIONS_ID: &[u8; 28] = b"pallet-freelance-credentials";
+
+/// Storage layout before versioning was introduced
+pub mod v0 {
+	use super::*;
+
+	/// `Credentials` as a map of credential_id -> (owner_account, metadata_json)
+	#[storage_alias]
+	pub type Credentials<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as polkadot_sdk::frame_system::Config>::Hash,
+		(
+			<T as polkadot_sdk::frame_system::Config>::AccountId,
+			BoundedVec<u8, ConstU32<4096>>,
+		),
+		OptionQuery,
+	>;
+}
+
+/// Migration from the v0 tuple layout to [`CredentialInfo`] records
+pub mod v1 {
+	use super::*;
+
+	/// Translate a single v0 entry into the v1 format
+	///
+	/// The mint block of legacy credentials is unknown and recorded as zero.
+	fn translate<T: Config>(
+		(owner, metadata): (T::AccountId, BoundedVec<u8, ConstU32<4096>>),
+	) -> crate::CredentialInfoOf<T> {
+		CredentialInfo { owner, metadata, minted_at: Zero::zero() }
+	}
+
+	/// Unversioned single-block migration, wrapped by [`MigrateV0ToV1`]
+	pub struct InnerMigrateV0ToV1<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+			Credentials::<T>::translate::<(T::AccountId, BoundedVec<u8, ConstU32<4096>>), _>(
+				|_credential_id, old| {
+					translated += 1;
+					Some(translate::<T>(old))
+				},
+			);
+
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			check_v0::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			check_v1::<T>(state)
+		}
+	}
+
+	/// Count the v0 entries, checking that every one of them decodes
+	#[cfg(feature = "try-runtime")]
+	fn check_v0<T: Config>() -> Result<Vec<u8>, TryRuntimeError> {
+		// Every v0 entry must decode, otherwise `translate` would silently drop it
+		let count = v0::Credentials::<T>::iter_keys().count() as u64;
+		let decodable = v0::Credentials::<T>::iter().count() as u64;
+		ensure!(count == decodable, "Undecodable v0 credential entries found");
+
+		Ok(count.encode())
+	}
+
+	/// Check that the migration kept every credential, and owners consistent
+	#[cfg(feature = "try-runtime")]
+	fn check_v1<T: Config>(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+		let expected = u64::decode(&mut &state[..])
+			.map_err(|_| "Failed to decode pre-upgrade credential count")?;
+		ensure!(
+			Credentials::<T>::iter().count() as u64 == expected,
+			"Credential count changed during migration"
+		);
+		ensure!(
+			Pallet::<T>::on_chain_storage_version() >= 1,
+			"Storage version was not bumped by the migration"
+		);
+
+		// Owner indexes must still point at credentials owned by that account
+		for (owner, credential_ids) in crate::OwnerCredentials::<T>::iter() {
+			for credential_id in credential_ids.iter() {
+				let credential = Credentials::<T>::get(credential_id)
+					.ok_or("Owner index references a missing credential")?;
+				ensure!(credential.owner == owner, "Credential owner does not match owner index");
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Single-block migration from storage version 0 to 1
+	pub type MigrateV0ToV1<T> = VersionedMigration<
+		0,
+		1,
+		InnerMigrateV0ToV1<T>,
+		Pallet<T>,
+		<T as polkadot_sdk::frame_system::Config>::DbWeight,
+	>;
+
+	/// Multi-block migration from storage version 0 to 1, driven by `pallet-migrations`
+	///
+	/// Translates entries one at a time in key order. The cursor is the last migrated
+	/// credential ID; the storage version is bumped once the last entry is done.
+	pub struct LazyMigrationV0ToV1<T>(PhantomData<T>);
+
+	impl<T: Config> SteppedMigration for LazyMigrationV0ToV1<T> {
+		type Cursor = T::Hash;
+		type Identifier = MigrationId<28>;
+
+		fn id() -> Self::Identifier {
+			MigrationId { pallet_id: *PALLET_MIGRATIONS_ID, version_from: 0, version_to: 1 }
+		}
+
+		fn step(
+			mut cursor: Option<Self::Cursor>,
+			meter: &mut WeightMeter,
+		) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+			// Nothing to do on chains that already use the v1 layout
+			if cursor.is_none() && Pallet::<T>::on_chain_storage_version() >= 1 {
+				return Ok(None);
+			}
+
+			// One read of the old entry and one write of the new one
+			let required = T::DbWeight::get().reads_writes(1, 1);
+			if meter.remaining().any_lt(required) {
+				return Err(SteppedMigrationError::InsufficientWeight { required });
+			}
+
+			loop {
+				if meter.try_consume(required).is_err() {
+					break;
+				}
+
+				let mut iter = if let Some(last_key) = cursor {
+					v0::Credentials::<T>::iter_from(v0::Credentials::<T>::hashed_key_for(last_key))
+				} else {
+					v0::Credentials::<T>::iter()
+				};
+
+				if let Some((credential_id, old)) = iter.next() {
+					Credentials::<T>::insert(credential_id, translate::<T>(old));
+					cursor = Some(credential_id);
+				} else {
+					// All entries translated
+					StorageVersion::new(1).put::<Pallet<T>>();
+					cursor = None;
+					break;
+				}
+			}
+
+			Ok(cursor)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			// Chains already on v1 are left untouched
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				return Ok((Credentials::<T>::iter_keys().count() as u64).encode());
+			}
+			check_v0::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			check_v1::<T>(state)
+		}
+	}
+}
@@ -0,0 +1,113 @@
+//! # Offchain credential index
+//!
+//! `OwnerCredentials` answers "what does this account hold", but not "who holds a Rust
+//! skill credential". Answering that on-chain would mean scanning every credential, so
+//! instead the pallet writes a normalized record of every change into the node's offchain
+//! database through `sp_io::offchain_index`, and node-side RPCs search those records.
+//!
+//! ## Layout
+//!
+//! The offchain database is a plain key-value store without iteration, so records are
+//! written as an append-only journal whose entries are chained by hash:
+//!
+//! - `freelance-credentials::index::<link>` holds the [`IndexEntry`] following the entry
+//!   whose link is `link` (SCALE-encoded hash). The first entry follows the zero hash, and
+//!   the link of every entry is [`next_link`] of the previous link and the entry
+//! - the on-chain `OffchainIndexHead` value holds the number of entries written so far, and
+//!   `OffchainIndexTip` the link of the last one
+//!
+//! Readers follow the links from the zero hash, replaying `OffchainIndexHead` entries in
+//! order to rebuild the current set of credentials. Offchain indexing is only active on
+//! nodes started with `--enable-offchain-indexing true`; other nodes simply never see the
+//! journal.
+//!
+//! Offchain writes are not fork-aware, but links are: every block imported writes its
+//! entries, and since two forks diverging at some entry give it different links, a retracted
+//! fork never overwrites the journal of the canonical chain. A reader that followed a
+//! retracted fork ends on a link other than the best block's `OffchainIndexTip`, and replays
+//! the journal from the start.
+//!
+//! Offchain writes made while building the genesis state are dropped, so credentials minted
+//! at genesis are journaled by the first block instead.
+
+use crate::metadata::{CredentialName, CredentialType, IssuerName, ParsedMetadata, Visibility};
+use alloc::vec::Vec;
+use frame::prelude::*;
+use polkadot_sdk::polkadot_sdk_frame as frame;
+
+/// Prefix of every journal key in the offchain database
+pub const INDEX_KEY_PREFIX: &[u8] = b"freelance-credentials::index::";
+
+/// Offchain database key of the journal entry following the entry whose link is `link`
+pub fn entry_key<Hash: Encode>(link: &Hash) -> Vec<u8> {
+	let mut key = INDEX_KEY_PREFIX.to_vec();
+	key.extend_from_slice(&link.encode());
+	key
+}
+
+/// Link of `entry`, following the entry whose link is `link`
+pub fn next_link<H: Hash, AccountId: Encode>(
+	link: &H::Output,
+	entry: &IndexEntry<AccountId, H::Output>,
+) -> H::Output {
+	H::hash_of(&(link, entry))
+}
+
+/// Normalize a search term: trimmed and ASCII-lowercased
+///
+/// Applied to names and issuers before they are indexed, and should be applied to
+/// queries before they are compared.
+pub fn normalize(value: &[u8]) -> Vec<u8> {
+	value.trim_ascii().to_ascii_lowercase()
+}
+
+/// Searchable summary of a credential
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct IndexedCredential<AccountId, Hash> {
+	/// ID of the credential
+	pub credential_id: Hash,
+	/// Current owner
+	pub owner: AccountId,
+	/// Declared credential type
+	pub credential_type: CredentialType,
+	/// Normalized credential name (the skill name for skill credentials)
+	pub name: CredentialName,
+	/// Normalized issuer name
+	pub issuer: IssuerName,
+	/// Declared visibility
+	pub visibility: Visibility,
+}
+
+impl<AccountId, Hash> IndexedCredential<AccountId, Hash> {
+	/// Summarise raw credential metadata
+	pub fn new(credential_id: Hash, owner: AccountId, metadata: &[u8]) -> Self {
+		let parsed = ParsedMetadata::parse(metadata);
+		Self {
+			credential_id,
+			owner,
+			credential_type: parsed.credential_type(),
+			name: BoundedVec::truncate_from(normalize(&parsed.name())),
+			issuer: BoundedVec::truncate_from(normalize(&parsed.issuer())),
+			visibility: parsed.visibility(),
+		}
+	}
+}
+
+/// A single journal entry
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum IndexEntry<AccountId, Hash> {
+	/// A credential was minted, updated or moved to a new owner
+	Upsert(IndexedCredential<AccountId, Hash>),
+	/// A credential was deleted
+	Remove(Hash),
+}
+
+impl<AccountId, Hash> IndexEntry<AccountId, Hash> {
+	/// ID of the credential this entry is about
+	pub fn credential_id(&self) -> &Hash {
+		match self {
+			IndexEntry::Upsert(record) => &record.credential_id,
+			IndexEntry::Remove(credential_id) => credential_id,
+		}
+	}
+}
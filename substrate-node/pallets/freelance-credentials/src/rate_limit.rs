@@ -0,0 +1,108 @@
+//! Transaction extension rejecting rate limited mints.
+//!
+//! [`Pallet::mint_credential`] fails with [`Error::RateLimited`] once an account exceeds its
+//! mint rate limits, but a failed extrinsic still takes block space. [`CheckMintRateLimit`]
+//! runs the same check when transactions are validated, so excess mints are turned away by
+//! the transaction pool instead.
+//!
+//! The pool check is best effort. It only sees `mint_credential` called directly by its
+//! signer: mints wrapped in other calls (e.g. proxy, multisig or organization dispatches) are
+//! not inspected. It also checks against the mints already on chain, so a burst of mints
+//! with consecutive nonces all pass validation together. Such mints are still stopped at
+//! dispatch, where the check in [`Pallet::mint_credential`] is the actual limit; they only
+//! take block space and pay fees.
+
+use crate::{Call, Config, Pallet};
+use codec::DecodeWithMemTracking;
+use core::{fmt, marker::PhantomData};
+use frame::prelude::*;
+use polkadot_sdk::{
+	frame_support::{
+		dispatch::{DispatchInfo, PostDispatchInfo},
+		traits::IsSubType,
+	},
+	polkadot_sdk_frame as frame,
+	sp_runtime::{
+		traits::{
+			AsSystemOriginSigner, DispatchInfoOf, Dispatchable, Implication, TransactionExtension,
+			ValidateResult,
+		},
+		transaction_validity::{
+			InvalidTransaction, TransactionSource, TransactionValidityError, ValidTransaction,
+		},
+	},
+};
+
+/// `InvalidTransaction::Custom` code of mints exceeding a rate limit
+pub const RATE_LIMITED: u8 = 1;
+
+/// Rejects `mint_credential` transactions of accounts that exceeded a mint rate limit
+#[derive(Encode, Decode, DecodeWithMemTracking, CloneNoBound, EqNoBound, PartialEqNoBound, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckMintRateLimit<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckMintRateLimit<T> {
+	/// Create a new instance
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> Default for CheckMintRateLimit<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> fmt::Debug for CheckMintRateLimit<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "CheckMintRateLimit")
+	}
+}
+
+impl<T: Config + Send + Sync> TransactionExtension<T::RuntimeCall> for CheckMintRateLimit<T>
+where
+	T::RuntimeCall:
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + IsSubType<Call<T>>,
+	<T::RuntimeCall as Dispatchable>::RuntimeOrigin: AsSystemOriginSigner<T::AccountId> + Clone,
+{
+	const IDENTIFIER: &'static str = "CheckMintRateLimit";
+	type Implicit = ();
+	type Val = ();
+	type Pre = ();
+
+	fn weight(&self, _call: &T::RuntimeCall) -> Weight {
+		// Reading the recent mints of the signer
+		T::DbWeight::get().reads(1)
+	}
+
+	fn validate(
+		&self,
+		origin: <T::RuntimeCall as Dispatchable>::RuntimeOrigin,
+		call: &T::RuntimeCall,
+		_info: &DispatchInfoOf<T::RuntimeCall>,
+		_len: usize,
+		_self_implicit: (),
+		_inherited_implication: &impl Implication,
+		_source: TransactionSource,
+	) -> ValidateResult<Self::Val, T::RuntimeCall> {
+		if let (Some(who), Some(Call::mint_credential { .. })) =
+			(origin.as_system_origin_signer(), call.is_sub_type())
+		{
+			Pallet::<T>::ensure_mint_allowed(who)
+				.map_err(|_| InvalidTransaction::Custom(RATE_LIMITED))?;
+		}
+		Ok((ValidTransaction::default(), (), origin))
+	}
+
+	fn prepare(
+		self,
+		_val: Self::Val,
+		_origin: &<T::RuntimeCall as Dispatchable>::RuntimeOrigin,
+		_call: &T::RuntimeCall,
+		_info: &DispatchInfoOf<T::RuntimeCall>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		Ok(())
+	}
+}
@@ -0,0 +1,30 @@
+//! Runtime API finding freelancers by canonical skill.
+
+use crate::{SkillId, SkillMatch};
+use alloc::vec::Vec;
+use codec::Codec;
+use polkadot_sdk::polkadot_sdk_frame as frame;
+
+frame::deps::sp_api::decl_runtime_apis! {
+	/// Search of the accounts holding public credentials of canonical skills
+	pub trait SkillSearchApi<AccountId>
+	where
+		AccountId: Codec,
+	{
+		/// Accounts holding at least `min_credentials` public skill credentials or
+		/// certifications of every skill in `skills`, best trust score first
+		///
+		/// Results are paginated: `page` starts at zero and `page_size` is capped at
+		/// [`crate::MAX_SEARCH_PAGE_SIZE`].
+		///
+		/// To bound the work of a call, only the first [`crate::MAX_SCANNED_SKILL_HOLDERS`]
+		/// holders of the first skill in `skills`, in storage order, are considered. Searches
+		/// for popular skills may therefore miss matches: list the rarest skill first.
+		fn freelancers_with_skills(
+			skills: Vec<SkillId>,
+			min_credentials: u32,
+			page: u32,
+			page_size: u32,
+		) -> Vec<SkillMatch<AccountId>>;
+	}
+}
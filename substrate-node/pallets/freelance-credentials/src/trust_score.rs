@@ -0,0 +1,88 @@
+//! # On-chain trust score
+//!
+//! The frontend rates freelancers with a trust score out of 100 points, made of a review
+//! (60), a skill (30) and a payment (10) component. [`TrustScore`] computes the same
+//! components from credential metadata, in hundredths of a point and without floating
+//! point arithmetic, so the runtime can rank accounts.
+//!
+//! Metadata carries no reliable payment amounts or dates, so every payment counts as the
+//! frontend's default volume of $100 and recency is ignored.
+//!
+//! Credentials backed by bonded stake count several times, as weighted by
+//! [`crate::CredentialStakes`].
+
+use crate::metadata::{CredentialType, ParsedMetadata};
+use codec::DecodeWithMemTracking;
+use frame::prelude::*;
+use polkadot_sdk::polkadot_sdk_frame as frame;
+
+/// Trust score components, in hundredths of a point
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	Default,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct TrustScore {
+	/// Average review rating out of 5 stars, weighted to at most 60 points
+	pub review: u32,
+	/// 5 points per skill and 10 per certification, capped at 100 and weighted to at most
+	/// 30 points
+	pub skill: u32,
+	/// 1 point per payment, capped at 100 and weighted to at most 10 points
+	pub payment: u32,
+}
+
+impl TrustScore {
+	/// Score of an account holding `credentials`
+	pub fn of<'a>(credentials: impl IntoIterator<Item = &'a ParsedMetadata>) -> Self {
+		Self::of_weighted(credentials.into_iter().map(|credential| (credential, 1)))
+	}
+
+	/// Score of an account holding `credentials`, each counting as `weight` credentials
+	///
+	/// Reviews are averaged by weight, so a review of weight 3 weighs as much as three
+	/// identical reviews.
+	pub fn of_weighted<'a>(
+		credentials: impl IntoIterator<Item = (&'a ParsedMetadata, u32)>,
+	) -> Self {
+		let (mut ratings, mut rated, mut skills, mut certifications, mut payments) =
+			(0u32, 0u32, 0u32, 0u32, 0u32);
+		for (credential, weight) in credentials {
+			match credential.credential_type() {
+				CredentialType::Review =>
+					if let Some(tenths) = credential.rating_tenths() {
+						ratings = ratings.saturating_add(tenths.saturating_mul(weight));
+						rated = rated.saturating_add(weight);
+					},
+				CredentialType::Skill => skills = skills.saturating_add(weight),
+				CredentialType::Certification =>
+					certifications = certifications.saturating_add(weight),
+				CredentialType::Payment => payments = payments.saturating_add(weight),
+				CredentialType::Other => {},
+			}
+		}
+
+		// (rating / 5) × 100 × 0.6 points, with ratings in tenths of a star
+		let review = ratings.saturating_mul(120).checked_div(rated).unwrap_or(0);
+		let skill_points = skills.saturating_mul(5).saturating_add(certifications.saturating_mul(10));
+		Self { review, skill: skill_points.min(100) * 30, payment: payments.min(100) * 10 }
+	}
+
+	/// Sum of the components, out of 10 000
+	pub fn total(&self) -> u32 {
+		self.review + self.skill + self.payment
+	}
+
+	/// Ranking key: higher scores first, ties broken by review, then skill component
+	pub fn rank_key(&self) -> (u32, u32, u32, u32) {
+		(self.total(), self.review, self.skill, self.payment)
+	}
+}
@@ -0,0 +1,891 @@
+//! # FreelanceForge Marketplace Pallet
+//!
+//! A job marketplace putting on-chain reputation to use: clients post jobs, freelancers
+//! apply, and only freelancers whose credentials meet the job's requirements may apply.
+//!
+//! ## Job lifecycle
+//!
+//! 1. A client posts a job with a budget, an application deadline, required canonical skills
+//!    and minimum [`Requirements`]. The budget is held from the client's balance under
+//!    [`HoldReason::JobBudget`], so no other pallet can slash or release it.
+//! 2. Until the deadline, freelancers apply. Their public credentials, as reported by
+//!    [`Config::Credentials`], are checked against the requirements at application time.
+//! 3. The client accepts an applicant, and the job moves into escrow: the budget stays
+//!    held until the work is settled.
+//! 4. The client releases the escrow to the freelancer, the freelancer refunds it to the
+//!    client, or [`Config::ArbitrationOrigin`] settles it either way.
+//!
+//! Open jobs can be cancelled by their client, which returns the budget.
+//!
+//! ## Storage
+//!
+//! - `Jobs`: Maps job IDs to [`JobDetails`] records (client, title, budget, deadline,
+//!   required skills, requirements, applications, status)
+//! - `Applications`: Maps (job ID, applicant) pairs to the block of the application
+//! - `NextJobId`: ID given to the next job
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::DecodeWithMemTracking;
+use frame::prelude::*;
+use pallet_freelance_credentials::{CredentialHoldings, SkillId};
+use polkadot_sdk::{
+	frame_support::traits::{
+		fungible::{Inspect, MutateHold},
+		tokens::{Fortitude, Precision, Restriction},
+	},
+	polkadot_sdk_frame as frame,
+};
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+// Re-export all pallet parts for runtime integration
+pub use pallet::*;
+
+/// Identifier of a job
+pub type JobId = u32;
+
+/// Title of a job (max 256 bytes)
+pub type JobTitle = BoundedVec<u8, ConstU32<256>>;
+
+/// Maximum number of canonical skills a job can require
+pub const MAX_REQUIRED_SKILLS: u32 = 8;
+
+/// Canonical skills required by a job
+pub type RequiredSkills = BoundedVec<SkillId, ConstU32<MAX_REQUIRED_SKILLS>>;
+
+/// Balance of the currency escrowed by the pallet
+pub type BalanceOf<T> = <<T as Config>::Currency as Inspect<
+	<T as polkadot_sdk::frame_system::Config>::AccountId,
+>>::Balance;
+
+/// Minimum public credentials applicants must hold
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	Default,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct Requirements {
+	/// Credentials (skill credentials or certifications) of every required skill
+	pub min_skill_credentials: u32,
+	/// Certifications, of any skill
+	pub min_certifications: u32,
+	/// Client reviews
+	pub min_reviews: u32,
+	/// Total trust score, in hundredths of a point (out of 10 000)
+	pub min_trust_score: u32,
+}
+
+/// Stage of a job
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum JobStatus<AccountId> {
+	/// Accepting applications until the deadline
+	Open,
+	/// An applicant was accepted, and the budget is held in escrow for them
+	InEscrow { freelancer: AccountId },
+}
+
+/// On-chain record of a job
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct JobDetails<AccountId, Balance, BlockNumber> {
+	/// Account that posted the job and pays for it
+	pub client: AccountId,
+	/// Short description of the job
+	pub title: JobTitle,
+	/// Amount paid for the job, held from the client
+	pub budget: Balance,
+	/// Last block in which freelancers can apply
+	pub deadline: BlockNumber,
+	/// Canonical skills applicants must hold
+	pub required_skills: RequiredSkills,
+	/// Minimum public credentials applicants must hold
+	pub requirements: Requirements,
+	/// Number of pending applications
+	pub applications: u32,
+	/// Stage of the job
+	pub status: JobStatus<AccountId>,
+}
+
+/// [`JobDetails`] specialised for a runtime
+pub type JobDetailsOf<T> = JobDetails<
+	<T as polkadot_sdk::frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	BlockNumberFor<T>,
+>;
+
+#[frame::pallet]
+pub mod pallet {
+	use super::*;
+
+	/// Configuration trait for this pallet
+	#[pallet::config]
+	pub trait Config: polkadot_sdk::frame_system::Config {
+		/// The overarching runtime event type
+		type RuntimeEvent: From<Event<Self>>
+			+ IsType<<Self as polkadot_sdk::frame_system::Config>::RuntimeEvent>;
+
+		/// The overarching hold reason
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// Currency job budgets are paid and escrowed in
+		type Currency: MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// Credentials of applicants, checked against job requirements
+		type Credentials: CredentialHoldings<Self::AccountId>;
+
+		/// Origin allowed to settle escrows the client and freelancer disagree on
+		type ArbitrationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Maximum number of pending applications per job
+		#[pallet::constant]
+		type MaxApplications: Get<u32>;
+	}
+
+	/// The pallet struct
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Reasons the pallet holds funds for
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Budget of a job, held until the job is cancelled or its escrow settled
+		JobBudget,
+	}
+
+	/// Storage map of open and escrowed jobs
+	/// Maps job_id -> JobDetails (client, title, budget, deadline, required skills, requirements, applications, status)
+	#[pallet::storage]
+	#[pallet::getter(fn jobs)]
+	pub type Jobs<T: Config> = StorageMap<_, Twox64Concat, JobId, JobDetailsOf<T>, OptionQuery>;
+
+	/// Storage double map of pending applications
+	/// Maps (job_id, applicant) -> block of the application
+	#[pallet::storage]
+	#[pallet::getter(fn applications)]
+	pub type Applications<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		JobId,
+		Blake2_128Concat,
+		T::AccountId,
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// ID given to the next job
+	#[pallet::storage]
+	#[pallet::getter(fn next_job_id)]
+	pub type NextJobId<T: Config> = StorageValue<_, JobId, ValueQuery>;
+
+	/// Events emitted by the pallet
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A client posted a job, holding its budget
+		/// [job_id, client, budget, deadline]
+		JobPosted {
+			job_id: JobId,
+			client: T::AccountId,
+			budget: BalanceOf<T>,
+			deadline: BlockNumberFor<T>,
+		},
+
+		/// A freelancer meeting the requirements applied to a job
+		/// [job_id, applicant]
+		Applied { job_id: JobId, applicant: T::AccountId },
+
+		/// A freelancer withdrew their application
+		/// [job_id, applicant]
+		ApplicationWithdrawn { job_id: JobId, applicant: T::AccountId },
+
+		/// The client accepted an applicant, and the budget is held in escrow
+		/// [job_id, freelancer, amount]
+		ApplicantAccepted { job_id: JobId, freelancer: T::AccountId, amount: BalanceOf<T> },
+
+		/// The client cancelled an open job, and its budget was returned
+		/// [job_id]
+		JobCancelled { job_id: JobId },
+
+		/// The escrow was paid to the freelancer
+		/// [job_id, freelancer, amount]
+		EscrowReleased { job_id: JobId, freelancer: T::AccountId, amount: BalanceOf<T> },
+
+		/// The escrow was returned to the client
+		/// [job_id, client, amount]
+		EscrowRefunded { job_id: JobId, client: T::AccountId, amount: BalanceOf<T> },
+	}
+
+	/// Errors that can occur when calling pallet extrinsics
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The job does not exist
+		JobNotFound,
+		/// The title exceeds 256 bytes
+		TitleTooLong,
+		/// The job requires more than 8 skills
+		TooManySkills,
+		/// The budget is zero
+		ZeroBudget,
+		/// The client cannot hold the budget
+		InsufficientBalance,
+		/// The deadline has passed
+		DeadlinePassed,
+		/// The caller is not the client of the job
+		NotClient,
+		/// The caller is neither the client nor the freelancer of the job
+		NotParticipant,
+		/// The job no longer accepts applications
+		JobNotOpen,
+		/// The job is not in escrow
+		NotInEscrow,
+		/// Clients cannot apply to their own jobs
+		OwnJob,
+		/// The caller already applied to the job
+		AlreadyApplied,
+		/// The account has not applied to the job
+		NotApplicant,
+		/// The job already has the maximum number of applications
+		TooManyApplications,
+		/// The applicant's credentials do not meet the job requirements
+		RequirementsNotMet,
+		/// All job IDs are taken
+		NoJobIdAvailable,
+	}
+
+	/// Dispatchable extrinsics (functions) that can be called by users
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Post a job, holding its budget from the caller
+		///
+		/// Parameters:
+		/// - `title`: Short description of the job (max 256 bytes)
+		/// - `budget`: Amount paid for the job
+		/// - `deadline`: Last block in which freelancers can apply
+		/// - `required_skills`: Canonical skills applicants must hold (max 8)
+		/// - `requirements`: Minimum public credentials applicants must hold
+		///
+		/// Emits:
+		/// - `JobPosted` event with the job ID, client, budget and deadline
+		///
+		/// Errors:
+		/// - `TitleTooLong`: If the title exceeds 256 bytes
+		/// - `TooManySkills`: If more than 8 skills are required
+		/// - `ZeroBudget`: If the budget is zero
+		/// - `DeadlinePassed`: If the deadline is not after the current block
+		/// - `InsufficientBalance`: If the caller cannot hold the budget
+		/// - `NoJobIdAvailable`: If all job IDs are taken
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 3) + Weight::from_parts(40_000, 0))]
+		pub fn post_job(
+			origin: OriginFor<T>,
+			title: Vec<u8>,
+			budget: BalanceOf<T>,
+			deadline: BlockNumberFor<T>,
+			required_skills: Vec<SkillId>,
+			requirements: Requirements,
+		) -> DispatchResult {
+			let client = ensure_signed(origin)?;
+			let title: JobTitle = title.try_into().map_err(|_| Error::<T>::TitleTooLong)?;
+			let required_skills: RequiredSkills =
+				required_skills.try_into().map_err(|_| Error::<T>::TooManySkills)?;
+			ensure!(!budget.is_zero(), Error::<T>::ZeroBudget);
+			ensure!(
+				deadline > polkadot_sdk::frame_system::Pallet::<T>::block_number(),
+				Error::<T>::DeadlinePassed
+			);
+
+			let job_id = NextJobId::<T>::get();
+			let next = job_id.checked_add(1).ok_or(Error::<T>::NoJobIdAvailable)?;
+			T::Currency::hold(&HoldReason::JobBudget.into(), &client, budget)
+				.map_err(|_| Error::<T>::InsufficientBalance)?;
+
+			Jobs::<T>::insert(
+				job_id,
+				JobDetails {
+					client: client.clone(),
+					title,
+					budget,
+					deadline,
+					required_skills,
+					requirements,
+					applications: 0,
+					status: JobStatus::Open,
+				},
+			);
+			NextJobId::<T>::put(next);
+
+			Self::deposit_event(Event::JobPosted { job_id, client, budget, deadline });
+
+			Ok(())
+		}
+
+		/// Apply to an open job
+		///
+		/// The caller's public credentials must meet the job's requirements: at least
+		/// `min_skill_credentials` credentials of every required skill, and the minimum
+		/// number of certifications, reviews and trust score.
+		///
+		/// Parameters:
+		/// - `job_id`: Job to apply to
+		///
+		/// Emits:
+		/// - `Applied` event with the job ID and applicant
+		///
+		/// Errors:
+		/// - `JobNotFound`: If the job does not exist
+		/// - `JobNotOpen`: If an applicant was already accepted
+		/// - `DeadlinePassed`: If the job's deadline has passed
+		/// - `OwnJob`: If the caller is the client
+		/// - `AlreadyApplied`: If the caller already applied
+		/// - `TooManyApplications`: If the job already has `MaxApplications` applications
+		/// - `RequirementsNotMet`: If the caller's credentials do not meet the requirements
+		#[pallet::call_index(1)]
+		#[pallet::weight(
+			T::DbWeight::get().reads_writes(3, 2) + Weight::from_parts(40_000, 0)
+				+ T::Credentials::holdings_weight()
+		)]
+		pub fn apply(origin: OriginFor<T>, job_id: JobId) -> DispatchResult {
+			let applicant = ensure_signed(origin)?;
+			let mut job = Self::jobs(job_id).ok_or(Error::<T>::JobNotFound)?;
+
+			ensure!(job.status == JobStatus::Open, Error::<T>::JobNotOpen);
+			let now = polkadot_sdk::frame_system::Pallet::<T>::block_number();
+			ensure!(now <= job.deadline, Error::<T>::DeadlinePassed);
+			ensure!(applicant != job.client, Error::<T>::OwnJob);
+			ensure!(
+				!Applications::<T>::contains_key(job_id, &applicant),
+				Error::<T>::AlreadyApplied
+			);
+			ensure!(job.applications < T::MaxApplications::get(), Error::<T>::TooManyApplications);
+			ensure!(Self::meets_requirements(&job, &applicant), Error::<T>::RequirementsNotMet);
+
+			job.applications += 1;
+			Jobs::<T>::insert(job_id, job);
+			Applications::<T>::insert(job_id, &applicant, now);
+
+			Self::deposit_event(Event::Applied { job_id, applicant });
+
+			Ok(())
+		}
+
+		/// Withdraw an application to an open job
+		///
+		/// Parameters:
+		/// - `job_id`: Job applied to
+		///
+		/// Emits:
+		/// - `ApplicationWithdrawn` event with the job ID and applicant
+		///
+		/// Errors:
+		/// - `JobNotFound`: If the job does not exist
+		/// - `NotApplicant`: If the caller has not applied to the job
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2) + Weight::from_parts(20_000, 0))]
+		pub fn withdraw_application(origin: OriginFor<T>, job_id: JobId) -> DispatchResult {
+			let applicant = ensure_signed(origin)?;
+			let mut job = Self::jobs(job_id).ok_or(Error::<T>::JobNotFound)?;
+
+			Applications::<T>::take(job_id, &applicant).ok_or(Error::<T>::NotApplicant)?;
+			job.applications = job.applications.saturating_sub(1);
+			Jobs::<T>::insert(job_id, job);
+
+			Self::deposit_event(Event::ApplicationWithdrawn { job_id, applicant });
+
+			Ok(())
+		}
+
+		/// Accept an applicant, moving the job's budget into escrow for them
+		///
+		/// Other applications are discarded. Applicants can be accepted after the deadline.
+		///
+		/// Parameters:
+		/// - `job_id`: Job to accept the applicant for
+		/// - `freelancer`: Applicant to accept
+		///
+		/// Emits:
+		/// - `ApplicantAccepted` event with the job ID, freelancer and escrowed amount
+		///
+		/// Errors:
+		/// - `JobNotFound`: If the job does not exist
+		/// - `NotClient`: If the caller is not the client
+		/// - `JobNotOpen`: If an applicant was already accepted
+		/// - `NotApplicant`: If the account has not applied to the job
+		#[pallet::call_index(3)]
+		#[pallet::weight(
+			T::DbWeight::get().reads_writes(2, 1 + T::MaxApplications::get() as u64)
+				+ Weight::from_parts(30_000, 0)
+		)]
+		pub fn accept_applicant(
+			origin: OriginFor<T>,
+			job_id: JobId,
+			freelancer: T::AccountId,
+		) -> DispatchResult {
+			let client = ensure_signed(origin)?;
+			let mut job = Self::jobs(job_id).ok_or(Error::<T>::JobNotFound)?;
+
+			ensure!(job.client == client, Error::<T>::NotClient);
+			ensure!(job.status == JobStatus::Open, Error::<T>::JobNotOpen);
+			ensure!(Applications::<T>::contains_key(job_id, &freelancer), Error::<T>::NotApplicant);
+
+			Self::clear_applications(job_id);
+			job.applications = 0;
+			job.status = JobStatus::InEscrow { freelancer: freelancer.clone() };
+			let amount = job.budget;
+			Jobs::<T>::insert(job_id, job);
+
+			Self::deposit_event(Event::ApplicantAccepted { job_id, freelancer, amount });
+
+			Ok(())
+		}
+
+		/// Cancel an open job, returning its budget to the client
+		///
+		/// Parameters:
+		/// - `job_id`: Job to cancel
+		///
+		/// Emits:
+		/// - `JobCancelled` event with the job ID
+		///
+		/// Errors:
+		/// - `JobNotFound`: If the job does not exist
+		/// - `NotClient`: If the caller is not the client
+		/// - `JobNotOpen`: If an applicant was already accepted
+		#[pallet::call_index(4)]
+		#[pallet::weight(
+			T::DbWeight::get().reads_writes(2, 2 + T::MaxApplications::get() as u64)
+				+ Weight::from_parts(30_000, 0)
+		)]
+		pub fn cancel_job(origin: OriginFor<T>, job_id: JobId) -> DispatchResult {
+			let client = ensure_signed(origin)?;
+			let job = Self::jobs(job_id).ok_or(Error::<T>::JobNotFound)?;
+
+			ensure!(job.client == client, Error::<T>::NotClient);
+			ensure!(job.status == JobStatus::Open, Error::<T>::JobNotOpen);
+
+			Self::clear_applications(job_id);
+			T::Currency::release(
+				&HoldReason::JobBudget.into(),
+				&client,
+				job.budget,
+				Precision::BestEffort,
+			)?;
+			Jobs::<T>::remove(job_id);
+
+			Self::deposit_event(Event::JobCancelled { job_id });
+
+			Ok(())
+		}
+
+		/// Settle an escrow
+		///
+		/// The client can only pay the freelancer, and the freelancer can only refund the
+		/// client; `ArbitrationOrigin` can do either. The job is removed once settled.
+		///
+		/// Parameters:
+		/// - `job_id`: Job in escrow
+		/// - `pay_freelancer`: Whether the escrow goes to the freelancer rather than back to
+		///   the client
+		///
+		/// Emits:
+		/// - `EscrowReleased` event with the job ID, freelancer and amount paid, when paid
+		/// - `EscrowRefunded` event with the job ID, client and amount returned, when
+		///   refunded
+		///
+		/// Errors:
+		/// - `JobNotFound`: If the job does not exist
+		/// - `NotInEscrow`: If no applicant was accepted
+		/// - `NotParticipant`: If a signed caller may not settle the escrow this way
+		/// - Any currency error, e.g. if the budget would not create the freelancer's account
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 3) + Weight::from_parts(40_000, 0))]
+		pub fn settle_escrow(
+			origin: OriginFor<T>,
+			job_id: JobId,
+			pay_freelancer: bool,
+		) -> DispatchResult {
+			let job = Self::jobs(job_id).ok_or(Error::<T>::JobNotFound)?;
+			let JobStatus::InEscrow { freelancer } = job.status else {
+				return Err(Error::<T>::NotInEscrow.into());
+			};
+
+			if T::ArbitrationOrigin::try_origin(origin.clone()).is_err() {
+				let who = ensure_signed(origin)?;
+				let allowed = if pay_freelancer { who == job.client } else { who == freelancer };
+				ensure!(allowed, Error::<T>::NotParticipant);
+			}
+
+			Jobs::<T>::remove(job_id);
+			let reason = HoldReason::JobBudget.into();
+			if pay_freelancer {
+				let amount = T::Currency::transfer_on_hold(
+					&reason,
+					&job.client,
+					&freelancer,
+					job.budget,
+					Precision::BestEffort,
+					Restriction::Free,
+					Fortitude::Polite,
+				)?;
+				Self::deposit_event(Event::EscrowReleased { job_id, freelancer, amount });
+			} else {
+				let amount =
+					T::Currency::release(&reason, &job.client, job.budget, Precision::BestEffort)?;
+				Self::deposit_event(Event::EscrowRefunded { job_id, client: job.client, amount });
+			}
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether the public credentials of `applicant` meet the requirements of `job`
+	pub fn meets_requirements(job: &JobDetailsOf<T>, applicant: &T::AccountId) -> bool {
+		let holdings = T::Credentials::holdings(applicant);
+		let requirements = &job.requirements;
+
+		job.required_skills.iter().all(|skill_id| {
+			holdings.skill_credentials(*skill_id) >= requirements.min_skill_credentials.max(1)
+		}) && holdings.certifications >= requirements.min_certifications
+			&& holdings.reviews >= requirements.min_reviews
+			&& holdings.trust_score.total() >= requirements.min_trust_score
+	}
+
+	/// Discard every pending application to `job_id`
+	fn clear_applications(job_id: JobId) {
+		let _ = Applications::<T>::clear_prefix(job_id, T::MaxApplications::get(), None);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame::testing_prelude::*;
+	use polkadot_sdk::frame_support::traits::fungible::InspectHold;
+	use pallet_freelance_credentials::{Holdings, TrustScore};
+	use polkadot_sdk::pallet_balances;
+
+	// Configure a mock runtime to test the pallet
+	construct_runtime!(
+		pub enum Test {
+			System: frame_system,
+			Balances: pallet_balances,
+			Marketplace: crate,
+		}
+	);
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = MockBlock<Test>;
+		type AccountId = u64;
+		type AccountData = pallet_balances::AccountData<u64>;
+	}
+
+	#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+	impl pallet_balances::Config for Test {
+		type AccountStore = System;
+	}
+
+	impl Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type RuntimeHoldReason = RuntimeHoldReason;
+		type Currency = Balances;
+		type Credentials = MockCredentials;
+		type ArbitrationOrigin = frame_system::EnsureRoot<u64>;
+		type MaxApplications = ConstU32<2>;
+	}
+
+	const CLIENT: u64 = 1;
+	// Holds two Rust (1) credentials, a certification and a review
+	const SENIOR: u64 = 2;
+	// Holds one Rust (1) credential
+	const JUNIOR: u64 = 3;
+	// Holds two Rust (1) credentials and one Design (2) credential
+	const DESIGNER: u64 = 4;
+
+	pub struct MockCredentials;
+
+	impl CredentialHoldings<u64> for MockCredentials {
+		fn holdings(who: &u64) -> Holdings {
+			match *who {
+				SENIOR => Holdings {
+					skills: vec![(1, 2)],
+					certifications: 1,
+					reviews: 1,
+					trust_score: TrustScore { review: 6000, skill: 600, payment: 0 },
+				},
+				JUNIOR => Holdings {
+					skills: vec![(1, 1)],
+					trust_score: TrustScore { review: 0, skill: 150, payment: 0 },
+					..Default::default()
+				},
+				DESIGNER => Holdings {
+					skills: vec![(1, 2), (2, 1)],
+					trust_score: TrustScore { review: 0, skill: 450, payment: 0 },
+					..Default::default()
+				},
+				_ => Holdings::default(),
+			}
+		}
+
+		fn holdings_weight() -> Weight {
+			Weight::zero()
+		}
+	}
+
+	// Build genesis storage according to the mock runtime
+	pub fn new_test_ext() -> TestExternalities {
+		let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(CLIENT, 1_000), (SENIOR, 100), (JUNIOR, 100), (DESIGNER, 100)],
+			..Default::default()
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+		let mut ext: TestExternalities = storage.into();
+		// Events are only recorded after the genesis block
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	// Post job 0, paying 500, open until block 10, for Rust (1) with two credentials
+	fn post_rust_job() {
+		assert_ok!(Marketplace::post_job(
+			RuntimeOrigin::signed(CLIENT),
+			b"Audit a pallet".to_vec(),
+			500,
+			10,
+			vec![1],
+			Requirements { min_skill_credentials: 2, ..Default::default() },
+		));
+	}
+
+	#[test]
+	fn test_post_job() {
+		new_test_ext().execute_with(|| {
+			post_rust_job();
+			System::assert_last_event(
+				Event::JobPosted { job_id: 0, client: CLIENT, budget: 500, deadline: 10 }.into(),
+			);
+			assert_eq!(Balances::balance_on_hold(&HoldReason::JobBudget.into(), &CLIENT), 500);
+			let job = Marketplace::jobs(0).unwrap();
+			assert_eq!(job.status, JobStatus::Open);
+			assert_eq!(job.required_skills.into_inner(), vec![1]);
+			assert_eq!(Marketplace::next_job_id(), 1);
+
+			let post = |budget: u64, deadline: u64, skills: Vec<SkillId>| {
+				Marketplace::post_job(
+					RuntimeOrigin::signed(CLIENT),
+					b"Job".to_vec(),
+					budget,
+					deadline,
+					skills,
+					Requirements::default(),
+				)
+			};
+			assert_noop!(post(0, 10, vec![]), Error::<Test>::ZeroBudget);
+			assert_noop!(post(100, 1, vec![]), Error::<Test>::DeadlinePassed);
+			assert_noop!(post(100, 10, vec![1; 9]), Error::<Test>::TooManySkills);
+			assert_noop!(post(501, 10, vec![]), Error::<Test>::InsufficientBalance);
+		});
+	}
+
+	#[test]
+	fn test_applications_are_gated_by_credentials() {
+		new_test_ext().execute_with(|| {
+			post_rust_job();
+
+			assert_noop!(
+				Marketplace::apply(RuntimeOrigin::signed(JUNIOR), 0),
+				Error::<Test>::RequirementsNotMet
+			);
+			assert_noop!(
+				Marketplace::apply(RuntimeOrigin::signed(CLIENT), 0),
+				Error::<Test>::OwnJob
+			);
+			assert_ok!(Marketplace::apply(RuntimeOrigin::signed(SENIOR), 0));
+			System::assert_last_event(Event::Applied { job_id: 0, applicant: SENIOR }.into());
+			assert_noop!(
+				Marketplace::apply(RuntimeOrigin::signed(SENIOR), 0),
+				Error::<Test>::AlreadyApplied
+			);
+			assert_eq!(Marketplace::applications(0, SENIOR), Some(1));
+
+			// Certification, review and trust score requirements
+			let post = |requirements: Requirements| {
+				assert_ok!(Marketplace::post_job(
+					RuntimeOrigin::signed(CLIENT),
+					b"Job".to_vec(),
+					100,
+					10,
+					vec![],
+					requirements,
+				));
+				Marketplace::next_job_id() - 1
+			};
+			let job = post(Requirements { min_certifications: 1, ..Default::default() });
+			assert_noop!(
+				Marketplace::apply(RuntimeOrigin::signed(DESIGNER), job),
+				Error::<Test>::RequirementsNotMet
+			);
+			assert_ok!(Marketplace::apply(RuntimeOrigin::signed(SENIOR), job));
+			let job = post(Requirements { min_reviews: 1, ..Default::default() });
+			assert_noop!(
+				Marketplace::apply(RuntimeOrigin::signed(JUNIOR), job),
+				Error::<Test>::RequirementsNotMet
+			);
+			let job = post(Requirements { min_trust_score: 400, ..Default::default() });
+			assert_noop!(
+				Marketplace::apply(RuntimeOrigin::signed(JUNIOR), job),
+				Error::<Test>::RequirementsNotMet
+			);
+			assert_ok!(Marketplace::apply(RuntimeOrigin::signed(DESIGNER), job));
+
+			// Every required skill is needed
+			assert_ok!(Marketplace::post_job(
+				RuntimeOrigin::signed(CLIENT),
+				b"Job".to_vec(),
+				100,
+				10,
+				vec![1, 2],
+				Requirements::default(),
+			));
+			let job = Marketplace::next_job_id() - 1;
+			assert_noop!(
+				Marketplace::apply(RuntimeOrigin::signed(SENIOR), job),
+				Error::<Test>::RequirementsNotMet
+			);
+			assert_ok!(Marketplace::apply(RuntimeOrigin::signed(DESIGNER), job));
+		});
+	}
+
+	#[test]
+	fn test_application_limits() {
+		new_test_ext().execute_with(|| {
+			post_rust_job();
+			assert_ok!(Marketplace::apply(RuntimeOrigin::signed(SENIOR), 0));
+			assert_ok!(Marketplace::apply(RuntimeOrigin::signed(DESIGNER), 0));
+			assert_noop!(
+				Marketplace::apply(RuntimeOrigin::signed(5), 0),
+				Error::<Test>::TooManyApplications
+			);
+
+			assert_ok!(Marketplace::withdraw_application(RuntimeOrigin::signed(DESIGNER), 0));
+			System::assert_last_event(
+				Event::ApplicationWithdrawn { job_id: 0, applicant: DESIGNER }.into(),
+			);
+			assert_eq!(Marketplace::jobs(0).unwrap().applications, 1);
+			assert_noop!(
+				Marketplace::withdraw_application(RuntimeOrigin::signed(DESIGNER), 0),
+				Error::<Test>::NotApplicant
+			);
+
+			// No applications after the deadline
+			System::set_block_number(11);
+			assert_noop!(
+				Marketplace::apply(RuntimeOrigin::signed(DESIGNER), 0),
+				Error::<Test>::DeadlinePassed
+			);
+		});
+	}
+
+	#[test]
+	fn test_accept_and_release_escrow() {
+		new_test_ext().execute_with(|| {
+			post_rust_job();
+			assert_ok!(Marketplace::apply(RuntimeOrigin::signed(SENIOR), 0));
+			assert_ok!(Marketplace::apply(RuntimeOrigin::signed(DESIGNER), 0));
+
+			assert_noop!(
+				Marketplace::accept_applicant(RuntimeOrigin::signed(SENIOR), 0, SENIOR),
+				Error::<Test>::NotClient
+			);
+			assert_noop!(
+				Marketplace::accept_applicant(RuntimeOrigin::signed(CLIENT), 0, JUNIOR),
+				Error::<Test>::NotApplicant
+			);
+			assert_noop!(
+				Marketplace::settle_escrow(RuntimeOrigin::signed(CLIENT), 0, true),
+				Error::<Test>::NotInEscrow
+			);
+
+			assert_ok!(Marketplace::accept_applicant(RuntimeOrigin::signed(CLIENT), 0, SENIOR));
+			System::assert_last_event(
+				Event::ApplicantAccepted { job_id: 0, freelancer: SENIOR, amount: 500 }.into(),
+			);
+			assert_eq!(
+				Marketplace::jobs(0).unwrap().status,
+				JobStatus::InEscrow { freelancer: SENIOR }
+			);
+			assert_eq!(Marketplace::applications(0, DESIGNER), None);
+			assert_noop!(
+				Marketplace::cancel_job(RuntimeOrigin::signed(CLIENT), 0),
+				Error::<Test>::JobNotOpen
+			);
+			assert_noop!(
+				Marketplace::apply(RuntimeOrigin::signed(DESIGNER), 0),
+				Error::<Test>::JobNotOpen
+			);
+
+			// Only the client releases the escrow to the freelancer
+			assert_noop!(
+				Marketplace::settle_escrow(RuntimeOrigin::signed(SENIOR), 0, true),
+				Error::<Test>::NotParticipant
+			);
+			assert_ok!(Marketplace::settle_escrow(RuntimeOrigin::signed(CLIENT), 0, true));
+			System::assert_last_event(
+				Event::EscrowReleased { job_id: 0, freelancer: SENIOR, amount: 500 }.into(),
+			);
+			assert_eq!(Balances::balance_on_hold(&HoldReason::JobBudget.into(), &CLIENT), 0);
+			assert_eq!(Balances::free_balance(CLIENT), 500);
+			assert_eq!(Balances::free_balance(SENIOR), 600);
+			assert_eq!(Marketplace::jobs(0), None);
+		});
+	}
+
+	#[test]
+	fn test_refunds() {
+		new_test_ext().execute_with(|| {
+			// Cancelling an open job returns the budget
+			post_rust_job();
+			assert_noop!(
+				Marketplace::cancel_job(RuntimeOrigin::signed(SENIOR), 0),
+				Error::<Test>::NotClient
+			);
+			assert_ok!(Marketplace::cancel_job(RuntimeOrigin::signed(CLIENT), 0));
+			System::assert_last_event(Event::JobCancelled { job_id: 0 }.into());
+			assert_eq!(Balances::free_balance(CLIENT), 1_000);
+
+			// Only the freelancer refunds the escrow to the client
+			post_rust_job();
+			assert_ok!(Marketplace::apply(RuntimeOrigin::signed(SENIOR), 1));
+			assert_ok!(Marketplace::accept_applicant(RuntimeOrigin::signed(CLIENT), 1, SENIOR));
+			assert_noop!(
+				Marketplace::settle_escrow(RuntimeOrigin::signed(CLIENT), 1, false),
+				Error::<Test>::NotParticipant
+			);
+			assert_ok!(Marketplace::settle_escrow(RuntimeOrigin::signed(SENIOR), 1, false));
+			System::assert_last_event(
+				Event::EscrowRefunded { job_id: 1, client: CLIENT, amount: 500 }.into(),
+			);
+			assert_eq!(Balances::free_balance(CLIENT), 1_000);
+
+			// Arbitration settles either way
+			post_rust_job();
+			assert_ok!(Marketplace::apply(RuntimeOrigin::signed(SENIOR), 2));
+			assert_ok!(Marketplace::accept_applicant(RuntimeOrigin::signed(CLIENT), 2, SENIOR));
+			assert_ok!(Marketplace::settle_escrow(RuntimeOrigin::root(), 2, true));
+			assert_eq!(Balances::free_balance(SENIOR), 600);
+		});
+	}
+}
@@ -0,0 +1,779 @@
+//! # FreelanceForge Organizations Pallet
+//!
+//! Organization accounts for agencies, universities and other teams, so their members no
+//! longer need to share one private key.
+//!
+//! Every organization has a keyless account derived from its ID. Members act as that account
+//! through [`Pallet::dispatch_as_organization`], within the limits of their [`Role`]:
+//!
+//! - **Admin**: Manages members and may make any call as the organization
+//! - **Issuer**: May make the calls of [`Config::IssuerCalls`] (e.g. the credentials pallet)
+//!   as the organization
+//! - **Viewer**: May read the organization's private data off-chain, but make no calls
+//!
+//! ## Features
+//!
+//! - **Audited membership**: Every membership change emits an event naming the admin who
+//!   made it
+//! - **High-value approval**: Calls matched by [`Config::HighValueCalls`] can be reserved to
+//!   an approver account, typically a `pallet_multisig` account of several admins, which
+//!   also alone may replace itself
+//! - **Last admin protection**: An organization always keeps at least one admin
+//!
+//! ## Storage
+//!
+//! - `Organizations`: Maps organization IDs to [`OrganizationDetails`] records (account,
+//!   name, member and admin counts, high-value approver)
+//! - `Members`: Maps (organization ID, account) pairs to the member's [`Role`]
+//! - `NextOrganizationId`: ID given to the next organization
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::DecodeWithMemTracking;
+use frame::prelude::*;
+use polkadot_sdk::{
+	frame_support::{
+		dispatch::{GetDispatchInfo, PostDispatchInfo},
+		traits::Contains,
+		PalletId,
+	},
+	polkadot_sdk_frame as frame,
+	sp_runtime::traits::{AccountIdConversion, Dispatchable},
+};
+
+extern crate alloc;
+use alloc::{boxed::Box, vec::Vec};
+
+// Re-export all pallet parts for runtime integration
+pub use pallet::*;
+
+/// Identifier of an organization
+pub type OrganizationId = u32;
+
+/// Display name of an organization (max 128 bytes)
+pub type OrganizationName = BoundedVec<u8, ConstU32<128>>;
+
+/// Role of an organization member
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum Role {
+	/// Manages members and may make any call as the organization
+	Admin,
+	/// May make credential calls as the organization
+	Issuer,
+	/// May read the organization's private data, but make no calls
+	Viewer,
+}
+
+/// On-chain record of an organization
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct OrganizationDetails<AccountId> {
+	/// Keyless account members act as
+	pub account: AccountId,
+	/// Display name
+	pub name: OrganizationName,
+	/// Number of members, admins included
+	pub members: u32,
+	/// Number of admins
+	pub admins: u32,
+	/// Account that alone may make high-value calls as the organization, if any
+	pub approver: Option<AccountId>,
+}
+
+/// [`OrganizationDetails`] specialised for a runtime
+pub type OrganizationDetailsOf<T> =
+	OrganizationDetails<<T as polkadot_sdk::frame_system::Config>::AccountId>;
+
+#[frame::pallet]
+pub mod pallet {
+	use super::*;
+
+	/// Configuration trait for this pallet
+	#[pallet::config]
+	pub trait Config: polkadot_sdk::frame_system::Config {
+		/// The overarching runtime event type
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as polkadot_sdk::frame_system::Config>::RuntimeEvent>;
+
+		/// The overarching call type, dispatched as organizations
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ IsType<<Self as polkadot_sdk::frame_system::Config>::RuntimeCall>;
+
+		/// Derives the accounts of organizations
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Maximum number of members of an organization
+		#[pallet::constant]
+		type MaxMembers: Get<u32>;
+
+		/// Calls issuers may make as their organization
+		type IssuerCalls: Contains<<Self as Config>::RuntimeCall>;
+
+		/// Calls reserved to the approver of organizations that have one
+		type HighValueCalls: Contains<<Self as Config>::RuntimeCall>;
+	}
+
+	/// The pallet struct
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Storage map of organizations
+	/// Maps organization_id -> OrganizationDetails (account, name, members, admins, approver)
+	#[pallet::storage]
+	#[pallet::getter(fn organizations)]
+	pub type Organizations<T: Config> =
+		StorageMap<_, Twox64Concat, OrganizationId, OrganizationDetailsOf<T>, OptionQuery>;
+
+	/// Storage double map of organization members
+	/// Maps (organization_id, account_id) -> Role
+	#[pallet::storage]
+	#[pallet::getter(fn members)]
+	pub type Members<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		OrganizationId,
+		Blake2_128Concat,
+		T::AccountId,
+		Role,
+		OptionQuery,
+	>;
+
+	/// ID given to the next organization
+	#[pallet::storage]
+	#[pallet::getter(fn next_organization_id)]
+	pub type NextOrganizationId<T: Config> = StorageValue<_, OrganizationId, ValueQuery>;
+
+	/// Events emitted by the pallet
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An organization was created, with its creator as admin
+		/// [org, account, creator]
+		OrganizationCreated { org: OrganizationId, account: T::AccountId, creator: T::AccountId },
+
+		/// An account joined an organization
+		/// [org, member, role, by]
+		MemberAdded { org: OrganizationId, member: T::AccountId, role: Role, by: T::AccountId },
+
+		/// The role of a member changed
+		/// [org, member, role, by]
+		MemberRoleChanged { org: OrganizationId, member: T::AccountId, role: Role, by: T::AccountId },
+
+		/// A member left or was removed from an organization
+		/// [org, member, by]
+		MemberRemoved { org: OrganizationId, member: T::AccountId, by: T::AccountId },
+
+		/// The high-value approver of an organization changed
+		/// [org, approver, by]
+		ApproverChanged { org: OrganizationId, approver: Option<T::AccountId>, by: T::AccountId },
+
+		/// A member made a call as an organization
+		/// [org, member, result]
+		CalledAsOrganization { org: OrganizationId, member: T::AccountId, result: DispatchResult },
+	}
+
+	/// Errors that can occur when calling pallet extrinsics
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The organization does not exist
+		OrganizationNotFound,
+		/// The organization name exceeds 128 bytes
+		NameTooLong,
+		/// The caller is not a member of the organization
+		NotMember,
+		/// The caller's role does not allow this action
+		NotAuthorized,
+		/// The account is already a member of the organization
+		AlreadyMember,
+		/// The organization already has the maximum number of members
+		TooManyMembers,
+		/// The organization would be left without an admin
+		LastAdmin,
+		/// The call is reserved to the organization's high-value approver
+		ApprovalRequired,
+		/// All organization IDs are taken
+		NoOrganizationIdAvailable,
+	}
+
+	/// Dispatchable extrinsics (functions) that can be called by users
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create an organization with the caller as its first admin
+		///
+		/// Parameters:
+		/// - `name`: Display name of the organization (max 128 bytes)
+		///
+		/// Emits:
+		/// - `OrganizationCreated` event with the organization ID, account and creator
+		///
+		/// Errors:
+		/// - `NameTooLong`: If the name exceeds 128 bytes
+		/// - `NoOrganizationIdAvailable`: If all organization IDs are taken
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 3) + Weight::from_parts(30_000, 0))]
+		pub fn create_organization(origin: OriginFor<T>, name: Vec<u8>) -> DispatchResult {
+			let creator = ensure_signed(origin)?;
+			let name: OrganizationName = name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+
+			let org = NextOrganizationId::<T>::get();
+			let next = org.checked_add(1).ok_or(Error::<T>::NoOrganizationIdAvailable)?;
+			let account = Self::account_of(org);
+
+			Organizations::<T>::insert(
+				org,
+				OrganizationDetails {
+					account: account.clone(),
+					name,
+					members: 1,
+					admins: 1,
+					approver: None,
+				},
+			);
+			Members::<T>::insert(org, &creator, Role::Admin);
+			NextOrganizationId::<T>::put(next);
+
+			Self::deposit_event(Event::OrganizationCreated { org, account, creator });
+
+			Ok(())
+		}
+
+		/// Add a member to an organization
+		///
+		/// Parameters:
+		/// - `org`: Organization to add the member to
+		/// - `member`: Account joining the organization
+		/// - `role`: Role of the new member
+		///
+		/// Emits:
+		/// - `MemberAdded` event with the organization, member, role and admin
+		///
+		/// Errors:
+		/// - `OrganizationNotFound`: If the organization does not exist
+		/// - `NotAuthorized`: If the caller is not an admin of the organization
+		/// - `AlreadyMember`: If the account is already a member
+		/// - `TooManyMembers`: If the organization already has `MaxMembers` members
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 2) + Weight::from_parts(25_000, 0))]
+		pub fn add_member(
+			origin: OriginFor<T>,
+			org: OrganizationId,
+			member: T::AccountId,
+			role: Role,
+		) -> DispatchResult {
+			let admin = ensure_signed(origin)?;
+			let mut details = Self::organization_for_admin(org, &admin)?;
+
+			ensure!(!Members::<T>::contains_key(org, &member), Error::<T>::AlreadyMember);
+			ensure!(details.members < T::MaxMembers::get(), Error::<T>::TooManyMembers);
+
+			details.members += 1;
+			if role == Role::Admin {
+				details.admins += 1;
+			}
+			Organizations::<T>::insert(org, details);
+			Members::<T>::insert(org, &member, role);
+
+			Self::deposit_event(Event::MemberAdded { org, member, role, by: admin });
+
+			Ok(())
+		}
+
+		/// Change the role of a member
+		///
+		/// Parameters:
+		/// - `org`: Organization of the member
+		/// - `member`: Member whose role changes
+		/// - `role`: New role of the member
+		///
+		/// Emits:
+		/// - `MemberRoleChanged` event with the organization, member, role and admin
+		///
+		/// Errors:
+		/// - `OrganizationNotFound`: If the organization does not exist
+		/// - `NotAuthorized`: If the caller is not an admin of the organization
+		/// - `NotMember`: If the account is not a member
+		/// - `LastAdmin`: If the member is the organization's only admin
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 2) + Weight::from_parts(25_000, 0))]
+		pub fn set_role(
+			origin: OriginFor<T>,
+			org: OrganizationId,
+			member: T::AccountId,
+			role: Role,
+		) -> DispatchResult {
+			let admin = ensure_signed(origin)?;
+			let mut details = Self::organization_for_admin(org, &admin)?;
+			let previous = Self::members(org, &member).ok_or(Error::<T>::NotMember)?;
+
+			match (previous, role) {
+				(Role::Admin, Role::Admin) => {},
+				(Role::Admin, _) => {
+					ensure!(details.admins > 1, Error::<T>::LastAdmin);
+					details.admins -= 1;
+				},
+				(_, Role::Admin) => details.admins += 1,
+				_ => {},
+			}
+			Organizations::<T>::insert(org, details);
+			Members::<T>::insert(org, &member, role);
+
+			Self::deposit_event(Event::MemberRoleChanged { org, member, role, by: admin });
+
+			Ok(())
+		}
+
+		/// Remove a member from an organization
+		///
+		/// Admins can remove any member; other members can only remove themselves.
+		///
+		/// Parameters:
+		/// - `org`: Organization of the member
+		/// - `member`: Member to remove
+		///
+		/// Emits:
+		/// - `MemberRemoved` event with the organization, member and caller
+		///
+		/// Errors:
+		/// - `OrganizationNotFound`: If the organization does not exist
+		/// - `NotMember`: If the caller or the account is not a member
+		/// - `NotAuthorized`: If the caller removes another member without being an admin
+		/// - `LastAdmin`: If the member is the organization's only admin
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 2) + Weight::from_parts(25_000, 0))]
+		pub fn remove_member(
+			origin: OriginFor<T>,
+			org: OrganizationId,
+			member: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut details = Self::organizations(org).ok_or(Error::<T>::OrganizationNotFound)?;
+			let caller_role = Self::members(org, &who).ok_or(Error::<T>::NotMember)?;
+			ensure!(who == member || caller_role == Role::Admin, Error::<T>::NotAuthorized);
+
+			let role = Members::<T>::take(org, &member).ok_or(Error::<T>::NotMember)?;
+			if role == Role::Admin {
+				ensure!(details.admins > 1, Error::<T>::LastAdmin);
+				details.admins -= 1;
+			}
+			details.members -= 1;
+			Organizations::<T>::insert(org, details);
+
+			Self::deposit_event(Event::MemberRemoved { org, member, by: who });
+
+			Ok(())
+		}
+
+		/// Reserve high-value calls to an approver account, or lift the reservation
+		///
+		/// Without an approver, admins set it. Once set, only the approver can replace or
+		/// remove itself, e.g. a `pallet_multisig` account acting on a threshold of its
+		/// signatories' approvals.
+		///
+		/// Parameters:
+		/// - `org`: Organization to change
+		/// - `approver`: New approver, or `None` to let admins make high-value calls
+		///
+		/// Emits:
+		/// - `ApproverChanged` event with the organization, approver and caller
+		///
+		/// Errors:
+		/// - `OrganizationNotFound`: If the organization does not exist
+		/// - `NotAuthorized`: If the caller is not an admin of an organization without approver
+		/// - `ApprovalRequired`: If the organization has an approver other than the caller
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 1) + Weight::from_parts(20_000, 0))]
+		pub fn set_approver(
+			origin: OriginFor<T>,
+			org: OrganizationId,
+			approver: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut details = Self::organizations(org).ok_or(Error::<T>::OrganizationNotFound)?;
+
+			match &details.approver {
+				Some(current) => ensure!(*current == who, Error::<T>::ApprovalRequired),
+				None => ensure!(
+					Self::members(org, &who) == Some(Role::Admin),
+					Error::<T>::NotAuthorized
+				),
+			}
+			details.approver = approver.clone();
+			Organizations::<T>::insert(org, details);
+
+			Self::deposit_event(Event::ApproverChanged { org, approver, by: who });
+
+			Ok(())
+		}
+
+		/// Make a call as an organization
+		///
+		/// Issuers may only make `IssuerCalls`; admins may make any call. High-value calls
+		/// of an organization with an approver can only be made by the approver, which need
+		/// not be a member. The call is dispatched from the organization's account, and its
+		/// outcome reported in the `CalledAsOrganization` event.
+		///
+		/// Parameters:
+		/// - `org`: Organization to act as
+		/// - `call`: Call to make
+		///
+		/// Emits:
+		/// - `CalledAsOrganization` event with the organization, caller and call result
+		///
+		/// Errors:
+		/// - `OrganizationNotFound`: If the organization does not exist
+		/// - `NotMember`: If the caller is neither a member nor the approver
+		/// - `NotAuthorized`: If the caller's role does not allow the call
+		/// - `ApprovalRequired`: If the call is reserved to the organization's approver
+		#[pallet::call_index(5)]
+		#[pallet::weight({
+			let info = call.get_dispatch_info();
+			(
+				T::DbWeight::get().reads(2)
+					.saturating_add(Weight::from_parts(20_000, 0))
+					.saturating_add(info.call_weight),
+				info.class,
+			)
+		})]
+		pub fn dispatch_as_organization(
+			origin: OriginFor<T>,
+			org: OrganizationId,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let details = Self::organizations(org).ok_or(Error::<T>::OrganizationNotFound)?;
+			Self::ensure_can_call(org, &details, &who, &call)?;
+
+			let origin = frame_system::RawOrigin::Signed(details.account).into();
+			let result = (*call).dispatch(origin).map(|_| ()).map_err(|error| error.error);
+
+			Self::deposit_event(Event::CalledAsOrganization { org, member: who, result });
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Keyless account of organization `org`
+	pub fn account_of(org: OrganizationId) -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating(org)
+	}
+
+	/// Role of `who` in organization `org`, if a member
+	pub fn role_of(org: OrganizationId, who: &T::AccountId) -> Option<Role> {
+		Self::members(org, who)
+	}
+
+	/// Details of organization `org`, if `admin` is one of its admins
+	fn organization_for_admin(
+		org: OrganizationId,
+		admin: &T::AccountId,
+	) -> Result<OrganizationDetailsOf<T>, DispatchError> {
+		let details = Self::organizations(org).ok_or(Error::<T>::OrganizationNotFound)?;
+		ensure!(Self::members(org, admin) == Some(Role::Admin), Error::<T>::NotAuthorized);
+		Ok(details)
+	}
+
+	/// Check that `who` may make `call` as organization `org`
+	fn ensure_can_call(
+		org: OrganizationId,
+		details: &OrganizationDetailsOf<T>,
+		who: &T::AccountId,
+		call: &<T as Config>::RuntimeCall,
+	) -> DispatchResult {
+		if let Some(approver) = &details.approver {
+			if T::HighValueCalls::contains(call) {
+				ensure!(approver == who, Error::<T>::ApprovalRequired);
+				return Ok(());
+			}
+		}
+
+		match Self::members(org, who).ok_or(Error::<T>::NotMember)? {
+			Role::Admin => Ok(()),
+			Role::Issuer if T::IssuerCalls::contains(call) => Ok(()),
+			Role::Issuer | Role::Viewer => Err(Error::<T>::NotAuthorized.into()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame::testing_prelude::*;
+	use polkadot_sdk::sp_runtime::AccountId32;
+
+	// Configure a mock runtime to test the pallet
+	construct_runtime!(
+		pub enum Test {
+			System: frame_system,
+			Organizations: crate,
+		}
+	);
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = MockBlock<Test>;
+		// Wide enough for the accounts of different organizations to differ
+		type AccountId = AccountId32;
+		type Lookup = IdentityLookup<AccountId32>;
+	}
+
+	parameter_types! {
+		pub const OrganizationsPalletId: PalletId = PalletId(*b"ff/orgs_");
+	}
+
+	/// Issuers may make plain remarks
+	pub struct Remarks;
+	impl Contains<RuntimeCall> for Remarks {
+		fn contains(call: &RuntimeCall) -> bool {
+			matches!(call, RuntimeCall::System(frame_system::Call::remark { .. }))
+		}
+	}
+
+	/// Remarks with events are high-value
+	pub struct RemarksWithEvent;
+	impl Contains<RuntimeCall> for RemarksWithEvent {
+		fn contains(call: &RuntimeCall) -> bool {
+			matches!(call, RuntimeCall::System(frame_system::Call::remark_with_event { .. }))
+		}
+	}
+
+	impl Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type RuntimeCall = RuntimeCall;
+		type PalletId = OrganizationsPalletId;
+		type MaxMembers = ConstU32<3>;
+		type IssuerCalls = Remarks;
+		type HighValueCalls = RemarksWithEvent;
+	}
+
+	const ADMIN: AccountId32 = AccountId32::new([1; 32]);
+	const ISSUER: AccountId32 = AccountId32::new([2; 32]);
+	const VIEWER: AccountId32 = AccountId32::new([3; 32]);
+	const OUTSIDER: AccountId32 = AccountId32::new([4; 32]);
+	const MULTISIG: AccountId32 = AccountId32::new([5; 32]);
+
+	// Build genesis storage according to the mock runtime
+	pub fn new_test_ext() -> TestExternalities {
+		let mut ext: TestExternalities =
+			frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into();
+		// Events are only recorded after the genesis block
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	// Create organization 0 with an admin, an issuer and a viewer
+	fn setup() {
+		assert_ok!(Organizations::create_organization(RuntimeOrigin::signed(ADMIN), b"Acme".to_vec()));
+		assert_ok!(Organizations::add_member(RuntimeOrigin::signed(ADMIN), 0, ISSUER, Role::Issuer));
+		assert_ok!(Organizations::add_member(RuntimeOrigin::signed(ADMIN), 0, VIEWER, Role::Viewer));
+	}
+
+	fn remark() -> Box<RuntimeCall> {
+		Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![] }))
+	}
+
+	fn remark_with_event() -> Box<RuntimeCall> {
+		Box::new(RuntimeCall::System(frame_system::Call::remark_with_event { remark: vec![1] }))
+	}
+
+	#[test]
+	fn test_create_organization() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				Organizations::create_organization(RuntimeOrigin::signed(ADMIN), vec![b'x'; 129]),
+				Error::<Test>::NameTooLong
+			);
+			assert_ok!(Organizations::create_organization(RuntimeOrigin::signed(ADMIN), b"Acme".to_vec()));
+			assert_ok!(Organizations::create_organization(RuntimeOrigin::signed(ADMIN), b"Uni".to_vec()));
+
+			let details = Organizations::organizations(0).unwrap();
+			assert_eq!(details.account, Organizations::account_of(0));
+			assert_ne!(Organizations::account_of(0), Organizations::account_of(1));
+			assert_eq!((details.members, details.admins), (1, 1));
+			assert_eq!(Organizations::role_of(0, &ADMIN), Some(Role::Admin));
+			assert_eq!(Organizations::next_organization_id(), 2);
+			System::assert_has_event(
+				Event::OrganizationCreated { org: 0, account: details.account, creator: ADMIN }.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn test_membership_management() {
+		new_test_ext().execute_with(|| {
+			setup();
+			System::assert_last_event(
+				Event::MemberAdded { org: 0, member: VIEWER, role: Role::Viewer, by: ADMIN }.into(),
+			);
+
+			// Only admins manage members
+			assert_noop!(
+				Organizations::add_member(RuntimeOrigin::signed(ISSUER), 0, OUTSIDER, Role::Viewer),
+				Error::<Test>::NotAuthorized
+			);
+			assert_noop!(
+				Organizations::add_member(RuntimeOrigin::signed(ADMIN), 0, ISSUER, Role::Viewer),
+				Error::<Test>::AlreadyMember
+			);
+			assert_noop!(
+				Organizations::add_member(RuntimeOrigin::signed(ADMIN), 0, OUTSIDER, Role::Viewer),
+				Error::<Test>::TooManyMembers
+			);
+			assert_noop!(
+				Organizations::add_member(RuntimeOrigin::signed(ADMIN), 1, OUTSIDER, Role::Viewer),
+				Error::<Test>::OrganizationNotFound
+			);
+
+			assert_ok!(Organizations::set_role(RuntimeOrigin::signed(ADMIN), 0, VIEWER, Role::Issuer));
+			assert_eq!(Organizations::role_of(0, &VIEWER), Some(Role::Issuer));
+			System::assert_last_event(
+				Event::MemberRoleChanged { org: 0, member: VIEWER, role: Role::Issuer, by: ADMIN }
+					.into(),
+			);
+
+			// Members can leave, but only admins remove others
+			assert_noop!(
+				Organizations::remove_member(RuntimeOrigin::signed(ISSUER), 0, VIEWER),
+				Error::<Test>::NotAuthorized
+			);
+			assert_ok!(Organizations::remove_member(RuntimeOrigin::signed(ISSUER), 0, ISSUER));
+			assert_ok!(Organizations::remove_member(RuntimeOrigin::signed(ADMIN), 0, VIEWER));
+			System::assert_last_event(
+				Event::MemberRemoved { org: 0, member: VIEWER, by: ADMIN }.into(),
+			);
+			assert_eq!(Organizations::organizations(0).unwrap().members, 1);
+		});
+	}
+
+	#[test]
+	fn test_last_admin_is_kept() {
+		new_test_ext().execute_with(|| {
+			setup();
+
+			assert_noop!(
+				Organizations::set_role(RuntimeOrigin::signed(ADMIN), 0, ADMIN, Role::Viewer),
+				Error::<Test>::LastAdmin
+			);
+			assert_noop!(
+				Organizations::remove_member(RuntimeOrigin::signed(ADMIN), 0, ADMIN),
+				Error::<Test>::LastAdmin
+			);
+
+			// With a second admin, the first one can step down
+			assert_ok!(Organizations::set_role(RuntimeOrigin::signed(ADMIN), 0, ISSUER, Role::Admin));
+			assert_eq!(Organizations::organizations(0).unwrap().admins, 2);
+			assert_ok!(Organizations::remove_member(RuntimeOrigin::signed(ADMIN), 0, ADMIN));
+			assert_eq!(Organizations::organizations(0).unwrap().admins, 1);
+		});
+	}
+
+	#[test]
+	fn test_dispatch_by_role() {
+		new_test_ext().execute_with(|| {
+			setup();
+			let account = Organizations::account_of(0);
+
+			// Issuers make issuer calls, as the organization
+			assert_ok!(Organizations::dispatch_as_organization(RuntimeOrigin::signed(ISSUER), 0, remark()));
+			System::assert_last_event(
+				Event::CalledAsOrganization { org: 0, member: ISSUER, result: Ok(()) }.into(),
+			);
+			assert_noop!(
+				Organizations::dispatch_as_organization(RuntimeOrigin::signed(ISSUER), 0, remark_with_event()),
+				Error::<Test>::NotAuthorized
+			);
+
+			// Viewers and outsiders make no calls
+			assert_noop!(
+				Organizations::dispatch_as_organization(RuntimeOrigin::signed(VIEWER), 0, remark()),
+				Error::<Test>::NotAuthorized
+			);
+			assert_noop!(
+				Organizations::dispatch_as_organization(RuntimeOrigin::signed(OUTSIDER), 0, remark()),
+				Error::<Test>::NotMember
+			);
+
+			// Admins make any call
+			assert_ok!(Organizations::dispatch_as_organization(
+				RuntimeOrigin::signed(ADMIN),
+				0,
+				remark_with_event()
+			));
+			System::assert_has_event(
+				frame_system::Event::Remarked { sender: account, hash: BlakeTwo256::hash(&[1]) }
+					.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn test_failed_calls_are_reported() {
+		new_test_ext().execute_with(|| {
+			setup();
+
+			// Organizations are no root
+			let call = Box::new(RuntimeCall::System(frame_system::Call::set_heap_pages { pages: 1 }));
+			assert_ok!(Organizations::dispatch_as_organization(RuntimeOrigin::signed(ADMIN), 0, call));
+			System::assert_last_event(
+				Event::CalledAsOrganization {
+					org: 0,
+					member: ADMIN,
+					result: Err(DispatchError::BadOrigin),
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn test_high_value_calls_need_approver() {
+		new_test_ext().execute_with(|| {
+			setup();
+
+			assert_noop!(
+				Organizations::set_approver(RuntimeOrigin::signed(ISSUER), 0, Some(MULTISIG)),
+				Error::<Test>::NotAuthorized
+			);
+			assert_ok!(Organizations::set_approver(RuntimeOrigin::signed(ADMIN), 0, Some(MULTISIG)));
+			System::assert_last_event(
+				Event::ApproverChanged { org: 0, approver: Some(MULTISIG), by: ADMIN }.into(),
+			);
+
+			// Admins can no longer make high-value calls, nor replace the approver
+			assert_noop!(
+				Organizations::dispatch_as_organization(RuntimeOrigin::signed(ADMIN), 0, remark_with_event()),
+				Error::<Test>::ApprovalRequired
+			);
+			assert_noop!(
+				Organizations::set_approver(RuntimeOrigin::signed(ADMIN), 0, None),
+				Error::<Test>::ApprovalRequired
+			);
+			assert_ok!(Organizations::dispatch_as_organization(RuntimeOrigin::signed(ADMIN), 0, remark()));
+
+			// The approver can, without being a member, but makes no other calls
+			assert_ok!(Organizations::dispatch_as_organization(
+				RuntimeOrigin::signed(MULTISIG),
+				0,
+				remark_with_event()
+			));
+			assert_noop!(
+				Organizations::dispatch_as_organization(RuntimeOrigin::signed(MULTISIG), 0, remark()),
+				Error::<Test>::NotMember
+			);
+			assert_ok!(Organizations::set_approver(RuntimeOrigin::signed(MULTISIG), 0, None));
+		});
+	}
+}
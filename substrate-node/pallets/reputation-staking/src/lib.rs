@@ -0,0 +1,713 @@
+//! # FreelanceForge Reputation Staking Pallet
+//!
+//! Lets freelancers put their money where their credentials are: tokens bonded behind a
+//! credential make it weigh more in the owner's trust score, and are slashed if a challenge
+//! against the credential is upheld.
+//!
+//! ## Features
+//!
+//! - **Bonding**: Owners reserve tokens behind their own credentials, as checked through
+//!   [`Config::Credentials`]
+//! - **Trust score boost**: A credential counts `1 + boost` times in its owner's trust score,
+//!   where `boost` is one per [`Config::BoostUnit`] bonded, up to [`Config::MaxBoost`]
+//! - **Unbonding period**: Unbonded stake stops boosting at once, but stays reserved and
+//!   slashable for [`Config::UnbondingPeriod`] blocks
+//! - **Slashing**: When a challenge is upheld, the whole stake (bonded and unbonding) is
+//!   slashed; [`Config::ChallengerReward`] of it goes to the challenger and the rest is burned
+//!
+//! ## Storage
+//!
+//! - `Stakes`: Maps credential IDs to the [`StakeInfo`] (staker, bonded and unbonding
+//!   amounts, unlock block) backing them
+//! - `StakedCredentials`: Maps accounts to the credentials they have stake behind
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame::prelude::*;
+use pallet_freelance_credentials::{CredentialRegistry, CredentialStakes};
+use polkadot_sdk::{
+	frame_support::traits::{BalanceStatus, Currency, ReservableCurrency},
+	polkadot_sdk_frame as frame,
+	sp_runtime::{
+		traits::{CheckedDiv, Saturating, Zero},
+		Perbill, SaturatedConversion,
+	},
+};
+
+// Re-export all pallet parts for runtime integration
+pub use pallet::*;
+
+/// Balance of the currency stakes are bonded in
+pub type BalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as polkadot_sdk::frame_system::Config>::AccountId,
+>>::Balance;
+
+/// Stake backing a credential
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct StakeInfo<AccountId, Balance, BlockNumber> {
+	/// Account the stake is reserved from; the credential's owner when it was bonded
+	pub staker: AccountId,
+	/// Amount boosting the credential
+	pub bonded: Balance,
+	/// Amount waiting for `unlock_at` to be withdrawn, still slashable
+	pub unbonding: Balance,
+	/// Block from which the unbonding amount can be withdrawn
+	pub unlock_at: BlockNumber,
+}
+
+/// [`StakeInfo`] specialised for a runtime
+pub type StakeInfoOf<T> = StakeInfo<
+	<T as polkadot_sdk::frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	BlockNumberFor<T>,
+>;
+
+/// Slashing of the stake behind credentials, for pallets judging challenges
+pub trait StakeSlashing<AccountId, Hash> {
+	/// Slash the whole stake behind `credential_id`, rewarding `challenger` with part of it
+	fn slash(credential_id: &Hash, challenger: &AccountId);
+}
+
+/// No stakes: nothing to slash
+impl<AccountId, Hash> StakeSlashing<AccountId, Hash> for () {
+	fn slash(_credential_id: &Hash, _challenger: &AccountId) {}
+}
+
+#[frame::pallet]
+pub mod pallet {
+	use super::*;
+
+	/// Configuration trait for this pallet
+	#[pallet::config]
+	pub trait Config: polkadot_sdk::frame_system::Config {
+		/// The overarching runtime event type
+		type RuntimeEvent: From<Event<Self>>
+			+ IsType<<Self as polkadot_sdk::frame_system::Config>::RuntimeEvent>;
+
+		/// Currency stakes are bonded in
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Credentials stakes are bonded behind
+		type Credentials: CredentialRegistry<Self::AccountId, Self::Hash>;
+
+		/// Origin allowed to slash stakes when upholding a challenge (e.g. governance)
+		type SlashOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Minimum bonded amount behind a credential
+		#[pallet::constant]
+		type MinStake: Get<BalanceOf<Self>>;
+
+		/// Bonded amount adding one to a credential's weight in trust scores
+		#[pallet::constant]
+		type BoostUnit: Get<BalanceOf<Self>>;
+
+		/// Maximum extra weight of a credential in trust scores
+		#[pallet::constant]
+		type MaxBoost: Get<u32>;
+
+		/// Number of blocks unbonded stake stays reserved and slashable
+		#[pallet::constant]
+		type UnbondingPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Share of a slashed stake paid to the challenger; the rest is burned
+		#[pallet::constant]
+		type ChallengerReward: Get<Perbill>;
+
+		/// Maximum number of credentials an account can have stake behind
+		#[pallet::constant]
+		type MaxStakedCredentials: Get<u32>;
+	}
+
+	/// The pallet struct
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Storage map of the stake backing credentials
+	/// Maps credential_id -> StakeInfo (staker, bonded, unbonding, unlock_at)
+	#[pallet::storage]
+	#[pallet::getter(fn stakes)]
+	pub type Stakes<T: Config> = StorageMap<_, Identity, T::Hash, StakeInfoOf<T>, OptionQuery>;
+
+	/// Storage map of the credentials accounts have stake behind
+	/// Maps account_id -> credential IDs (max `MaxStakedCredentials`)
+	#[pallet::storage]
+	#[pallet::getter(fn staked_credentials)]
+	pub type StakedCredentials<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<T::Hash, T::MaxStakedCredentials>,
+		ValueQuery,
+	>;
+
+	/// Events emitted by the pallet
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Stake was bonded behind a credential
+		/// [credential_id, staker, amount, bonded]
+		Bonded {
+			credential_id: T::Hash,
+			staker: T::AccountId,
+			amount: BalanceOf<T>,
+			bonded: BalanceOf<T>,
+		},
+
+		/// Stake started unbonding, and no longer boosts the credential
+		/// [credential_id, staker, amount, unlock_at]
+		Unbonding {
+			credential_id: T::Hash,
+			staker: T::AccountId,
+			amount: BalanceOf<T>,
+			unlock_at: BlockNumberFor<T>,
+		},
+
+		/// Unbonded stake was returned to the staker
+		/// [credential_id, staker, amount]
+		Withdrawn { credential_id: T::Hash, staker: T::AccountId, amount: BalanceOf<T> },
+
+		/// The stake behind a credential was slashed after a challenge was upheld
+		/// [credential_id, staker, challenger, amount, reward]
+		Slashed {
+			credential_id: T::Hash,
+			staker: T::AccountId,
+			challenger: T::AccountId,
+			amount: BalanceOf<T>,
+			reward: BalanceOf<T>,
+		},
+	}
+
+	/// Errors that can occur when calling pallet extrinsics
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The credential does not exist
+		CredentialNotFound,
+		/// The caller does not own the credential
+		NotOwner,
+		/// Another account, e.g. a previous owner, still has stake behind the credential
+		StakedByAnother,
+		/// The amount is zero
+		ZeroAmount,
+		/// The bonded amount would be below `MinStake`
+		BelowMinimum,
+		/// The caller cannot reserve the amount
+		InsufficientBalance,
+		/// The caller has no stake behind the credential
+		NoStake,
+		/// The amount exceeds the bonded stake
+		InsufficientBonded,
+		/// No stake is unbonding
+		NothingUnbonding,
+		/// The unbonding period has not ended
+		StillUnbonding,
+		/// The caller already has stake behind `MaxStakedCredentials` credentials
+		TooManyStakes,
+	}
+
+	/// Dispatchable extrinsics (functions) that can be called by users
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Bond stake behind one of the caller's credentials
+		///
+		/// Parameters:
+		/// - `credential_id`: Credential to back
+		/// - `amount`: Amount to reserve, on top of any stake already bonded
+		///
+		/// Emits:
+		/// - `Bonded` event with the credential ID, staker, amount and total bonded
+		///
+		/// Errors:
+		/// - `ZeroAmount`: If the amount is zero
+		/// - `CredentialNotFound`: If the credential does not exist
+		/// - `NotOwner`: If the caller does not own the credential
+		/// - `StakedByAnother`: If another account still has stake behind the credential
+		/// - `BelowMinimum`: If the total bonded would be below `MinStake`
+		/// - `TooManyStakes`: If the caller backs `MaxStakedCredentials` credentials already
+		/// - `InsufficientBalance`: If the caller cannot reserve the amount
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(4, 3) + Weight::from_parts(40_000, 0))]
+		pub fn bond(
+			origin: OriginFor<T>,
+			credential_id: T::Hash,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let staker = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			let owner =
+				T::Credentials::owner(&credential_id).ok_or(Error::<T>::CredentialNotFound)?;
+			ensure!(owner == staker, Error::<T>::NotOwner);
+
+			let existing = Self::stakes(credential_id);
+			let mut stake = match existing.clone() {
+				Some(stake) => {
+					ensure!(stake.staker == staker, Error::<T>::StakedByAnother);
+					stake
+				},
+				None => StakeInfo {
+					staker: staker.clone(),
+					bonded: Zero::zero(),
+					unbonding: Zero::zero(),
+					unlock_at: Zero::zero(),
+				},
+			};
+			stake.bonded = stake.bonded.saturating_add(amount);
+			ensure!(stake.bonded >= T::MinStake::get(), Error::<T>::BelowMinimum);
+
+			if existing.is_none() {
+				StakedCredentials::<T>::try_mutate(&staker, |credentials| {
+					credentials.try_push(credential_id)
+				})
+				.map_err(|_| Error::<T>::TooManyStakes)?;
+			}
+			T::Currency::reserve(&staker, amount).map_err(|_| Error::<T>::InsufficientBalance)?;
+
+			let bonded = stake.bonded;
+			Stakes::<T>::insert(credential_id, stake);
+
+			Self::deposit_event(Event::Bonded { credential_id, staker, amount, bonded });
+
+			Ok(())
+		}
+
+		/// Start unbonding stake behind a credential
+		///
+		/// The amount stops boosting the credential at once, and can be withdrawn after
+		/// `UnbondingPeriod` blocks. Unbonding more stake restarts the period for all of it.
+		///
+		/// Parameters:
+		/// - `credential_id`: Credential backed by the caller
+		/// - `amount`: Amount to unbond
+		///
+		/// Emits:
+		/// - `Unbonding` event with the credential ID, staker, amount and unlock block
+		///
+		/// Errors:
+		/// - `ZeroAmount`: If the amount is zero
+		/// - `NoStake`: If the caller has no stake behind the credential
+		/// - `InsufficientBonded`: If the amount exceeds the bonded stake
+		/// - `BelowMinimum`: If the remaining bonded stake would be below `MinStake`, but
+		///   not zero
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1) + Weight::from_parts(25_000, 0))]
+		pub fn unbond(
+			origin: OriginFor<T>,
+			credential_id: T::Hash,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let staker = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			let mut stake = Self::stake_of(&credential_id, &staker)?;
+
+			ensure!(amount <= stake.bonded, Error::<T>::InsufficientBonded);
+			stake.bonded -= amount;
+			ensure!(
+				stake.bonded.is_zero() || stake.bonded >= T::MinStake::get(),
+				Error::<T>::BelowMinimum
+			);
+			stake.unbonding = stake.unbonding.saturating_add(amount);
+			let unlock_at = polkadot_sdk::frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::UnbondingPeriod::get());
+			stake.unlock_at = unlock_at;
+			Stakes::<T>::insert(credential_id, stake);
+
+			Self::deposit_event(Event::Unbonding { credential_id, staker, amount, unlock_at });
+
+			Ok(())
+		}
+
+		/// Withdraw stake whose unbonding period ended
+		///
+		/// Parameters:
+		/// - `credential_id`: Credential backed by the caller
+		///
+		/// Emits:
+		/// - `Withdrawn` event with the credential ID, staker and amount
+		///
+		/// Errors:
+		/// - `NoStake`: If the caller has no stake behind the credential
+		/// - `NothingUnbonding`: If no stake is unbonding
+		/// - `StillUnbonding`: If the unbonding period has not ended
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 3) + Weight::from_parts(30_000, 0))]
+		pub fn withdraw_unbonded(origin: OriginFor<T>, credential_id: T::Hash) -> DispatchResult {
+			let staker = ensure_signed(origin)?;
+			let mut stake = Self::stake_of(&credential_id, &staker)?;
+
+			ensure!(!stake.unbonding.is_zero(), Error::<T>::NothingUnbonding);
+			ensure!(
+				polkadot_sdk::frame_system::Pallet::<T>::block_number() >= stake.unlock_at,
+				Error::<T>::StillUnbonding
+			);
+
+			let amount = core::mem::replace(&mut stake.unbonding, Zero::zero());
+			T::Currency::unreserve(&staker, amount);
+			if stake.bonded.is_zero() {
+				Self::remove_stake(&credential_id, &staker);
+			} else {
+				Stakes::<T>::insert(credential_id, stake);
+			}
+
+			Self::deposit_event(Event::Withdrawn { credential_id, staker, amount });
+
+			Ok(())
+		}
+
+		/// Slash the stake behind a credential after upholding a challenge against it
+		///
+		/// Parameters:
+		/// - `credential_id`: Credential whose challenge was upheld
+		/// - `challenger`: Account rewarded with `ChallengerReward` of the stake
+		///
+		/// Emits:
+		/// - `Slashed` event with the credential ID, staker, challenger, amount and reward
+		///
+		/// Errors:
+		/// - `NoStake`: If no stake backs the credential
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(4, 4) + Weight::from_parts(50_000, 0))]
+		pub fn slash_stake(
+			origin: OriginFor<T>,
+			credential_id: T::Hash,
+			challenger: T::AccountId,
+		) -> DispatchResult {
+			T::SlashOrigin::ensure_origin(origin)?;
+			Self::do_slash(&credential_id, &challenger)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Stake of `staker` behind `credential_id`
+	fn stake_of(
+		credential_id: &T::Hash,
+		staker: &T::AccountId,
+	) -> Result<StakeInfoOf<T>, Error<T>> {
+		Self::stakes(credential_id)
+			.filter(|stake| stake.staker == *staker)
+			.ok_or(Error::<T>::NoStake)
+	}
+
+	/// Bonded amount boosting `credential_id` while owned by `owner`
+	///
+	/// Zero when the credential changed owner since the stake was bonded.
+	pub fn bonded_behind(credential_id: &T::Hash, owner: &T::AccountId) -> BalanceOf<T> {
+		Self::stakes(credential_id)
+			.filter(|stake| stake.staker == *owner)
+			.map_or_else(Zero::zero, |stake| stake.bonded)
+	}
+
+	/// Slash the whole stake behind `credential_id`, rewarding `challenger`
+	pub fn do_slash(credential_id: &T::Hash, challenger: &T::AccountId) -> DispatchResult {
+		let stake = Self::stakes(credential_id).ok_or(Error::<T>::NoStake)?;
+		let amount = stake.bonded.saturating_add(stake.unbonding);
+
+		let reward = T::ChallengerReward::get() * amount;
+		// A reward too small to create the challenger's account is burned instead
+		let reward = match T::Currency::repatriate_reserved(
+			&stake.staker,
+			challenger,
+			reward,
+			BalanceStatus::Free,
+		) {
+			Ok(unmoved) => reward.saturating_sub(unmoved),
+			Err(_) => Zero::zero(),
+		};
+		// Dropping the imbalance burns the rest
+		let _ = T::Currency::slash_reserved(&stake.staker, amount.saturating_sub(reward));
+		Self::remove_stake(credential_id, &stake.staker);
+
+		Self::deposit_event(Event::Slashed {
+			credential_id: *credential_id,
+			staker: stake.staker,
+			challenger: challenger.clone(),
+			amount,
+			reward,
+		});
+
+		Ok(())
+	}
+
+	/// Forget the stake of `staker` behind `credential_id`
+	fn remove_stake(credential_id: &T::Hash, staker: &T::AccountId) {
+		Stakes::<T>::remove(credential_id);
+		StakedCredentials::<T>::mutate_exists(staker, |entry| {
+			if let Some(credentials) = entry {
+				credentials.retain(|id| id != credential_id);
+				if credentials.is_empty() {
+					*entry = None;
+				}
+			}
+		});
+	}
+}
+
+impl<T: Config> CredentialStakes<T::AccountId, T::Hash> for Pallet<T> {
+	fn boost(credential_id: &T::Hash, owner: &T::AccountId) -> u32 {
+		let bonded = Self::bonded_behind(credential_id, owner);
+		bonded
+			.checked_div(&T::BoostUnit::get())
+			.map_or(0, |units| units.saturated_into::<u32>())
+			.min(T::MaxBoost::get())
+	}
+}
+
+impl<T: Config> StakeSlashing<T::AccountId, T::Hash> for Pallet<T> {
+	fn slash(credential_id: &T::Hash, challenger: &T::AccountId) {
+		// Credentials without stake have nothing to slash
+		let _ = Self::do_slash(credential_id, challenger);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame::testing_prelude::*;
+	use polkadot_sdk::{pallet_balances, sp_runtime::traits::Hash as _};
+
+	// Configure a mock runtime to test the pallet
+	construct_runtime!(
+		pub enum Test {
+			System: frame_system,
+			Balances: pallet_balances,
+			ReputationStaking: crate,
+		}
+	);
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = MockBlock<Test>;
+		type AccountId = u64;
+		type AccountData = pallet_balances::AccountData<u64>;
+	}
+
+	#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+	impl pallet_balances::Config for Test {
+		type AccountStore = System;
+	}
+
+	type Hash = <Test as frame_system::Config>::Hash;
+
+	parameter_types! {
+		// Owners of the credentials of the mock runtime
+		pub static Owners: Vec<(Hash, u64)> = vec![];
+		pub const ChallengerReward: Perbill = Perbill::from_percent(40);
+	}
+
+	impl Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type Currency = Balances;
+		type Credentials = MockCredentials;
+		type SlashOrigin = frame_system::EnsureRoot<u64>;
+		type MinStake = ConstU64<10>;
+		type BoostUnit = ConstU64<100>;
+		type MaxBoost = ConstU32<3>;
+		type UnbondingPeriod = ConstU64<5>;
+		type ChallengerReward = ChallengerReward;
+		type MaxStakedCredentials = ConstU32<2>;
+	}
+
+	pub struct MockCredentials;
+
+	impl CredentialRegistry<u64, Hash> for MockCredentials {
+		fn owner(credential_id: &Hash) -> Option<u64> {
+			Owners::get().iter().find(|(id, _)| id == credential_id).map(|(_, owner)| *owner)
+		}
+	}
+
+	// ID of the `n`th credential, owned by account 1 unless moved with `Owners`
+	fn credential(n: u8) -> Hash {
+		BlakeTwo256::hash(&[n])
+	}
+
+	// Build genesis storage according to the mock runtime
+	pub fn new_test_ext() -> TestExternalities {
+		let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(1, 1_000), (2, 100)],
+			..Default::default()
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+		let mut ext: TestExternalities = storage.into();
+		ext.execute_with(|| {
+			// Events are only recorded after the genesis block
+			System::set_block_number(1);
+			Owners::set((0..4).map(|n| (credential(n), 1)).collect());
+		});
+		ext
+	}
+
+	#[test]
+	fn test_bond() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(ReputationStaking::bond(RuntimeOrigin::signed(1), credential(0), 150));
+			System::assert_last_event(
+				Event::Bonded { credential_id: credential(0), staker: 1, amount: 150, bonded: 150 }
+					.into(),
+			);
+			assert_ok!(ReputationStaking::bond(RuntimeOrigin::signed(1), credential(0), 50));
+			assert_eq!(ReputationStaking::stakes(credential(0)).unwrap().bonded, 200);
+			assert_eq!(Balances::reserved_balance(1), 200);
+			assert_eq!(ReputationStaking::staked_credentials(1).into_inner(), vec![credential(0)]);
+
+			assert_noop!(
+				ReputationStaking::bond(RuntimeOrigin::signed(2), credential(1), 50),
+				Error::<Test>::NotOwner
+			);
+			assert_noop!(
+				ReputationStaking::bond(RuntimeOrigin::signed(1), credential(9), 50),
+				Error::<Test>::CredentialNotFound
+			);
+			assert_noop!(
+				ReputationStaking::bond(RuntimeOrigin::signed(1), credential(1), 0),
+				Error::<Test>::ZeroAmount
+			);
+			assert_noop!(
+				ReputationStaking::bond(RuntimeOrigin::signed(1), credential(1), 9),
+				Error::<Test>::BelowMinimum
+			);
+			assert_noop!(
+				ReputationStaking::bond(RuntimeOrigin::signed(1), credential(1), 801),
+				Error::<Test>::InsufficientBalance
+			);
+			assert_ok!(ReputationStaking::bond(RuntimeOrigin::signed(1), credential(1), 10));
+			assert_noop!(
+				ReputationStaking::bond(RuntimeOrigin::signed(1), credential(2), 10),
+				Error::<Test>::TooManyStakes
+			);
+		});
+	}
+
+	#[test]
+	fn test_boost() {
+		new_test_ext().execute_with(|| {
+			let boost = |n: u8, owner: u64| {
+				<ReputationStaking as CredentialStakes<u64, Hash>>::boost(&credential(n), &owner)
+			};
+
+			assert_ok!(ReputationStaking::bond(RuntimeOrigin::signed(1), credential(0), 99));
+			assert_eq!(boost(0, 1), 0);
+			assert_ok!(ReputationStaking::bond(RuntimeOrigin::signed(1), credential(0), 151));
+			assert_eq!(boost(0, 1), 2);
+			// Capped at `MaxBoost`
+			assert_ok!(ReputationStaking::bond(RuntimeOrigin::signed(1), credential(0), 500));
+			assert_eq!(boost(0, 1), 3);
+			assert_eq!(boost(1, 1), 0);
+
+			// Unbonding stake stops boosting at once
+			assert_ok!(ReputationStaking::unbond(RuntimeOrigin::signed(1), credential(0), 650));
+			assert_eq!(boost(0, 1), 1);
+
+			// Stake bonded by a previous owner does not boost the credential
+			Owners::set(vec![(credential(0), 2)]);
+			assert_eq!(boost(0, 2), 0);
+			assert_eq!(ReputationStaking::bonded_behind(&credential(0), &2), 0);
+			assert_noop!(
+				ReputationStaking::bond(RuntimeOrigin::signed(2), credential(0), 10),
+				Error::<Test>::StakedByAnother
+			);
+		});
+	}
+
+	#[test]
+	fn test_unbond_and_withdraw() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(ReputationStaking::bond(RuntimeOrigin::signed(1), credential(0), 100));
+
+			assert_noop!(
+				ReputationStaking::unbond(RuntimeOrigin::signed(2), credential(0), 10),
+				Error::<Test>::NoStake
+			);
+			assert_noop!(
+				ReputationStaking::unbond(RuntimeOrigin::signed(1), credential(0), 101),
+				Error::<Test>::InsufficientBonded
+			);
+			assert_noop!(
+				ReputationStaking::unbond(RuntimeOrigin::signed(1), credential(0), 95),
+				Error::<Test>::BelowMinimum
+			);
+			assert_noop!(
+				ReputationStaking::withdraw_unbonded(RuntimeOrigin::signed(1), credential(0)),
+				Error::<Test>::NothingUnbonding
+			);
+
+			assert_ok!(ReputationStaking::unbond(RuntimeOrigin::signed(1), credential(0), 40));
+			System::assert_last_event(
+				Event::Unbonding {
+					credential_id: credential(0),
+					staker: 1,
+					amount: 40,
+					unlock_at: 6,
+				}
+				.into(),
+			);
+			assert_noop!(
+				ReputationStaking::withdraw_unbonded(RuntimeOrigin::signed(1), credential(0)),
+				Error::<Test>::StillUnbonding
+			);
+
+			System::set_block_number(6);
+			assert_ok!(ReputationStaking::withdraw_unbonded(
+				RuntimeOrigin::signed(1),
+				credential(0)
+			));
+			System::assert_last_event(
+				Event::Withdrawn { credential_id: credential(0), staker: 1, amount: 40 }.into(),
+			);
+			assert_eq!(Balances::reserved_balance(1), 60);
+
+			// Withdrawing the whole stake forgets it
+			assert_ok!(ReputationStaking::unbond(RuntimeOrigin::signed(1), credential(0), 60));
+			System::set_block_number(11);
+			assert_ok!(ReputationStaking::withdraw_unbonded(
+				RuntimeOrigin::signed(1),
+				credential(0)
+			));
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(ReputationStaking::stakes(credential(0)), None);
+			assert!(!StakedCredentials::<Test>::contains_key(1));
+		});
+	}
+
+	#[test]
+	fn test_slash() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(ReputationStaking::bond(RuntimeOrigin::signed(1), credential(0), 200));
+			assert_ok!(ReputationStaking::unbond(RuntimeOrigin::signed(1), credential(0), 50));
+			let issuance = Balances::total_issuance();
+
+			assert_noop!(
+				ReputationStaking::slash_stake(RuntimeOrigin::signed(2), credential(0), 2),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				ReputationStaking::slash_stake(RuntimeOrigin::root(), credential(1), 2),
+				Error::<Test>::NoStake
+			);
+
+			// Unbonding stake is slashed too
+			assert_ok!(ReputationStaking::slash_stake(RuntimeOrigin::root(), credential(0), 2));
+			System::assert_last_event(
+				Event::Slashed {
+					credential_id: credential(0),
+					staker: 1,
+					challenger: 2,
+					amount: 200,
+					reward: 80,
+				}
+				.into(),
+			);
+			assert_eq!(Balances::free_balance(1), 800);
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::free_balance(2), 180);
+			assert_eq!(Balances::total_issuance(), issuance - 120);
+			assert_eq!(ReputationStaking::stakes(credential(0)), None);
+			assert!(!StakedCredentials::<Test>::contains_key(1));
+
+			// Through the trait, credentials without stake are ignored
+			<ReputationStaking as StakeSlashing<u64, Hash>>::slash(&credential(0), &2);
+			assert_eq!(Balances::free_balance(2), 180);
+		});
+	}
+}
@@ -0,0 +1,707 @@
+//! # FreelanceForge Skills Pallet
+//!
+//! A curated taxonomy of canonical skills, so that "Rust", "rust-lang" and "Rust programming"
+//! credentials all refer to the same skill.
+//!
+//! Every skill has a numeric [`SkillId`], a display name, any number of aliases and an
+//! optional parent category (e.g. "Rust" under "Programming Languages"). The taxonomy is
+//! curated by [`Config::CuratorOrigin`], typically governance or a curator account.
+//!
+//! ## Features
+//!
+//! - **Name resolution**: Names and aliases resolve to skill IDs ignoring ASCII case and
+//!   surrounding whitespace, see [`Pallet::resolve`]
+//! - **Categories**: Skills form a tree; parents can never become descendants of their own
+//!   children, and skills with children cannot be removed
+//! - **Registry trait**: Other pallets look skills up through [`SkillRegistry`] without
+//!   depending on this pallet's storage, and learn about removed skills through
+//!   [`OnSkillRemoved`]
+//!
+//! ## Storage
+//!
+//! - `Skills`: Maps skill IDs to [`SkillDetails`] records (name, parent, aliases, child count)
+//! - `SkillByName`: Maps normalized names and aliases to skill IDs
+//! - `NextSkillId`: ID given to the next skill
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame::prelude::*;
+use polkadot_sdk::polkadot_sdk_frame as frame;
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+// Re-export all pallet parts for runtime integration
+pub use pallet::*;
+
+/// Identifier of a canonical skill
+pub type SkillId = u32;
+
+/// Maximum length of skill names and aliases
+pub const MAX_SKILL_NAME_LEN: u32 = 64;
+
+/// Maximum number of aliases of a skill
+pub const MAX_ALIASES: u32 = 16;
+
+/// Name or alias of a skill (max 64 bytes)
+pub type SkillName = BoundedVec<u8, ConstU32<MAX_SKILL_NAME_LEN>>;
+
+/// On-chain record of a canonical skill
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SkillDetails {
+	/// Display name, as given by the curator
+	pub name: SkillName,
+	/// Category the skill belongs to, if any
+	pub parent: Option<SkillId>,
+	/// Alternative names resolving to this skill, as given by the curator
+	pub aliases: BoundedVec<SkillName, ConstU32<MAX_ALIASES>>,
+	/// Number of skills with this skill as parent
+	pub children: u32,
+}
+
+/// Lookup of canonical skills, implemented by this pallet
+pub trait SkillRegistry {
+	/// Whether `skill` is a canonical skill
+	fn skill_exists(skill: SkillId) -> bool;
+
+	/// Canonical skill named `name`, or with `name` as alias
+	fn resolve(name: &[u8]) -> Option<SkillId>;
+}
+
+/// No skill registry: every skill is unknown
+impl SkillRegistry for () {
+	fn skill_exists(_skill: SkillId) -> bool {
+		false
+	}
+
+	fn resolve(_name: &[u8]) -> Option<SkillId> {
+		None
+	}
+}
+
+/// Hook for pallets keeping data about skills, called when a skill is removed
+pub trait OnSkillRemoved {
+	/// `skill` was removed from the taxonomy
+	///
+	/// Called from `remove_skill`, whose weight covers one storage write for the hook, so
+	/// implementations should defer anything heavier (e.g. to `on_idle`).
+	fn on_skill_removed(skill: SkillId);
+}
+
+/// Nobody keeps data about skills
+impl OnSkillRemoved for () {
+	fn on_skill_removed(_skill: SkillId) {}
+}
+
+/// Lookup key of a name or alias: trimmed and ASCII lowercased
+pub fn normalize(name: &[u8]) -> Vec<u8> {
+	name.trim_ascii().to_ascii_lowercase()
+}
+
+#[frame::pallet]
+pub mod pallet {
+	use super::*;
+
+	/// Configuration trait for this pallet
+	#[pallet::config]
+	pub trait Config: polkadot_sdk::frame_system::Config {
+		/// The overarching runtime event type
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as polkadot_sdk::frame_system::Config>::RuntimeEvent>;
+
+		/// Origin allowed to curate the taxonomy (e.g. governance or a curator account)
+		type CuratorOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Told about removed skills, e.g. so data referencing them can be cleaned up
+		type OnSkillRemoved: OnSkillRemoved;
+	}
+
+	/// The pallet struct
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Storage map of canonical skills
+	/// Maps skill_id -> SkillDetails (name, parent, aliases, children)
+	#[pallet::storage]
+	#[pallet::getter(fn skills)]
+	pub type Skills<T: Config> = StorageMap<_, Twox64Concat, SkillId, SkillDetails, OptionQuery>;
+
+	/// Storage map of skill names and aliases
+	/// Maps normalized name or alias -> skill_id
+	#[pallet::storage]
+	#[pallet::getter(fn skill_by_name)]
+	pub type SkillByName<T: Config> =
+		StorageMap<_, Blake2_128Concat, SkillName, SkillId, OptionQuery>;
+
+	/// ID given to the next skill
+	#[pallet::storage]
+	#[pallet::getter(fn next_skill_id)]
+	pub type NextSkillId<T: Config> = StorageValue<_, SkillId, ValueQuery>;
+
+	/// Events emitted by the pallet
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A canonical skill was added
+		/// [skill, name, parent]
+		SkillAdded { skill: SkillId, name: SkillName, parent: Option<SkillId> },
+
+		/// A skill was renamed
+		/// [skill, name]
+		SkillRenamed { skill: SkillId, name: SkillName },
+
+		/// An alias was added to a skill
+		/// [skill, alias]
+		AliasAdded { skill: SkillId, alias: SkillName },
+
+		/// An alias was removed from a skill
+		/// [skill, alias]
+		AliasRemoved { skill: SkillId, alias: SkillName },
+
+		/// A skill moved to another category
+		/// [skill, parent]
+		ParentChanged { skill: SkillId, parent: Option<SkillId> },
+
+		/// A skill was removed, together with its aliases
+		/// [skill]
+		SkillRemoved { skill: SkillId },
+	}
+
+	/// Errors that can occur when calling pallet extrinsics
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The skill does not exist
+		SkillNotFound,
+		/// The parent skill does not exist
+		ParentNotFound,
+		/// The name or alias exceeds 64 bytes
+		NameTooLong,
+		/// The name or alias is empty or only whitespace
+		EmptyName,
+		/// The name or alias already resolves to a skill
+		NameTaken,
+		/// The skill already has the maximum number of aliases
+		TooManyAliases,
+		/// The alias does not belong to the skill
+		AliasNotFound,
+		/// The parent is the skill itself or one of its descendants
+		CyclicParent,
+		/// The skill is the parent of other skills
+		HasChildren,
+		/// All skill IDs are taken
+		NoSkillIdAvailable,
+	}
+
+	/// Dispatchable extrinsics (functions) that can be called by the curator
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Add a canonical skill
+		///
+		/// Parameters:
+		/// - `name`: Display name of the skill (max 64 bytes)
+		/// - `parent`: Category of the skill, if any
+		///
+		/// Emits:
+		/// - `SkillAdded` event with the skill ID, name and parent
+		///
+		/// Errors:
+		/// - `NameTooLong`, `EmptyName`: If the name is invalid
+		/// - `NameTaken`: If the name already resolves to a skill
+		/// - `ParentNotFound`: If the parent does not exist
+		/// - `NoSkillIdAvailable`: If all skill IDs are taken
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 4) + Weight::from_parts(30_000, 0))]
+		pub fn add_skill(
+			origin: OriginFor<T>,
+			name: Vec<u8>,
+			parent: Option<SkillId>,
+		) -> DispatchResult {
+			T::CuratorOrigin::ensure_origin(origin)?;
+			let name = Self::bounded_name(&name)?;
+			let key = Self::unused_key(&name)?;
+
+			let skill = NextSkillId::<T>::get();
+			let next = skill.checked_add(1).ok_or(Error::<T>::NoSkillIdAvailable)?;
+			if let Some(parent) = parent {
+				Skills::<T>::try_mutate(parent, |details| -> DispatchResult {
+					let details = details.as_mut().ok_or(Error::<T>::ParentNotFound)?;
+					details.children += 1;
+					Ok(())
+				})?;
+			}
+
+			Skills::<T>::insert(
+				skill,
+				SkillDetails { name: name.clone(), parent, aliases: Default::default(), children: 0 },
+			);
+			SkillByName::<T>::insert(key, skill);
+			NextSkillId::<T>::put(next);
+
+			Self::deposit_event(Event::SkillAdded { skill, name, parent });
+
+			Ok(())
+		}
+
+		/// Rename a skill
+		///
+		/// The old name stops resolving to the skill; keep it as an alias with `add_alias`
+		/// if credentials still use it.
+		///
+		/// Parameters:
+		/// - `skill`: Skill to rename
+		/// - `name`: New display name (max 64 bytes)
+		///
+		/// Emits:
+		/// - `SkillRenamed` event with the skill ID and name
+		///
+		/// Errors:
+		/// - `SkillNotFound`: If the skill does not exist
+		/// - `NameTooLong`, `EmptyName`: If the name is invalid
+		/// - `NameTaken`: If the name already resolves to another skill
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 3) + Weight::from_parts(25_000, 0))]
+		pub fn rename_skill(origin: OriginFor<T>, skill: SkillId, name: Vec<u8>) -> DispatchResult {
+			T::CuratorOrigin::ensure_origin(origin)?;
+			let mut details = Self::skills(skill).ok_or(Error::<T>::SkillNotFound)?;
+			let name = Self::bounded_name(&name)?;
+			let key = Self::key_of(&name);
+			let old_key = Self::key_of(&details.name);
+
+			// Changing only the case or spacing of the name keeps its key
+			if key != old_key {
+				ensure!(!SkillByName::<T>::contains_key(&key), Error::<T>::NameTaken);
+				SkillByName::<T>::remove(old_key);
+				SkillByName::<T>::insert(key, skill);
+			}
+			details.name = name.clone();
+			Skills::<T>::insert(skill, details);
+
+			Self::deposit_event(Event::SkillRenamed { skill, name });
+
+			Ok(())
+		}
+
+		/// Add an alternative name resolving to a skill
+		///
+		/// Parameters:
+		/// - `skill`: Skill to add the alias to
+		/// - `alias`: Alternative name (max 64 bytes)
+		///
+		/// Emits:
+		/// - `AliasAdded` event with the skill ID and alias
+		///
+		/// Errors:
+		/// - `SkillNotFound`: If the skill does not exist
+		/// - `NameTooLong`, `EmptyName`: If the alias is invalid
+		/// - `NameTaken`: If the alias already resolves to a skill
+		/// - `TooManyAliases`: If the skill already has 16 aliases
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2) + Weight::from_parts(25_000, 0))]
+		pub fn add_alias(origin: OriginFor<T>, skill: SkillId, alias: Vec<u8>) -> DispatchResult {
+			T::CuratorOrigin::ensure_origin(origin)?;
+			let mut details = Self::skills(skill).ok_or(Error::<T>::SkillNotFound)?;
+			let alias = Self::bounded_name(&alias)?;
+			let key = Self::unused_key(&alias)?;
+
+			details.aliases.try_push(alias.clone()).map_err(|_| Error::<T>::TooManyAliases)?;
+			Skills::<T>::insert(skill, details);
+			SkillByName::<T>::insert(key, skill);
+
+			Self::deposit_event(Event::AliasAdded { skill, alias });
+
+			Ok(())
+		}
+
+		/// Remove an alternative name of a skill
+		///
+		/// Parameters:
+		/// - `skill`: Skill to remove the alias from
+		/// - `alias`: Alias to remove, matched ignoring case and surrounding whitespace
+		///
+		/// Emits:
+		/// - `AliasRemoved` event with the skill ID and alias as it was added
+		///
+		/// Errors:
+		/// - `SkillNotFound`: If the skill does not exist
+		/// - `AliasNotFound`: If the alias does not belong to the skill
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 2) + Weight::from_parts(25_000, 0))]
+		pub fn remove_alias(origin: OriginFor<T>, skill: SkillId, alias: Vec<u8>) -> DispatchResult {
+			T::CuratorOrigin::ensure_origin(origin)?;
+			let mut details = Self::skills(skill).ok_or(Error::<T>::SkillNotFound)?;
+			let key = normalize(&alias);
+
+			let index = details
+				.aliases
+				.iter()
+				.position(|existing| normalize(existing) == key)
+				.ok_or(Error::<T>::AliasNotFound)?;
+			let alias = details.aliases.remove(index);
+			Skills::<T>::insert(skill, details);
+			SkillByName::<T>::remove(Self::key_of(&alias));
+
+			Self::deposit_event(Event::AliasRemoved { skill, alias });
+
+			Ok(())
+		}
+
+		/// Move a skill to another category
+		///
+		/// Parameters:
+		/// - `skill`: Skill to move
+		/// - `parent`: New category, or `None` to make the skill a top-level category
+		///
+		/// Emits:
+		/// - `ParentChanged` event with the skill ID and parent
+		///
+		/// Errors:
+		/// - `SkillNotFound`: If the skill does not exist
+		/// - `ParentNotFound`: If the parent does not exist
+		/// - `CyclicParent`: If the parent is the skill itself or one of its descendants
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 3) + Weight::from_parts(30_000, 0))]
+		pub fn set_parent(
+			origin: OriginFor<T>,
+			skill: SkillId,
+			parent: Option<SkillId>,
+		) -> DispatchResult {
+			T::CuratorOrigin::ensure_origin(origin)?;
+			let mut details = Self::skills(skill).ok_or(Error::<T>::SkillNotFound)?;
+
+			if let Some(parent) = parent {
+				ensure!(Skills::<T>::contains_key(parent), Error::<T>::ParentNotFound);
+				ensure!(!Self::is_ancestor_or_self(skill, parent), Error::<T>::CyclicParent);
+			}
+
+			if details.parent != parent {
+				if let Some(old) = details.parent {
+					Skills::<T>::mutate(old, |old| {
+						if let Some(old) = old {
+							old.children = old.children.saturating_sub(1);
+						}
+					});
+				}
+				if let Some(new) = parent {
+					Skills::<T>::mutate(new, |new| {
+						if let Some(new) = new {
+							new.children += 1;
+						}
+					});
+				}
+				details.parent = parent;
+				Skills::<T>::insert(skill, details);
+			}
+
+			Self::deposit_event(Event::ParentChanged { skill, parent });
+
+			Ok(())
+		}
+
+		/// Remove a skill and its aliases
+		///
+		/// [`Config::OnSkillRemoved`] is told, e.g. so the credentials pallet unlinks the
+		/// credentials of the skill. Skill IDs are never reused, so anything still referencing
+		/// the skill points at no skill rather than a different one.
+		///
+		/// Parameters:
+		/// - `skill`: Skill to remove
+		///
+		/// Emits:
+		/// - `SkillRemoved` event with the skill ID
+		///
+		/// Errors:
+		/// - `SkillNotFound`: If the skill does not exist
+		/// - `HasChildren`: If other skills have the skill as parent
+		#[pallet::call_index(5)]
+		#[pallet::weight(
+			T::DbWeight::get().reads_writes(2, 4 + MAX_ALIASES as u64) + Weight::from_parts(40_000, 0)
+		)]
+		pub fn remove_skill(origin: OriginFor<T>, skill: SkillId) -> DispatchResult {
+			T::CuratorOrigin::ensure_origin(origin)?;
+			let details = Self::skills(skill).ok_or(Error::<T>::SkillNotFound)?;
+			ensure!(details.children == 0, Error::<T>::HasChildren);
+
+			if let Some(parent) = details.parent {
+				Skills::<T>::mutate(parent, |parent| {
+					if let Some(parent) = parent {
+						parent.children = parent.children.saturating_sub(1);
+					}
+				});
+			}
+			SkillByName::<T>::remove(Self::key_of(&details.name));
+			for alias in details.aliases.iter() {
+				SkillByName::<T>::remove(Self::key_of(alias));
+			}
+			Skills::<T>::remove(skill);
+			T::OnSkillRemoved::on_skill_removed(skill);
+
+			Self::deposit_event(Event::SkillRemoved { skill });
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Canonical skill named `name`, or with `name` as alias
+	///
+	/// Matching ignores ASCII case and surrounding whitespace.
+	pub fn resolve(name: &[u8]) -> Option<SkillId> {
+		let key: SkillName = normalize(name).try_into().ok()?;
+		Self::skill_by_name(key)
+	}
+
+	/// Whether `ancestor` is `skill` itself or one of its (transitive) parents
+	fn is_ancestor_or_self(ancestor: SkillId, skill: SkillId) -> bool {
+		let mut current = Some(skill);
+		while let Some(id) = current {
+			if id == ancestor {
+				return true;
+			}
+			current = Self::skills(id).and_then(|details| details.parent);
+		}
+		false
+	}
+
+	/// Trimmed `name`, if neither empty nor longer than 64 bytes
+	fn bounded_name(name: &[u8]) -> Result<SkillName, Error<T>> {
+		let name = name.trim_ascii();
+		ensure!(!name.is_empty(), Error::<T>::EmptyName);
+		name.to_vec().try_into().map_err(|_| Error::<T>::NameTooLong)
+	}
+
+	/// Lookup key of a valid name or alias
+	fn key_of(name: &SkillName) -> SkillName {
+		// Normalizing never makes a name longer
+		BoundedVec::truncate_from(normalize(name))
+	}
+
+	/// Lookup key of `name`, if it does not resolve to a skill yet
+	fn unused_key(name: &SkillName) -> Result<SkillName, Error<T>> {
+		let key = Self::key_of(name);
+		ensure!(!SkillByName::<T>::contains_key(&key), Error::<T>::NameTaken);
+		Ok(key)
+	}
+}
+
+impl<T: Config> SkillRegistry for Pallet<T> {
+	fn skill_exists(skill: SkillId) -> bool {
+		Skills::<T>::contains_key(skill)
+	}
+
+	fn resolve(name: &[u8]) -> Option<SkillId> {
+		Pallet::<T>::resolve(name)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame::testing_prelude::*;
+
+	// Configure a mock runtime to test the pallet
+	construct_runtime!(
+		pub enum Test {
+			System: frame_system,
+			Skills: crate,
+		}
+	);
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = MockBlock<Test>;
+		type AccountId = u64;
+	}
+
+	impl Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type CuratorOrigin = frame_system::EnsureRoot<u64>;
+		type OnSkillRemoved = ();
+	}
+
+	// Build genesis storage according to the mock runtime
+	pub fn new_test_ext() -> TestExternalities {
+		let mut ext: TestExternalities =
+			frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into();
+		// Events are only recorded after the genesis block
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	// Add "Programming" (0), "Rust" (1) under it and "Design" (2)
+	fn setup() {
+		assert_ok!(Skills::add_skill(RuntimeOrigin::root(), b"Programming".to_vec(), None));
+		assert_ok!(Skills::add_skill(RuntimeOrigin::root(), b"Rust".to_vec(), Some(0)));
+		assert_ok!(Skills::add_skill(RuntimeOrigin::root(), b"Design".to_vec(), None));
+	}
+
+	#[test]
+	fn test_add_skill() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				Skills::add_skill(RuntimeOrigin::signed(1), b"Rust".to_vec(), None),
+				DispatchError::BadOrigin
+			);
+			setup();
+			System::assert_has_event(
+				Event::SkillAdded { skill: 1, name: b"Rust".to_vec().try_into().unwrap(), parent: Some(0) }
+					.into(),
+			);
+
+			let details = Skills::skills(1).unwrap();
+			assert_eq!(details.name.to_vec(), b"Rust".to_vec());
+			assert_eq!(details.parent, Some(0));
+			assert_eq!(Skills::skills(0).unwrap().children, 1);
+			assert_eq!(Skills::next_skill_id(), 3);
+
+			assert_noop!(
+				Skills::add_skill(RuntimeOrigin::root(), b" rust ".to_vec(), None),
+				Error::<Test>::NameTaken
+			);
+			assert_noop!(
+				Skills::add_skill(RuntimeOrigin::root(), b"Go".to_vec(), Some(7)),
+				Error::<Test>::ParentNotFound
+			);
+			assert_noop!(
+				Skills::add_skill(RuntimeOrigin::root(), b"  ".to_vec(), None),
+				Error::<Test>::EmptyName
+			);
+			assert_noop!(
+				Skills::add_skill(RuntimeOrigin::root(), vec![b'x'; 65], None),
+				Error::<Test>::NameTooLong
+			);
+		});
+	}
+
+	#[test]
+	fn test_resolve_names_and_aliases() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_ok!(Skills::add_alias(RuntimeOrigin::root(), 1, b"rust-lang".to_vec()));
+
+			assert_eq!(Skills::resolve(b"Rust"), Some(1));
+			assert_eq!(Skills::resolve(b"  RUST "), Some(1));
+			assert_eq!(Skills::resolve(b"Rust-Lang"), Some(1));
+			assert_eq!(Skills::resolve(b"Go"), None);
+			assert_eq!(<Skills as SkillRegistry>::resolve(b"rust-lang"), Some(1));
+			assert!(<Skills as SkillRegistry>::skill_exists(2));
+			assert!(!<Skills as SkillRegistry>::skill_exists(3));
+
+			assert_noop!(
+				Skills::add_alias(RuntimeOrigin::root(), 2, b"RUST".to_vec()),
+				Error::<Test>::NameTaken
+			);
+
+			assert_ok!(Skills::remove_alias(RuntimeOrigin::root(), 1, b"RUST-LANG".to_vec()));
+			System::assert_last_event(
+				Event::AliasRemoved { skill: 1, alias: b"rust-lang".to_vec().try_into().unwrap() }
+					.into(),
+			);
+			assert_eq!(Skills::resolve(b"rust-lang"), None);
+			assert_noop!(
+				Skills::remove_alias(RuntimeOrigin::root(), 1, b"rust-lang".to_vec()),
+				Error::<Test>::AliasNotFound
+			);
+		});
+	}
+
+	#[test]
+	fn test_alias_limit() {
+		new_test_ext().execute_with(|| {
+			setup();
+			for i in 0..MAX_ALIASES {
+				assert_ok!(Skills::add_alias(RuntimeOrigin::root(), 1, format!("rs{}", i).into_bytes()));
+			}
+			assert_noop!(
+				Skills::add_alias(RuntimeOrigin::root(), 1, b"rs-extra".to_vec()),
+				Error::<Test>::TooManyAliases
+			);
+		});
+	}
+
+	#[test]
+	fn test_rename_skill() {
+		new_test_ext().execute_with(|| {
+			setup();
+
+			// Changing the case keeps the lookup key
+			assert_ok!(Skills::rename_skill(RuntimeOrigin::root(), 1, b"RUST".to_vec()));
+			assert_eq!(Skills::resolve(b"rust"), Some(1));
+
+			assert_ok!(Skills::rename_skill(RuntimeOrigin::root(), 1, b"Rust Programming".to_vec()));
+			assert_eq!(Skills::resolve(b"rust"), None);
+			assert_eq!(Skills::resolve(b"rust programming"), Some(1));
+			System::assert_last_event(
+				Event::SkillRenamed { skill: 1, name: b"Rust Programming".to_vec().try_into().unwrap() }
+					.into(),
+			);
+
+			assert_noop!(
+				Skills::rename_skill(RuntimeOrigin::root(), 1, b"design".to_vec()),
+				Error::<Test>::NameTaken
+			);
+			assert_noop!(
+				Skills::rename_skill(RuntimeOrigin::root(), 9, b"Go".to_vec()),
+				Error::<Test>::SkillNotFound
+			);
+		});
+	}
+
+	#[test]
+	fn test_set_parent() {
+		new_test_ext().execute_with(|| {
+			setup();
+
+			// A skill cannot be moved under itself or its descendants
+			assert_noop!(
+				Skills::set_parent(RuntimeOrigin::root(), 0, Some(0)),
+				Error::<Test>::CyclicParent
+			);
+			assert_noop!(
+				Skills::set_parent(RuntimeOrigin::root(), 0, Some(1)),
+				Error::<Test>::CyclicParent
+			);
+			assert_noop!(
+				Skills::set_parent(RuntimeOrigin::root(), 1, Some(9)),
+				Error::<Test>::ParentNotFound
+			);
+
+			assert_ok!(Skills::set_parent(RuntimeOrigin::root(), 1, Some(2)));
+			assert_eq!(Skills::skills(1).unwrap().parent, Some(2));
+			assert_eq!(Skills::skills(0).unwrap().children, 0);
+			assert_eq!(Skills::skills(2).unwrap().children, 1);
+			System::assert_last_event(Event::ParentChanged { skill: 1, parent: Some(2) }.into());
+
+			// Categories can be nested
+			assert_ok!(Skills::set_parent(RuntimeOrigin::root(), 2, Some(0)));
+			assert_noop!(
+				Skills::set_parent(RuntimeOrigin::root(), 0, Some(1)),
+				Error::<Test>::CyclicParent
+			);
+
+			assert_ok!(Skills::set_parent(RuntimeOrigin::root(), 1, None));
+			assert_eq!(Skills::skills(2).unwrap().children, 0);
+		});
+	}
+
+	#[test]
+	fn test_remove_skill() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_ok!(Skills::add_alias(RuntimeOrigin::root(), 1, b"rust-lang".to_vec()));
+
+			assert_noop!(Skills::remove_skill(RuntimeOrigin::root(), 0), Error::<Test>::HasChildren);
+
+			assert_ok!(Skills::remove_skill(RuntimeOrigin::root(), 1));
+			System::assert_last_event(Event::SkillRemoved { skill: 1 }.into());
+			assert_eq!(Skills::skills(1), None);
+			assert_eq!(Skills::resolve(b"rust"), None);
+			assert_eq!(Skills::resolve(b"rust-lang"), None);
+			assert_eq!(Skills::skills(0).unwrap().children, 0);
+
+			// IDs are not reused
+			assert_ok!(Skills::remove_skill(RuntimeOrigin::root(), 0));
+			assert_ok!(Skills::add_skill(RuntimeOrigin::root(), b"Rust".to_vec(), None));
+			assert_eq!(Skills::resolve(b"rust"), Some(3));
+		});
+	}
+}
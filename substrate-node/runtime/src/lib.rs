@@ -26,7 +26,18 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 extern crate alloc;
 
 use alloc::vec::Vec;
-use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{ConstBool, ConstU64, InstanceFilter},
+	weights::{
+		constants::{ExtrinsicBaseWeight, RocksDbWeight},
+		ConstantMultiplier, WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial,
+	},
+};
+use pallet_transaction_payment::{
+	FeeDetails, Multiplier, RuntimeDispatchInfo, TargetedFeeAdjustment,
+};
+use sp_runtime::{FixedPointNumber, Perquintill};
 use polkadot_sdk::{
 	polkadot_sdk_frame::{
 		self as frame,
@@ -35,36 +46,235 @@ use polkadot_sdk::{
 	},
 	*,
 };
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_consensus_grandpa::AuthorityId as GrandpaId;
 
 /// Provides getters for genesis configuration presets.
 pub mod genesis_config_presets {
 	use super::*;
 	use crate::{
-		interface::{Balance, MinimumBalance},
-		sp_keyring::Sr25519Keyring,
-		BalancesConfig, RuntimeGenesisConfig, SudoConfig,
+		currency::UNIT,
+		sp_keyring::{Ed25519Keyring, Sr25519Keyring},
+		AuraConfig, BalancesConfig, FreelanceCredentialsConfig, GrandpaConfig,
+		RuntimeGenesisConfig, SudoConfig,
 	};
 
-	use alloc::{vec, vec::Vec};
-	use serde_json::Value;
+	use alloc::{
+		string::{String, ToString},
+		vec,
+		vec::Vec,
+	};
+	use serde_json::{json, Value};
 
-	/// Returns a development genesis config preset.
-	pub fn development_config_genesis() -> Value {
-		let endowment = <MinimumBalance as Get<Balance>>::get().max(1) * 1000;
+	/// Metadata JSON of a credential in the frontend's full format
+	fn credential(
+		credential_type: &str,
+		name: &str,
+		description: &str,
+		issuer: &str,
+		timestamp: &str,
+		rating: Option<f64>,
+	) -> String {
+		let mut metadata = json!({
+			"credential_type": credential_type,
+			"name": name,
+			"description": description,
+			"issuer": issuer,
+			"timestamp": timestamp,
+			"visibility": "public",
+		});
+		if let Some(rating) = rating {
+			metadata["rating"] = json!(rating);
+		}
+		metadata.to_string()
+	}
+
+	/// Alice's portfolio: the frontend's sample Upwork import
+	fn alice_portfolio() -> Vec<String> {
+		vec![
+			credential(
+				"review",
+				"React.js Frontend Development",
+				"Built a modern e-commerce frontend using React.js and TypeScript",
+				"Upwork",
+				"2024-03-15T00:00:00Z",
+				Some(4.8),
+			),
+			credential(
+				"payment",
+				"Payment: E-commerce Frontend",
+				"$2500 from TechCorp Inc.",
+				"Upwork",
+				"2024-03-15T00:00:00Z",
+				None,
+			),
+			credential(
+				"review",
+				"Mobile App UI/UX Design",
+				"Designed user interface for iOS and Android mobile application",
+				"Upwork",
+				"2024-05-15T00:00:00Z",
+				Some(5.0),
+			),
+			credential(
+				"payment",
+				"Payment: Mobile App Design",
+				"$1800 from StartupXYZ",
+				"Upwork",
+				"2024-05-15T00:00:00Z",
+				None,
+			),
+			credential(
+				"skill",
+				"React.js Expert",
+				"Upwork skill test: React.js",
+				"Upwork",
+				"2024-01-05T00:00:00Z",
+				None,
+			),
+			credential(
+				"skill",
+				"TypeScript Advanced",
+				"Upwork skill test: TypeScript",
+				"Upwork",
+				"2024-01-05T00:00:00Z",
+				None,
+			),
+			credential(
+				"skill",
+				"Node.js Intermediate",
+				"Upwork skill test: Node.js",
+				"Upwork",
+				"2024-01-05T00:00:00Z",
+				None,
+			),
+		]
+	}
+
+	/// Bob's portfolio: the frontend's sample LinkedIn import
+	fn bob_portfolio() -> Vec<String> {
+		vec![
+			credential(
+				"skill",
+				"JavaScript",
+				"25 endorsements on LinkedIn",
+				"LinkedIn",
+				"2024-03-01T00:00:00Z",
+				None,
+			),
+			credential(
+				"skill",
+				"React",
+				"18 endorsements on LinkedIn",
+				"LinkedIn",
+				"2024-03-01T00:00:00Z",
+				None,
+			),
+			credential(
+				"skill",
+				"Project Management",
+				"12 endorsements on LinkedIn",
+				"LinkedIn",
+				"2024-03-01T00:00:00Z",
+				None,
+			),
+			credential(
+				"review",
+				"Recommendation from Sarah Johnson",
+				"CTO at TechCorp",
+				"LinkedIn",
+				"2024-03-20T00:00:00Z",
+				Some(5.0),
+			),
+			credential(
+				"review",
+				"Recommendation from Mike Chen",
+				"Product Manager",
+				"LinkedIn",
+				"2024-05-10T00:00:00Z",
+				Some(4.5),
+			),
+			credential(
+				"certification",
+				"AWS Certified Developer",
+				"Credential ID: AWS-DEV-2024-001",
+				"Amazon Web Services",
+				"2024-02-15T00:00:00Z",
+				None,
+			),
+			credential(
+				"certification",
+				"React Developer Certification",
+				"Credential ID: META-REACT-2024",
+				"Meta",
+				"2024-01-10T00:00:00Z",
+				None,
+			),
+		]
+	}
+
+	/// Genesis config with the sample portfolios and `authorities` as Aura and GRANDPA
+	/// authorities.
+	fn testnet_genesis(authorities: &[(Sr25519Keyring, Ed25519Keyring)]) -> Value {
+		let endowment = 100_000 * UNIT;
 		frame_support::build_struct_json_patch!(RuntimeGenesisConfig {
+			aura: AuraConfig {
+				authorities: authorities
+					.iter()
+					.map(|(aura, _)| AuraId::from(aura.public()))
+					.collect::<Vec<_>>(),
+			},
+			grandpa: GrandpaConfig {
+				authorities: authorities
+					.iter()
+					.map(|(_, grandpa)| (GrandpaId::from(grandpa.public()), 1))
+					.collect::<Vec<_>>(),
+			},
 			balances: BalancesConfig {
 				balances: Sr25519Keyring::iter()
 					.map(|a| (a.to_account_id(), endowment))
 					.collect::<Vec<_>>(),
 			},
 			sudo: SudoConfig { key: Some(Sr25519Keyring::Alice.to_account_id()) },
+			freelance_credentials: FreelanceCredentialsConfig {
+				credentials: alice_portfolio()
+					.into_iter()
+					.map(|metadata| (Sr25519Keyring::Alice.to_account_id(), metadata))
+					.chain(
+						bob_portfolio()
+							.into_iter()
+							.map(|metadata| (Sr25519Keyring::Bob.to_account_id(), metadata)),
+					)
+					.collect::<Vec<_>>(),
+				issuers: vec![
+					(Sr25519Keyring::Charlie.to_account_id(), "Upwork".to_string()),
+					(Sr25519Keyring::Dave.to_account_id(), "LinkedIn".to_string()),
+					(Sr25519Keyring::Eve.to_account_id(), "Amazon Web Services".to_string()),
+				],
+			},
 		})
 	}
 
+	/// Returns a development genesis config preset, with Alice as the only authority.
+	pub fn development_config_genesis() -> Value {
+		testnet_genesis(&[(Sr25519Keyring::Alice, Ed25519Keyring::Alice)])
+	}
+
+	/// Returns a local testnet genesis config preset, with Alice, Bob and Charlie as
+	/// authorities.
+	pub fn local_testnet_genesis() -> Value {
+		testnet_genesis(&[
+			(Sr25519Keyring::Alice, Ed25519Keyring::Alice),
+			(Sr25519Keyring::Bob, Ed25519Keyring::Bob),
+			(Sr25519Keyring::Charlie, Ed25519Keyring::Charlie),
+		])
+	}
+
 	/// Get the set of the available genesis config presets.
 	pub fn get_preset(id: &PresetId) -> Option<Vec<u8>> {
 		let patch = match id.as_ref() {
 			sp_genesis_builder::DEV_RUNTIME_PRESET => development_config_genesis(),
+			sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET => local_testnet_genesis(),
 			_ => return None,
 		};
 		Some(
@@ -76,7 +286,10 @@ pub mod genesis_config_presets {
 
 	/// List of supported presets.
 	pub fn preset_names() -> Vec<PresetId> {
-		vec![PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET)]
+		vec![
+			PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET),
+			PresetId::from(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET),
+		]
 	}
 }
 
@@ -86,13 +299,55 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: alloc::borrow::Cow::Borrowed("minimal-template-runtime"),
 	impl_name: alloc::borrow::Cow::Borrowed("minimal-template-runtime"),
 	authoring_version: 1,
-	spec_version: 0,
+	spec_version: 12,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 3,
 	system_version: 1,
 };
 
+/// Units of the native token.
+pub mod currency {
+	use crate::interface::Balance;
+
+	/// Number of decimals of the token.
+	pub const DECIMALS: u8 = 12;
+	/// One token.
+	pub const UNIT: Balance = 10u64.pow(DECIMALS as u32);
+	/// A thousandth of a token.
+	pub const MILLI_UNIT: Balance = UNIT / 1_000;
+	/// A millionth of a token.
+	pub const MICRO_UNIT: Balance = UNIT / 1_000_000;
+	/// Minimum balance of an account.
+	pub const EXISTENTIAL_DEPOSIT: Balance = MILLI_UNIT;
+}
+
+/// Target block time of the `aura` consensus mode, in milliseconds.
+///
+/// Also the minimum spacing of blocks produced by manual and instant seal, since every block
+/// must be in a later Aura slot than its parent.
+pub const SLOT_DURATION: u64 = 3000;
+
+/// Blocks per minute and hour at the target block time.
+pub mod time {
+	use crate::{interface::BlockNumber, SLOT_DURATION};
+
+	/// Blocks produced in a minute.
+	pub const MINUTES: BlockNumber = (60_000 / SLOT_DURATION) as BlockNumber;
+	/// Blocks produced in an hour.
+	pub const HOURS: BlockNumber = MINUTES * 60;
+}
+
+sp_runtime::impl_opaque_keys! {
+	/// Session keys of a block authoring validator.
+	pub struct SessionKeys {
+		/// Key used to author blocks.
+		pub aura: Aura,
+		/// Key used to vote on finality.
+		pub grandpa: Grandpa,
+	}
+}
+
 /// The version information used to identify this runtime when compiled natively.
 #[cfg(feature = "std")]
 pub fn native_version() -> NativeVersion {
@@ -115,9 +370,11 @@ type TxExtension = (
 	frame_system::CheckNonce<Runtime>,
 	// Checks that the weight is valid.
 	frame_system::CheckWeight<Runtime>,
-	// Ensures that the sender has enough funds to pay for the transaction
-	// and deducts the fee from the sender's account.
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	// Rejects credential mints of accounts exceeding their mint rate limits.
+	pallet_freelance_credentials::CheckMintRateLimit<Runtime>,
+	// Ensures that the sender, or the sender's sponsor for credential calls, has enough
+	// funds to pay for the transaction and deducts the fee from that account.
+	pallet_fee_sponsorship::ChargeSponsoredTransactionPayment<Runtime>,
 	// Reclaim the unused weight from the block using post dispatch information.
 	// It must be last in the pipeline in order to catch the refund in previous transaction
 	// extensions
@@ -170,10 +427,65 @@ mod runtime {
 	/// FreelanceForge credentials pallet for managing soulbound credential NFTs.
 	#[runtime::pallet_index(6)]
 	pub type FreelanceCredentials = pallet_freelance_credentials::Pallet<Runtime>;
+
+	/// Runs storage migrations that span multiple blocks.
+	#[runtime::pallet_index(7)]
+	pub type MultiBlockMigrations = pallet_migrations::Pallet<Runtime>;
+
+	/// `did:forge` DID documents of freelancers, clients and issuers.
+	#[runtime::pallet_index(8)]
+	pub type Did = pallet_did::Pallet<Runtime>;
+
+	/// Round-robin block authoring by the session key authorities.
+	#[runtime::pallet_index(9)]
+	pub type Aura = pallet_aura::Pallet<Runtime>;
+
+	/// GRANDPA finality of the blocks authored by Aura.
+	#[runtime::pallet_index(10)]
+	pub type Grandpa = pallet_grandpa::Pallet<Runtime>;
+
+	/// Sponsors paying the credential transaction fees of their users.
+	#[runtime::pallet_index(11)]
+	pub type FeeSponsorship = pallet_fee_sponsorship::Pallet<Runtime>;
+
+	/// Accounts controlled by a threshold of signatories, e.g. organization approvers.
+	#[runtime::pallet_index(12)]
+	pub type Multisig = pallet_multisig::Pallet<Runtime>;
+
+	/// Organization accounts acted as by their members, according to their roles.
+	#[runtime::pallet_index(13)]
+	pub type Organizations = pallet_organizations::Pallet<Runtime>;
+
+	/// Delegation of calls to proxy accounts, e.g. credentials-only browser keys.
+	#[runtime::pallet_index(14)]
+	pub type Proxy = pallet_proxy::Pallet<Runtime>;
+
+	/// Canonical skills referenced by skill credentials.
+	#[runtime::pallet_index(15)]
+	pub type Skills = pallet_skills::Pallet<Runtime>;
+
+	/// Jobs posted by clients, applied to with credentials and paid through escrow.
+	#[runtime::pallet_index(16)]
+	pub type Marketplace = pallet_marketplace::Pallet<Runtime>;
+
+	/// Stake bonded behind credentials, boosting trust scores and slashed on upheld challenges.
+	#[runtime::pallet_index(17)]
+	pub type ReputationStaking = pallet_reputation_staking::Pallet<Runtime>;
+
+	/// Challenges against credentials, judged by juries drawn from a bonded pool.
+	#[runtime::pallet_index(18)]
+	pub type Challenges = pallet_challenges::Pallet<Runtime>;
 }
 
 parameter_types! {
 	pub const Version: RuntimeVersion = VERSION;
+	// Leave a fifth of every block to regular transactions while migrations are running
+	pub MbmServiceWeight: Weight =
+		sp_runtime::Perbill::from_percent(80) *
+			<<Runtime as frame_system::Config>::BlockWeights as Get<
+				frame_system::limits::BlockWeights,
+			>>::get()
+			.max_block;
 }
 
 /// Implements the types required for the system pallet.
@@ -183,30 +495,100 @@ impl frame_system::Config for Runtime {
 	type Version = Version;
 	// Use the account data from the balances pallet
 	type AccountData = pallet_balances::AccountData<<Runtime as pallet_balances::Config>::Balance>;
+	// Block transactions while multi-block migrations are in progress
+	type MultiBlockMigrator = MultiBlockMigrations;
+	// Weigh storage accesses as reads and writes of the RocksDB backend, so fees reflect them
+	type DbWeight = RocksDbWeight;
 }
 
 // Implements the types required for the balances pallet.
 #[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
 impl pallet_balances::Config for Runtime {
 	type AccountStore = System;
+	type ExistentialDeposit = ConstU64<{ currency::EXISTENTIAL_DEPOSIT }>;
 }
 
 // Implements the types required for the sudo pallet.
 #[derive_impl(pallet_sudo::config_preludes::TestDefaultConfig)]
 impl pallet_sudo::Config for Runtime {}
 
-// Implements the types required for the sudo pallet.
+// Implements the types required for the timestamp pallet.
 #[derive_impl(pallet_timestamp::config_preludes::TestDefaultConfig)]
-impl pallet_timestamp::Config for Runtime {}
+impl pallet_timestamp::Config for Runtime {
+	// Check every timestamp against the slot of the block
+	type OnTimestampSet = Aura;
+	type MinimumPeriod = ConstU64<{ SLOT_DURATION / 2 }>;
+}
+
+// Implements the types required for the Aura pallet.
+impl pallet_aura::Config for Runtime {
+	type AuthorityId = AuraId;
+	type DisabledValidators = ();
+	type MaxAuthorities = ConstU32<32>;
+	type AllowMultipleBlocksPerSlot = ConstBool<false>;
+	// Twice the timestamp pallet's minimum period, i.e. `SLOT_DURATION`
+	type SlotDuration = pallet_aura::MinimumPeriodTimesTwo<Runtime>;
+}
+
+// Implements the types required for the GRANDPA pallet.
+impl pallet_grandpa::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type MaxAuthorities = ConstU32<32>;
+	type MaxNominators = ConstU32<0>;
+	type MaxSetIdSessionEntries = ConstU64<0>;
+	// Equivocations are not reported, as there is no staking to slash
+	type KeyOwnerProof = sp_core::Void;
+	type EquivocationReportSystem = ();
+}
+
+/// Converts the weight of a transaction into its fee.
+///
+/// The fee is linear in weight: the benchmarked base weight of an extrinsic, i.e. the
+/// cost of the cheapest transaction, is charged a tenth of a milli-unit.
+pub struct WeightToFee;
+
+impl WeightToFeePolynomial for WeightToFee {
+	type Balance = interface::Balance;
+
+	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+		let fee = currency::MILLI_UNIT / 10;
+		let weight = interface::Balance::from(ExtrinsicBaseWeight::get().ref_time());
+		alloc::vec![WeightToFeeCoefficient {
+			degree: 1,
+			negative: false,
+			coeff_frac: sp_runtime::Perbill::from_rational(fee % weight, weight),
+			coeff_integer: fee / weight,
+		}]
+		.into()
+	}
+}
+
+parameter_types! {
+	// Every byte of a transaction costs ten micro-units, so a 4KB credential costs 0.04 units
+	pub const TransactionByteFee: interface::Balance = 10 * currency::MICRO_UNIT;
+	// Fees go up when blocks are more than a quarter full and down when they are less
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	// How quickly the multiplier follows block fullness
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(3, 100_000);
+	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 10u128);
+	pub MaximumMultiplier: Multiplier = Multiplier::saturating_from_integer(100);
+}
 
 // Implements the types required for the transaction payment pallet.
 #[derive_impl(pallet_transaction_payment::config_preludes::TestDefaultConfig)]
 impl pallet_transaction_payment::Config for Runtime {
 	type OnChargeTransaction = pallet_transaction_payment::FungibleAdapter<Balances, ()>;
-	// Setting fee as independent of the weight of the extrinsic for demo purposes
-	type WeightToFee = NoFee<<Self as pallet_balances::Config>::Balance>;
-	// Setting fee as fixed for any length of the call data for demo purposes
-	type LengthToFee = FixedFee<1, <Self as pallet_balances::Config>::Balance>;
+	type WeightToFee = WeightToFee;
+	type LengthToFee = ConstantMultiplier<interface::Balance, TransactionByteFee>;
+	// Scale fees with block fullness, so spamming the chain gets increasingly expensive
+	type FeeMultiplierUpdate = TargetedFeeAdjustment<
+		Self,
+		TargetBlockFullness,
+		AdjustmentVariable,
+		MinimumMultiplier,
+		MaximumMultiplier,
+	>;
 }
 
 // Implements the types required for the template pallet.
@@ -215,6 +597,298 @@ impl pallet_minimal_template::Config for Runtime {}
 // Implements the types required for the freelance credentials pallet.
 impl pallet_freelance_credentials::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	// Only sudo can migrate credentials of an account that can no longer sign
+	type ForceOrigin = frame_system::EnsureRoot<interface::AccountId>;
+	// Owners and issuers with a deactivated DID are rejected
+	type Dids = Did;
+	// Skill credentials are linked to the canonical skills of the taxonomy
+	type Skills = Skills;
+	// Credentials backed by bonded stake weigh more in trust scores
+	type Stakes = ReputationStaking;
+	// Credentials a jury found invalid no longer count towards trust scores
+	type Disputes = Challenges;
+	// At most 10 mints per minute and 100 per hour per account
+	type ShortMintWindow = ConstU32<{ time::MINUTES }>;
+	type MaxMintsPerShortWindow = ConstU32<10>;
+	type LongMintWindow = ConstU32<{ time::HOURS }>;
+	type MaxMintsPerLongWindow = ConstU32<100>;
+}
+
+/// Calls sponsors pay the fees of: everything in the credentials pallet.
+pub struct CredentialCalls;
+
+impl frame_support::traits::Contains<RuntimeCall> for CredentialCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(call, RuntimeCall::FreelanceCredentials(_))
+	}
+}
+
+// Implements the types required for the fee sponsorship pallet.
+impl pallet_fee_sponsorship::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type SponsoredCalls = CredentialCalls;
+}
+
+parameter_types! {
+	// A multisig operation reserves 0.1 units, plus 0.01 units per signatory
+	pub const MultisigDepositBase: interface::Balance = 100 * currency::MILLI_UNIT;
+	pub const MultisigDepositFactor: interface::Balance = 10 * currency::MILLI_UNIT;
+	pub const OrganizationsPalletId: frame_support::PalletId = frame_support::PalletId(*b"ff/orgs_");
+}
+
+// Implements the types required for the multisig pallet.
+impl pallet_multisig::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type DepositBase = MultisigDepositBase;
+	type DepositFactor = MultisigDepositFactor;
+	type MaxSignatories = ConstU32<20>;
+	type WeightInfo = ();
+	type BlockNumberProvider = System;
+}
+
+/// Calls organization issuers make: minting and maintaining credentials.
+pub struct OrganizationIssuerCalls;
+
+impl frame_support::traits::Contains<RuntimeCall> for OrganizationIssuerCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		use pallet_freelance_credentials::Call;
+		matches!(
+			call,
+			RuntimeCall::FreelanceCredentials(
+				Call::mint_credential { .. } |
+					Call::update_credential { .. } |
+					Call::delete_credential { .. }
+			)
+		)
+	}
+}
+
+/// Calls reserved to the approver of an organization: every call but routine credential and
+/// DID management. Listing what admins may do, rather than what they may not, keeps calls
+/// wrapping other calls (proxies, multisigs, nested organization calls) behind the approver,
+/// as well as the calls of pallets added later.
+pub struct OrganizationHighValueCalls;
+
+impl frame_support::traits::Contains<RuntimeCall> for OrganizationHighValueCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		use pallet_did::Call as DidCall;
+		use pallet_freelance_credentials::Call as CredentialsCall;
+		let routine = match call {
+			RuntimeCall::System(
+				frame_system::Call::remark { .. } | frame_system::Call::remark_with_event { .. },
+			) => true,
+			RuntimeCall::FreelanceCredentials(call) => !matches!(
+				call,
+				CredentialsCall::approve_credential_migration { .. } |
+					CredentialsCall::migrate_credentials { .. } |
+					CredentialsCall::purge_all_credentials { .. }
+			),
+			// Handing the DID over or deactivating it stays with the approver
+			RuntimeCall::Did(call) => matches!(
+				call,
+				DidCall::add_verification_method { .. } |
+					DidCall::remove_verification_method { .. } |
+					DidCall::rotate_key { .. } |
+					DidCall::add_service { .. } |
+					DidCall::remove_service { .. }
+			),
+			_ => false,
+		};
+		!routine
+	}
+}
+
+// Implements the types required for the organizations pallet.
+impl pallet_organizations::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type PalletId = OrganizationsPalletId;
+	type MaxMembers = ConstU32<100>;
+	type IssuerCalls = OrganizationIssuerCalls;
+	type HighValueCalls = OrganizationHighValueCalls;
+}
+
+/// What a proxy may do on behalf of the account that added it.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Default,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	sp_runtime::RuntimeDebug,
+	MaxEncodedLen,
+	scale_info::TypeInfo,
+)]
+pub enum ProxyType {
+	/// Any call.
+	#[default]
+	Any,
+	/// Credentials pallet calls only, e.g. for a browser hot key that must never move funds.
+	/// Migration is left out too, as it hands every credential to another account.
+	CredentialManagement,
+	/// Credentials pallet calls that cannot remove credentials from the account: no deletion,
+	/// purge or migration.
+	NonDelete,
+}
+
+impl InstanceFilter<RuntimeCall> for ProxyType {
+	fn filter(&self, call: &RuntimeCall) -> bool {
+		use pallet_freelance_credentials::Call;
+		match self {
+			ProxyType::Any => true,
+			ProxyType::CredentialManagement => matches!(
+				call,
+				RuntimeCall::FreelanceCredentials(call)
+					if !matches!(call, Call::migrate_credentials { .. })
+			),
+			ProxyType::NonDelete => matches!(
+				call,
+				RuntimeCall::FreelanceCredentials(call) if !matches!(
+					call,
+					Call::delete_credential { .. } |
+						Call::purge_all_credentials { .. } |
+						Call::migrate_credentials { .. }
+				)
+			),
+		}
+	}
+
+	fn is_superset(&self, other: &Self) -> bool {
+		match (self, other) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(ProxyType::CredentialManagement, ProxyType::NonDelete) => true,
+			_ => false,
+		}
+	}
+}
+
+parameter_types! {
+	// A proxy reserves 0.1 units, plus 0.01 units per proxy of the account
+	pub const ProxyDepositBase: interface::Balance = 100 * currency::MILLI_UNIT;
+	pub const ProxyDepositFactor: interface::Balance = 10 * currency::MILLI_UNIT;
+	pub const AnnouncementDepositBase: interface::Balance = 100 * currency::MILLI_UNIT;
+	pub const AnnouncementDepositFactor: interface::Balance = 20 * currency::MILLI_UNIT;
+}
+
+// Implements the types required for the proxy pallet.
+impl pallet_proxy::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = ConstU32<32>;
+	type WeightInfo = ();
+	type MaxPending = ConstU32<32>;
+	type CallHasher = sp_runtime::traits::BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+	type BlockNumberProvider = System;
+}
+
+// Implements the types required for the DID pallet.
+impl pallet_did::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+}
+
+// Implements the types required for the skills pallet.
+impl pallet_skills::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	// Only sudo curates the skill taxonomy
+	type CuratorOrigin = frame_system::EnsureRoot<interface::AccountId>;
+	// Credentials of removed skills are unlinked and their holders dropped from the index
+	type OnSkillRemoved = FreelanceCredentials;
+}
+
+// Implements the types required for the marketplace pallet.
+impl pallet_marketplace::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Currency = Balances;
+	// Applicants are checked against their public credentials
+	type Credentials = FreelanceCredentials;
+	// Only sudo settles disputed escrows
+	type ArbitrationOrigin = frame_system::EnsureRoot<interface::AccountId>;
+	type MaxApplications = ConstU32<100>;
+}
+
+parameter_types! {
+	// A credential counts once more in trust scores per 100 units bonded, up to five times more
+	pub const MinCredentialStake: interface::Balance = 10 * currency::UNIT;
+	pub const CredentialBoostUnit: interface::Balance = 100 * currency::UNIT;
+	// Challengers receive half of a slashed stake, and the rest is burned
+	pub const ChallengerReward: sp_runtime::Perbill = sp_runtime::Perbill::from_percent(50);
+}
+
+// Implements the types required for the reputation staking pallet.
+impl pallet_reputation_staking::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type Credentials = FreelanceCredentials;
+	// Juries slash stakes through the challenges pallet, and sudo can do so directly
+	type SlashOrigin = frame_system::EnsureRoot<interface::AccountId>;
+	type MinStake = MinCredentialStake;
+	type BoostUnit = CredentialBoostUnit;
+	type MaxBoost = ConstU32<5>;
+	// Stake stays slashable for a week after unbonding
+	type UnbondingPeriod = ConstU32<{ 7 * 24 * time::HOURS }>;
+	type ChallengerReward = ChallengerReward;
+	// As many as the credentials an account can hold
+	type MaxStakedCredentials = ConstU32<500>;
+}
+
+parameter_types! {
+	// Challenging a credential reserves 10 units, and joining the jury pool 50, of which
+	// jurors forfeit 10 each time they do not vote
+	pub const ChallengeDeposit: interface::Balance = 10 * currency::UNIT;
+	pub const JurorBond: interface::Balance = 50 * currency::UNIT;
+	pub const NoShowPenalty: interface::Balance = 10 * currency::UNIT;
+}
+
+// Implements the types required for the challenges pallet.
+impl pallet_challenges::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type Credentials = FreelanceCredentials;
+	// Upheld challenges slash the stake behind the credential
+	type Stakes = ReputationStaking;
+	// Credentials found invalid leave the skill search index
+	type OnInvalidated = FreelanceCredentials;
+	// No randomness source is available. Juries are drawn blocks after the challenge, so
+	// challengers cannot predict them, though the author of the block before could bias them
+	type Randomness = pallet_challenges::ParentHashRandomness<Runtime>;
+	type ChallengeDeposit = ChallengeDeposit;
+	type JurorBond = JurorBond;
+	type NoShowPenalty = NoShowPenalty;
+	type JurySize = ConstU32<5>;
+	type MaxJurors = ConstU32<1_000>;
+	// Jurors have three days to vote
+	type VotingPeriod = ConstU32<{ 3 * 24 * time::HOURS }>;
+	// Juries are drawn a minute after the challenge, at most 50 per block
+	type DrawDelay = ConstU32<{ time::MINUTES }>;
+	type MaxDrawsPerBlock = ConstU32<50>;
+}
+
+// Implements the types required for the multi-block migrations pallet.
+impl pallet_migrations::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	// Credentials storage can be too large to translate within a single block
+	type Migrations = (pallet_freelance_credentials::migrations::v1::LazyMigrationV0ToV1<Runtime>,);
+	type CursorMaxLen = ConstU32<65_536>;
+	type IdentifierMaxLen = ConstU32<256>;
+	type MigrationStatusHandler = ();
+	type FailedMigrationHandler = frame_support::migrations::FreezeChainOnFailedMigration;
+	type MaxServiceWeight = MbmServiceWeight;
+	type WeightInfo = ();
 }
 
 type Block = frame::runtime::types_common::BlockOf<Runtime, TxExtension>;
@@ -289,14 +963,52 @@ impl_runtime_apis! {
 	}
 
 	impl apis::SessionKeys<Block> for Runtime {
-		fn generate_session_keys(_seed: Option<Vec<u8>>) -> Vec<u8> {
-			Default::default()
+		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
+			SessionKeys::generate(seed)
 		}
 
 		fn decode_session_keys(
-			_encoded: Vec<u8>,
+			encoded: Vec<u8>,
 		) -> Option<Vec<(Vec<u8>, apis::KeyTypeId)>> {
-			Default::default()
+			SessionKeys::decode_into_raw_public_keys(&encoded)
+		}
+	}
+
+	impl sp_consensus_aura::AuraApi<Block, AuraId> for Runtime {
+		fn slot_duration() -> sp_consensus_aura::SlotDuration {
+			sp_consensus_aura::SlotDuration::from_millis(Aura::slot_duration())
+		}
+
+		fn authorities() -> Vec<AuraId> {
+			pallet_aura::Authorities::<Runtime>::get().into_inner()
+		}
+	}
+
+	impl sp_consensus_grandpa::GrandpaApi<Block> for Runtime {
+		fn grandpa_authorities() -> sp_consensus_grandpa::AuthorityList {
+			Grandpa::grandpa_authorities()
+		}
+
+		fn current_set_id() -> sp_consensus_grandpa::SetId {
+			Grandpa::current_set_id()
+		}
+
+		fn submit_report_equivocation_unsigned_extrinsic(
+			_equivocation_proof: sp_consensus_grandpa::EquivocationProof<
+				interface::Hash,
+				interface::BlockNumber,
+			>,
+			_key_owner_proof: sp_consensus_grandpa::OpaqueKeyOwnershipProof,
+		) -> Option<()> {
+			None
+		}
+
+		fn generate_key_ownership_proof(
+			_set_id: sp_consensus_grandpa::SetId,
+			_authority_id: GrandpaId,
+		) -> Option<sp_consensus_grandpa::OpaqueKeyOwnershipProof> {
+			// No key ownership proofs without a session pallet
+			None
 		}
 	}
 
@@ -324,6 +1036,43 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_did::runtime_api::DidApi<Block, interface::AccountId, interface::BlockNumber> for Runtime {
+		fn resolve(
+			did: interface::AccountId,
+		) -> pallet_did::DidDocument<interface::AccountId, interface::BlockNumber> {
+			Did::resolve(did)
+		}
+	}
+
+	impl pallet_freelance_credentials::runtime_api::SkillSearchApi<Block, interface::AccountId> for Runtime {
+		fn freelancers_with_skills(
+			skills: Vec<pallet_freelance_credentials::SkillId>,
+			min_credentials: u32,
+			page: u32,
+			page_size: u32,
+		) -> Vec<pallet_freelance_credentials::SkillMatch<interface::AccountId>> {
+			FreelanceCredentials::freelancers_with_skills(skills, min_credentials, page, page_size)
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	impl frame_try_runtime::TryRuntime<Block> for Runtime {
+		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
+			let weight = RuntimeExecutive::try_runtime_upgrade(checks).unwrap();
+			(weight, <Runtime as frame_system::Config>::BlockWeights::get().max_block)
+		}
+
+		fn execute_block(
+			block: Block,
+			state_root_check: bool,
+			signature_check: bool,
+			select: frame_try_runtime::TryStateSelect,
+		) -> Weight {
+			RuntimeExecutive::try_execute_block(block, state_root_check, signature_check, select)
+				.unwrap()
+		}
+	}
+
 	impl apis::GenesisBuilder<Block> for Runtime {
 		fn build_state(config: Vec<u8>) -> sp_genesis_builder::Result {
 			build_state::<RuntimeGenesisConfig>(config)
@@ -352,6 +1101,7 @@ pub mod interface {
 	pub use frame::runtime::types_common::OpaqueBlock;
 	pub type AccountId = <Runtime as frame_system::Config>::AccountId;
 	pub type Nonce = <Runtime as frame_system::Config>::Nonce;
+	pub type BlockNumber = frame_system::pallet_prelude::BlockNumberFor<Runtime>;
 	pub type Hash = <Runtime as frame_system::Config>::Hash;
 	pub type Balance = <Runtime as pallet_balances::Config>::Balance;
 	pub type MinimumBalance = <Runtime as pallet_balances::Config>::ExistentialDeposit;